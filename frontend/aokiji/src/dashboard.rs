@@ -1,18 +1,44 @@
 //! This file contains the different building blocks that form the dashboard of the Nano shared acconut.
 
-use crate::{AppState, TransactionState, MAIN_CSS, PORT};
+use crate::{AppState, TransactionState, MAIN_CSS};
 use arboard::Clipboard;
 use dioxus::prelude::*;
 use dioxus_material_icons::{MaterialIcon, MaterialIconStylesheet};
-use frost_sig::{
-    client::SignInput,
-    nano::{
-        rpc::{AccountBalance, AccountHistory, AccountInfo, RPCState},
-        sign::{Subtype, UnsignedBlock},
-    },
+use frost_sig::nano::rpc::{AccountInfo, RPCState};
+use routes::{
+    account_to_public_key, check_node, current_time, format_timestamp, get_account_frontier,
+    get_nano_price, get_representatives_online, is_valid_account, nano_to_raw, parse_timestamp,
+    qr_code_svg, raw_to_nano, read_qr_code, watch_account, AccountEvent, AuditEvent, Cadence,
+    CalendarEntry, Network, NetworkSettings, NodeHealth, OutboxStatus, PaymentRequest,
+    PaymentStatus, PayoutBatch, PayoutStatus, PriceMode, PriceSource, ScheduledPayment,
+    SpendingPolicy, StateBlock, TimeWindow, WatchOptions, WorkSourceKind, DAY,
 };
-use routes::{get_nano_price_euro, NanoPriceEuro, NanoPriceResponse};
 use std::time::Duration;
+use wallet_core::{
+    account_balance, account_history, outbox_nodes, precompute_next_work, AccountSettings,
+    DaemonClient, Proposal, Role, Session, SessionStatus, TransactionKind, PORT,
+};
+
+/// Constant value for the time between each question to the daemon about a running session.
+const DAEMON_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Constant value for the time between each poll when the WebSocket isn't available.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Constant value for the time spent polling before reconnecting to the WebSocket.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Constant value for the time between each pass over the outbox.
+const OUTBOX_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Constant value for the time between each check for scheduled payments that became due.
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Constant value for the number of days before and after today shown in the calendar of scheduled payments.
+const CALENDAR_DAYS: u64 = 45;
+
+/// Constant value for the number of online representatives listed in the picker.
+const MAX_REPRESENTATIVES: usize = 20;
 
 /// Constant value for the path of the avatar image.
 const AVATAR: Asset = asset!("/assets/images/avatar.png");
@@ -23,6 +49,112 @@ pub fn Dashboard() -> Element {
     // mutable state that represents the section that is currently selected.
     let mut menu_item = use_signal(|| "account_details".to_string());
 
+    // represents the shared state of the application
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    // a link opened with the wallet is paid from the Transaction section
+    use_effect(move || {
+        if app_state.read().payment_uri.is_some() {
+            menu_item.set("transaction".to_string());
+        }
+    });
+
+    // watch the open account and refresh the sections every time it changes on the network
+    use_future(move || async move {
+        // what was known about a previously open account doesn't apply to this one
+        {
+            let mut app_state = app_state.write();
+            app_state.wallet.known_frontier = None;
+            app_state.wallet.known_balance = None;
+        }
+
+        let (options, work_settings, thresholds) = {
+            let app_state = app_state.peek();
+            let options = WatchOptions {
+                websocket_url: app_state.wallet.websocket_url(),
+                rpc_url: app_state.wallet.node().url,
+                key: app_state.wallet.node().key,
+                account: app_state.wallet.nano_account.clone(),
+                poll_interval: POLL_INTERVAL,
+                reconnect_interval: RECONNECT_INTERVAL,
+            };
+            (
+                options,
+                app_state.wallet.settings.work.clone(),
+                app_state.wallet.settings.network.work_thresholds(),
+            )
+        };
+
+        // compute the work of the next block after each confirmed transaction so it is instant to sign
+        let precompute = {
+            let options = options.clone();
+            let work_cache = app_state.peek().wallet.work_cache.clone();
+            move || {
+                if work_settings.precompute {
+                    tokio::spawn(precompute_next_work(
+                        options.rpc_url.clone(),
+                        options.key.clone(),
+                        options.account.clone(),
+                        work_settings.clone(),
+                        work_cache.clone(),
+                        thresholds,
+                    ));
+                }
+            }
+        };
+        precompute();
+
+        let account = options.account.clone();
+        watch_account(options, move |event| {
+            let mut state = app_state.write();
+            match event {
+                AccountEvent::Confirmation(confirmation) if confirmation.account == account => {
+                    state.wallet.known_balance = confirmation
+                        .block
+                        .and_then(|block| StateBlock::from_json(&block).ok())
+                        .map(|block| block.balance);
+                    state.wallet.known_frontier = Some(confirmation.hash);
+                }
+                AccountEvent::FrontierChanged(frontier) => {
+                    state.wallet.known_balance = None;
+                    state.wallet.known_frontier = Some(frontier);
+                }
+                _ => {}
+            }
+            state.account_updates += 1;
+            drop(state);
+            precompute();
+        })
+        .await;
+    });
+
+    // keep publishing the signed blocks that didn't reach the network and detect their confirmation
+    use_future(move || async move {
+        loop {
+            let outbox = app_state.peek().wallet.outbox.clone();
+            let nodes = outbox_nodes(&app_state.peek().wallet);
+            match outbox.process(&nodes).await {
+                Ok(changed) => {
+                    let mut state = app_state.write();
+                    state.outbox_error = None;
+                    if changed {
+                        state.account_updates += 1;
+                    }
+                }
+                Err(e) => app_state.write().outbox_error = Some(e),
+            }
+            tokio::time::sleep(OUTBOX_INTERVAL).await;
+        }
+    });
+
+    // look again for scheduled payments that became due
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(SCHEDULE_INTERVAL).await;
+            app_state.write().schedule_updates += 1;
+        }
+    });
+
     rsx! {
         document::Link { rel: "stylesheet", href: MAIN_CSS }
         MaterialIconStylesheet{}
@@ -39,14 +171,23 @@ pub fn Dashboard() -> Element {
                     button { id: "menu-button", onclick: move |_| menu_item.set("transaction".to_string()), "Transaction" }
                     div { style: "display: inline-block; margin-left: 14px;" }
                     button { id: "menu-button", onclick: move |_| menu_item.set("history".to_string()), "History" }
+                    div { style: "display: inline-block; margin-left: 14px;" }
+                    button { id: "menu-button", onclick: move |_| menu_item.set("schedule".to_string()), "Schedule" }
+                    div { style: "display: inline-block; margin-left: 14px;" }
+                    button { id: "menu-button", onclick: move |_| menu_item.set("audit".to_string()), "Audit" }
                 }
             }
             div { style: "display: inline-block; margin-bottom: 14px;" }
+            if menu_item() != "transaction" {
+                DuePaymentsNotice { menu_item }
+            }
             match menu_item.to_string().as_str() {
                 "account_details" => {
                     rsx! {
                         Balance{}
                         div { style: "display: inline-block; margin-bottom: 14px;" }
+                        PaymentRequestSection{}
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
                         PublicShare{}
                         div { style: "display: inline-block; margin-bottom: 14px;" }
                         AccountInfoSection {  }
@@ -54,18 +195,37 @@ pub fn Dashboard() -> Element {
                 },
                 "transaction" => {
                     rsx! {
+                        DuePaymentsSection{}
                         StartTransaction{}
                         div { style: "display: inline-block; margin-bottom: 14px;" }
                         JoinTransaction{}
                         div { style: "display: inline-block; margin-bottom: 14px;" }
+                        SpendingPolicySection{}
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        AccountSettingsSection{}
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
                         TransactionConfig{}
                     }
                 },
                 "history" => {
                     rsx! {
+                        OutboxSection{}
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
                         Transactions{}
                     }
                 },
+                "schedule" => {
+                    rsx! {
+                        ScheduledPaymentForm{}
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        PaymentCalendar{}
+                    }
+                },
+                "audit" => {
+                    rsx! {
+                        AuditSection{}
+                    }
+                },
                 _ => rsx!{}
             }
         }
@@ -78,48 +238,42 @@ fn Balance() -> Element {
     // represents the shared state of the application
     let app_state = use_context::<Signal<AppState>>();
 
-    // nano account stored in the app state
-    let account = app_state.read().nano_account.clone();
-
-    // closure that gets the balance from the RPC
+    // closure that gets the balance from the RPC (or from the daemon)
     let balance_future = use_resource(move || {
-        let account = account.clone();
-        let config = app_state.read().config_file.clone();
-        let state = RPCState::new(&config.url);
-        async move { AccountBalance::get_from_rpc(&state, &account, &config.key).await }
+        let _ = app_state.read().account_updates;
+        let wallet = app_state.read().wallet.clone();
+        async move { account_balance(&wallet).await }
     });
-    let balance_info: AccountBalance = match &*balance_future.read_unchecked() {
-        Some(res) => match res {
-            Ok(b) => (*b).clone(),
-            Err(_) => AccountBalance::default(),
-        },
-        None => AccountBalance::default(),
+    let (balance, receivable) = match &*balance_future.read_unchecked() {
+        Some(Ok(b)) => (b.balance.clone(), b.receivable.clone()),
+        _ => (String::new(), String::new()),
     };
 
-    // closure that gets the updated nano price
-    let nano_price_future = use_resource(|| async { get_nano_price_euro().await });
+    // closure that gets the updated nano price from the sources selected in the settings
+    let nano_price_future = use_resource(move || {
+        let price_settings = app_state.read().wallet.price_settings();
+        async move { get_nano_price(&price_settings).await }
+    });
     let nano_price = match &*nano_price_future.read_unchecked() {
-        Some(res) => (*res).clone(),
-        None => NanoPriceResponse {
-            nano: Some(NanoPriceEuro { eur: Some(0.) }),
-        },
+        Some(price) => *price,
+        None => Some(0.),
     };
 
-    let balance_nano = match balance_info.balance_nano.parse::<f32>() {
+    let balance_nano = match balance.parse::<f32>() {
         Ok(b) => b,
         Err(_) => 0.,
     };
-    let receivable_nano = match balance_info.receivable_nano.parse::<f32>() {
+    let receivable_nano = match receivable.parse::<f32>() {
         Ok(b) => b,
         Err(_) => 0.,
     };
-    let nano_price = match nano_price.nano {
-        Some(nano) => match nano.eur {
-            Some(price) => price,
-            None => 0.,
-        },
-        None => 0.,
-    };
+    // the account may use its own currency
+    let currency = app_state
+        .read()
+        .wallet
+        .price_settings()
+        .currency
+        .to_uppercase();
 
     rsx! {
         div {
@@ -134,10 +288,13 @@ fn Balance() -> Element {
                 }
                 div {
                     id: "fill-card",
-                    span { id: "secondary" , "~EUR" }
+                    span { id: "secondary" , "~{currency}" }
                     div {
                         id: "secondary" ,
-                        strong { id: "sub-heading" , {format!("{:.2}", nano_price * balance_nano)} {"€"} }
+                        match nano_price {
+                            Some(price) => rsx! { strong { id: "sub-heading" , {format!("{:.2}", price * balance_nano as f64)} } },
+                            None => rsx! { span { "Price unavailable" } },
+                        }
                     }
                 }
             }
@@ -163,27 +320,35 @@ fn Balance() -> Element {
     }
 }
 
+/// Function that copies a text to the clipboard of the user.
+fn copy_to_clipboard(text: String) {
+    if let Ok(mut clipboard) = Clipboard::new() {
+        let _ = clipboard.set_text(text);
+    }
+}
+
+/// Function that represents a QR code with the given text.
+#[component]
+fn QrCode(text: String) -> Element {
+    match qr_code_svg(&text) {
+        Ok(svg) => rsx! {
+            div { class: "qr-code", dangerous_inner_html: svg }
+        },
+        Err(e) => rsx! {
+            span { id: "secondary", "{e}" }
+        },
+    }
+}
+
 /// Function that represents the header section of the dashboard.
 #[component]
 fn Header() -> Element {
     // represents the shared state of the application
     let app_state = use_context::<Signal<AppState>>();
 
-    // closure that copies the nano account to the clipboard of the user
-    let copy_to_clipboard = move |_| {
-        let mut clipboard = match Clipboard::new() {
-            Ok(clipboard) => clipboard,
-            Err(_) => return,
-        };
-
-        // nano account stored in the app state
-        let account = app_state.read().nano_account.clone();
-
-        match clipboard.set_text(account) {
-            Ok(_) => {}
-            Err(_) => return,
-        }
-    };
+    // mutable state that represents whether the QR code of the account is shown
+    let mut show_qr_code = use_signal(|| false);
+    let account_uri = PaymentRequest::new(&app_state.read().wallet.nano_account).to_uri();
 
     rsx! {
         div {
@@ -203,420 +368,760 @@ fn Header() -> Element {
                         style: "display: flex; align-items: center; gap: 0px;",
                         a {
                             class: "nano-account",
-                            { app_state.read().nano_account.clone() }
+                            { app_state.read().wallet.nano_account.clone() }
                         }
                         button {
                             class: "clipboard",
-                            onclick: copy_to_clipboard,
+                            // copies the nano account to the clipboard of the user
+                            onclick: move |_| copy_to_clipboard(app_state.read().wallet.nano_account.clone()),
                             style: "font-size: 20px; margin-left: -8px;",
                             MaterialIcon { name: "content_copy" }
                         }
+                        button {
+                            class: "clipboard",
+                            onclick: move |_| show_qr_code.toggle(),
+                            style: "font-size: 20px; margin-left: -8px;",
+                            MaterialIcon { name: "qr_code" }
+                        }
                     }
                     div { id:"secondary", a { {
                         // FROST parameters stored in the app state
-                        let frost_state = app_state.read().frost_state.clone();
+                        let frost_state = app_state.read().wallet.frost_state.clone();
                         format!("{} Participants", frost_state.participants)
                     } } }
                 }
             }
         }
+        if show_qr_code() {
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            QrCode { text: account_uri }
+        }
     }
 }
 
-/// Function that represents the Start Transaction section of the account's dashboard.
+/// Function that represents the section that creates a payment request for the account, shown as a QR code.
 #[component]
-fn StartTransaction() -> Element {
-    // mutable state that represents the type of transaction selected
-    let mut transaction_type = use_signal(|| "SEND".to_string());
-
-    // mutable state that represents the account that will receive the sent Nano
-    let mut receivers_account = use_signal(|| "".to_string());
-
-    // mutable state that represents the amount of Nano sent
-    let mut amount = use_signal(|| "0".to_string());
-
-    // mutable synchronous state that represents the state of the transaction in real-time
-    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
-
-    // represents the shared state of the application
+fn PaymentRequestSection() -> Element {
     let app_state = use_context::<Signal<AppState>>();
 
-    // closure that opens the socket that will be used for the transaction and also connects as a client
-    let open_socket_and_connect = move |_| {
-        use_future(move || async move {
-            let url = app_state.read().config_file.url.clone();
-            let state = RPCState::new(&url);
-            let account = app_state.read().nano_account.clone();
-            let path = app_state.read().account_path.clone();
-            let config = app_state.read().config_file.clone();
-
-            // building the unsigned block according to the type of transaction
-            let unsigned_block = match transaction_type.read().as_str() {
-                "OPEN" => match UnsignedBlock::create_open(&state, &account, &config.key).await {
-                    Ok(block) => block,
-                    Err(_) => {
-                        transaction_state.set(TransactionState::Error(
-                            "Couldn't create the block.".to_string(),
-                        ));
-                        return;
-                    }
-                },
-                "RECEIVE" => {
-                    match UnsignedBlock::create_receive(&state, &account, &config.key).await {
-                        Ok(block) => block,
-                        Err(_) => {
-                            transaction_state.set(TransactionState::Error(
-                                "Couldn't create the block.".to_string(),
-                            ));
-                            return;
-                        }
-                    }
-                }
-                _ => match UnsignedBlock::create_send(
-                    &state,
-                    &account,
-                    &receivers_account.read(),
-                    &amount.read().parse::<f64>().unwrap_or(0.),
-                    &config.key,
-                )
-                .await
-                {
-                    Ok(block) => block,
-                    Err(_) => {
-                        transaction_state.set(TransactionState::Error(
-                            "Couldn't create the block.".to_string(),
-                        ));
-                        return;
-                    }
-                },
-            };
-
-            // getting the sign input from the path stored in the app state
-            let mut sign_input = match SignInput::from_file(&path).await {
-                Ok(input) => input,
-                Err(e) => {
-                    transaction_state.set(TransactionState::Error(e.to_string()));
-                    return;
-                }
-            };
-            sign_input.subtype = match transaction_type.read().as_str() {
-                "RECEIVE" => Subtype::RECEIVE,
-                "OPEN" => Subtype::OPEN,
-                _ => Subtype::SEND,
-            };
-            sign_input.message = unsigned_block;
-            match sign_input.to_file(&path).await {
-                Ok(_) => {}
-                Err(e) => {
-                    transaction_state.set(TransactionState::Error(e.to_string()));
-                    return;
-                }
-            };
-
-            // notify the user of the state of the transaction
-            transaction_state.set(TransactionState::Processing);
-
-            // get the frost state from the shared app state
-            let state = app_state.read().frost_state.clone();
-
-            // get the path of the sign input file from the shared app state
-            let path = app_state.read().account_path.clone();
+    // mutable state that represents the amount requested in Nano (empty for any amount)
+    let mut amount = use_signal(|| "".to_string());
 
-            // get the path of the config file from the shared app state
-            let config_file_path = app_state.read().config_file_path.clone();
-
-            // open the socket with the correct parameters
-            let server = tokio::spawn(async move {
-                match frost_sig::server::sign_server::run(
-                    "localhost",
-                    PORT,
-                    state.participants,
-                    state.threshold,
-                )
-                .await
-                {
-                    Ok(_) => {}
-                    Err(e) => {
-                        transaction_state.set(TransactionState::Error(e.to_string()));
-                        return;
-                    }
-                };
-            });
-
-            // connect to the socket that was opened
-            let client = tokio::spawn(async move {
-                tokio::time::sleep(Duration::from_secs(2)).await;
-                match frost_sig::client::sign_client::run(
-                    "localhost",
-                    PORT,
-                    &path,
-                    &config_file_path,
-                )
-                .await
-                {
-                    Ok(_) => {}
-                    Err(e) => {
-                        transaction_state.set(TransactionState::Error(e.to_string()));
-                        return;
-                    }
-                };
-            });
+    // mutable states that represent who asks for the payment and what it is for (both optional)
+    let mut label = use_signal(|| "".to_string());
+    let mut message = use_signal(|| "".to_string());
 
-            // after processing the transaction notify user
-            tokio::spawn(async move {
-                let _ = tokio::join!(server, client);
-                transaction_state.set(TransactionState::Successful);
-            });
-        });
+    let optional = |text: String| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+    let request = PaymentRequest {
+        label: optional(label()),
+        message: optional(message()),
+        ..PaymentRequest::new(&app_state.read().wallet.nano_account)
+    };
+    let request = match amount().trim() {
+        "" => Ok(request),
+        requested => match nano_to_raw(requested) {
+            Some(raw) if raw > 0 => Ok(PaymentRequest {
+                amount: Some(raw),
+                ..request
+            }),
+            _ => Err(format!("{requested} is not a valid amount.")),
+        },
     };
 
     rsx! {
         div {
             id: "card",
-            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "START TRANSACTION" }
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "PAYMENT REQUEST" }
             div {
                 id: "column-section",
-                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Type:" }
-                select {
-                    id: "select",
-                    onchange: move |event| transaction_type.set(event.value()),
-                    option { value: "SEND", "SEND" }
-                    option { value: "RECEIVE", "RECEIVE" }
-                    option { value: "OPEN", "OPEN" }
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Amount (XNO, empty for any):" }
+                input {
+                    id: "input",
+                    value: "{amount}",
+                    oninput: move |event| amount.set(event.value()),
                 }
             }
-            match transaction_type.to_string().as_str() {
-                "SEND" => {
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Label (optional):" }
+                input {
+                    id: "input",
+                    value: "{label}",
+                    oninput: move |event| label.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Message (optional):" }
+                input {
+                    id: "input",
+                    value: "{message}",
+                    oninput: move |event| message.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            match request {
+                Ok(request) => {
+                    let uri = request.to_uri();
+                    let copied = uri.clone();
                     rsx! {
+                        QrCode { text: uri.clone() }
                         div { style: "display: inline-block; margin-bottom: 14px;" }
                         div {
-                            id: "column-section",
-                            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Receiver's Account:" }
-                            input {
-                                id: "input",
-                                onchange: move |event| receivers_account.set(event.value()),
-                            }
-                        }
-                        div { style: "display: inline-block; margin-bottom: 14px;" }
-                        div {
-                            id: "column-section",
-                            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Amount (XNO):" }
-                            input {
-                                id: "input",
-                                value: "0",
-                                r#type: "number",
-                                min: "0",
-                                onchange: move |event| amount.set(event.value()),
-
+                            id: "fill-card",
+                            span { id: "secondary", style: "text-overflow: ellipsis; max-width: 380px; white-space: nowrap; overflow: hidden;", "{uri}" }
+                            button {
+                                class: "clipboard",
+                                onclick: move |_| copy_to_clipboard(copied.clone()),
+                                style: "font-size: 20px;",
+                                MaterialIcon { name: "content_copy" }
                             }
                         }
                     }
                 }
-                _ => {
-                    rsx! {
-                    }
-                }
+                Err(e) => rsx! {
+                    span { id: "secondary", "{e}" }
+                },
             }
-            {
-                match *transaction_state.read() {
-                    TransactionState::Processing => {
-                        rsx! {
-                            div { style: "display: inline-block; margin-bottom: 14px;" }
-                            span { id: "secondary", "Processing the transaction..." }
-                        }
-                    }
-                    TransactionState::Successful => {
-                        rsx! {
-                            div { style: "display: inline-block; margin-bottom: 14px;" }
-                            span { id: "secondary", "Transaction was successful." }
-                        }
-                    }
-                    TransactionState::Error(ref e) => {
-                        rsx! {
-                            div { style: "display: inline-block; margin-bottom: 14px;" }
-                            span { id: "secondary", "{e}" }
-                        }
-                    }
-                    _ => {
-                        rsx!{}
-                    }
-                }
+        }
+    }
+}
+
+/// Function that represents the field of the SEND form that reads a payment request, pasted as a `nano:` URI,
+/// decoded from an image of its QR code or opened from a link, and fills the receiver and the amount with it.
+#[component]
+fn PaymentRequestInput(receivers_account: Signal<String>, amount: Signal<String>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    // mutable state that represents why the last payment request couldn't be used
+    let mut error = use_signal(|| None::<String>);
+
+    // mutable state that represents the label and message of the payment request being paid
+    let mut description = use_signal(|| None::<String>);
+
+    // the account must belong to the wallet's network and the amount in raw is written exactly in Nano
+    let mut fill = move |text: Result<String, String>| match text
+        .and_then(|text| app_state.peek().wallet.payment_request(&text))
+    {
+        Ok(request) => {
+            description.set(request.description());
+            if let Some(requested) = request.amount_in_nano() {
+                amount.set(requested);
             }
-            div { style: "display: inline-block; margin-bottom: 36px;" }
+            receivers_account.set(request.account);
+            error.set(None);
+        }
+        Err(e) => {
+            description.set(None);
+            error.set(Some(e));
+        }
+    };
+
+    // a link opened with the wallet is paid by the open account
+    use_effect(move || {
+        if app_state.read().payment_uri.is_some() {
+            let payment_uri = app_state.write().payment_uri.take();
+            if let Some(payment_uri) = payment_uri {
+                fill(Ok(payment_uri));
+            }
+        }
+    });
+
+    rsx! {
+        div {
+            id: "column-section",
+            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Payment Request (optional):" }
             div {
-                id: "column-section",
-                button {
-                    id: "button",
-                    disabled: match *transaction_state.read() {
-                        TransactionState::Idle | TransactionState::Error(_) => match (receivers_account().as_str(), transaction_type().as_str()) {
-                            ("", "SEND") => true,
-                            _ => false
-                        },
-                        _ => true,
+                style: "display: flex; align-items: center; gap: 8px;",
+                input {
+                    id: "input",
+                    style: "flex: 1;",
+                    placeholder: "nano:...",
+                    onchange: move |event| {
+                        if !event.value().trim().is_empty() {
+                            fill(Ok(event.value()));
+                        }
                     },
-                    onclick: open_socket_and_connect,
-                    "Start",
                 }
+                label {
+                    class: "clipboard",
+                    style: "font-size: 24px; cursor: pointer;",
+                    MaterialIcon { name: "qr_code_scanner" }
+                    input {
+                        r#type: "file",
+                        accept: "image/*",
+                        style: "display: none;",
+                        onchange: move |event| {
+                            let Some(file) = event.files().and_then(|files| files.files().into_iter().next()) else {
+                                return;
+                            };
+                            fill(
+                                std::fs::read(&file)
+                                    .map_err(|e| format!("The image couldn't be opened: {e}"))
+                                    .and_then(|image| read_qr_code(&image)),
+                            );
+                        },
+                    }
+                }
+            }
+            if let Some(description) = description() {
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                span { id: "secondary", "{description}" }
+            }
+            if let Some(e) = error() {
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                span { id: "secondary", "{e}" }
             }
         }
     }
 }
 
-#[component]
-fn JoinTransaction() -> Element {
-    let mut transaction_type = use_signal(|| "SEND".to_string());
-    let mut receivers_account = use_signal(|| "".to_string());
-    let mut ip_address = use_signal(|| "".to_string());
-    let mut amount = use_signal(|| "0".to_string());
+/// Function that returns the proposal for the selected type of transaction (`None` for RECEIVE ALL).
+fn proposal(
+    transaction_type: &str,
+    receivers_account: &str,
+    amount: &str,
+    representative: &str,
+) -> Option<Proposal> {
+    Some(Proposal {
+        kind: TransactionKind::from_name(transaction_type)?,
+        receivers_account: receivers_account.to_string(),
+        amount: amount.to_string(),
+        representative: representative.to_string(),
+    })
+}
 
-    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
+/// Function that runs a signing session with the group and shows its progress, returning the hash of the signed block.
+/// Without a proposal every receivable block is received with a chain the coordinator builds and shares first.
+async fn run_session(
+    mut app_state: Signal<AppState>,
+    role: Role,
+    proposal: Option<Proposal>,
+    mut transaction_state: SyncSignal<TransactionState>,
+    mut received_blocks: Signal<Vec<(String, String)>>,
+) -> Option<String> {
+    let mut wallet = app_state.peek().wallet.clone();
+    received_blocks.set(Vec::new());
 
-    let app_state = use_context::<Signal<AppState>>();
+    // sends and joins go through the daemon when the wallet uses one
+    if let (Some(client), Some(proposal)) = (DaemonClient::from_wallet(&wallet), &proposal) {
+        if matches!(
+            (&role, proposal.kind),
+            (Role::Coordinator, TransactionKind::Send) | (Role::Participant(_), _)
+        ) {
+            return run_daemon_session(
+                client,
+                &wallet.nano_account,
+                &role,
+                proposal,
+                transaction_state,
+            )
+            .await;
+        }
+    }
 
-    let connect_to_socket = move |_| {
-        use_future(move || async move {
-            let url = app_state.read().config_file.url.clone();
-            let state = RPCState::new(&url);
-            let account = app_state.read().nano_account.clone();
-            let path = app_state.read().account_path.clone();
-            let config = app_state.read().config_file.clone();
-            let unsigned_block = match transaction_type.read().as_str() {
-                "OPEN" => match UnsignedBlock::create_open(&state, &account, &config.key).await {
-                    Ok(block) => block,
-                    Err(_) => {
-                        transaction_state.set(TransactionState::Error(
-                            "Couldn't create the block.".to_string(),
-                        ));
-                        return;
+    let (session, mut status) = Session::new();
+
+    // follow the session until it ends
+    let receiving_all = proposal.is_none();
+    spawn(async move {
+        while let Some(status) = status.recv().await {
+            match status {
+                SessionStatus::Preparing | SessionStatus::Signing { .. } => {
+                    transaction_state.set(TransactionState::Processing)
+                }
+                SessionStatus::Signed { hash, amount } if receiving_all => {
+                    received_blocks.write().push((hash, amount.to_string()))
+                }
+                SessionStatus::Finished => transaction_state.set(TransactionState::Successful),
+                SessionStatus::Failed(e) => transaction_state.set(TransactionState::Error(e)),
+                SessionStatus::Signed { .. } => {}
+            }
+        }
+    });
+
+    let hash = match &proposal {
+        Some(proposal) => session.sign(&mut wallet, &role, proposal).await.ok(),
+        // the received blocks were already shown as they were signed
+        None => {
+            let _ = session.receive_all(&mut wallet, &role).await;
+            None
+        }
+    };
+
+    // the blocks signed in the session are now the frontier known by the wallet
+    let mut app_state = app_state.write();
+    app_state.wallet.known_frontier = wallet.known_frontier;
+    app_state.wallet.known_balance = wallet.known_balance;
+    hash
+}
+
+/// Function that signs a proposal through the daemon and shows its progress until the session ends, returning the hash
+/// of the signed block.
+async fn run_daemon_session(
+    client: DaemonClient,
+    account: &str,
+    role: &Role,
+    proposal: &Proposal,
+    mut transaction_state: SyncSignal<TransactionState>,
+) -> Option<String> {
+    transaction_state.set(TransactionState::Processing);
+
+    let mut session = match client.sign(account, role, proposal).await {
+        Ok(session) => session,
+        Err(e) => {
+            transaction_state.set(TransactionState::Error(e));
+            return None;
+        }
+    };
+    while !session.status.is_finished() {
+        tokio::time::sleep(DAEMON_POLL_INTERVAL).await;
+        session = match client.session_status(session.session_id).await {
+            Ok(session) => session,
+            Err(e) => {
+                transaction_state.set(TransactionState::Error(e));
+                return None;
+            }
+        };
+    }
+
+    match session.status {
+        SessionStatus::Failed(e) => {
+            transaction_state.set(TransactionState::Error(e));
+            None
+        }
+        _ => {
+            transaction_state.set(TransactionState::Successful);
+            session.hash
+        }
+    }
+}
+
+/// Function that signs every payout of a batch with the group and shows the result of each row.
+/// The coordinator builds the chain of send blocks from its file and shares it first, and each block is then signed in
+/// its own round.
+async fn run_batch(
+    mut app_state: Signal<AppState>,
+    role: Role,
+    batch: PayoutBatch,
+    mut transaction_state: SyncSignal<TransactionState>,
+    mut payout_statuses: Signal<Vec<PayoutStatus>>,
+) {
+    let mut wallet = app_state.peek().wallet.clone();
+    payout_statuses.set(vec![PayoutStatus::Pending; batch.payouts.len()]);
+
+    let (session, mut status) = Session::new();
+
+    // follow the session until it ends, the rows being signed in order
+    spawn(async move {
+        let mut row = 0;
+        while let Some(status) = status.recv().await {
+            match status {
+                SessionStatus::Preparing => transaction_state.set(TransactionState::Processing),
+                SessionStatus::Signing { .. } => {
+                    if let Some(status) = payout_statuses.write().get_mut(row) {
+                        *status = PayoutStatus::Signing;
                     }
-                },
-                "RECEIVE" => {
-                    match UnsignedBlock::create_receive(&state, &account, &config.key).await {
-                        Ok(block) => block,
-                        Err(_) => {
-                            transaction_state.set(TransactionState::Error(
-                                "Couldn't create the block.".to_string(),
-                            ));
-                            return;
+                }
+                SessionStatus::Signed { hash, .. } => {
+                    if let Some(status) = payout_statuses.write().get_mut(row) {
+                        *status = PayoutStatus::Sent { hash };
+                    }
+                    row += 1;
+                }
+                SessionStatus::Finished => transaction_state.set(TransactionState::Successful),
+                SessionStatus::Failed(e) => {
+                    // the row being signed failed and the ones after it won't be sent
+                    for status in payout_statuses.write().iter_mut() {
+                        match status {
+                            PayoutStatus::Signing => *status = PayoutStatus::Failed(e.clone()),
+                            PayoutStatus::Pending => *status = PayoutStatus::NotSent,
+                            _ => {}
                         }
                     }
+                    transaction_state.set(TransactionState::Error(e));
                 }
-                _ => match UnsignedBlock::create_send(
-                    &state,
-                    &account,
-                    &receivers_account.read(),
-                    &amount.read().parse::<f64>().unwrap_or(0.),
-                    &config.key,
-                )
-                .await
-                {
-                    Ok(block) => block,
-                    Err(_) => {
-                        transaction_state.set(TransactionState::Error(
-                            "Couldn't create the block.".to_string(),
-                        ));
+            }
+        }
+    });
+
+    let _ = session.send_batch(&mut wallet, &role, &batch).await;
+
+    // the blocks signed in the session are now the frontier known by the wallet
+    let mut app_state = app_state.write();
+    app_state.wallet.known_frontier = wallet.known_frontier;
+    app_state.wallet.known_balance = wallet.known_balance;
+}
+
+/// Function that represents the form of a BATCH transaction: the CSV file of payouts, the problems found in its rows,
+/// their total and the balance left, and the result of each row once the group signs them.
+#[component]
+fn PayoutBatchInput(
+    batch: Signal<Option<PayoutBatch>>,
+    payout_statuses: Signal<Vec<PayoutStatus>>,
+) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    // mutable state that represents why the last file couldn't be read
+    let mut error = use_signal(|| None::<String>);
+
+    // closure that gets the balance the payouts are checked against
+    let balance_future = use_resource(move || {
+        let _ = app_state.read().account_updates;
+        let wallet = app_state.read().wallet.clone();
+        async move { account_balance(&wallet).await }
+    });
+    let balance = match &*balance_future.read_unchecked() {
+        Some(Ok(b)) => nano_to_raw(&b.balance),
+        _ => None,
+    };
+
+    // until the balance is known only the rows are checked
+    let problems = batch
+        .read()
+        .as_ref()
+        .map(|batch| batch.validate(balance.unwrap_or(u128::MAX)))
+        .unwrap_or_default();
+    let total = batch.read().as_ref().and_then(PayoutBatch::total);
+    let balance_after = batch
+        .read()
+        .as_ref()
+        .zip(balance)
+        .and_then(|(batch, balance)| batch.balance_after(balance));
+
+    rsx! {
+        div { style: "display: inline-block; margin-bottom: 14px;" }
+        div {
+            id: "column-section",
+            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Payouts (CSV with address,amount rows):" }
+            input {
+                id: "input",
+                r#type: "file",
+                accept: ".csv,text/csv,text/plain",
+                onchange: move |event| {
+                    let Some(file) = event.files().and_then(|files| files.files().into_iter().next()) else {
                         return;
+                    };
+                    payout_statuses.set(Vec::new());
+                    match std::fs::read_to_string(&file)
+                        .map_err(|e| format!("The file couldn't be opened: {e}"))
+                        .and_then(|csv| PayoutBatch::parse(&csv))
+                    {
+                        Ok(parsed) => {
+                            batch.set(Some(parsed));
+                            error.set(None);
+                        }
+                        Err(e) => {
+                            batch.set(None);
+                            error.set(Some(e));
+                        }
                     }
                 },
-            };
-            let mut sign_input = match SignInput::from_file(&path).await {
-                Ok(input) => input,
-                Err(e) => {
-                    transaction_state.set(TransactionState::Error(e.to_string()));
-                    return;
-                }
-            };
-            sign_input.subtype = match transaction_type.read().as_str() {
-                "RECEIVE" => Subtype::RECEIVE,
-                "OPEN" => Subtype::OPEN,
-                _ => Subtype::SEND,
-            };
-            sign_input.message = unsigned_block;
-            match sign_input.to_file(&path).await {
-                Ok(_) => {}
-                Err(e) => {
-                    transaction_state.set(TransactionState::Error(e.to_string()));
-                    return;
+            }
+            if let Some(e) = error() {
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                span { id: "secondary", "{e}" }
+            }
+        }
+        if let Some(batch) = batch() {
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            for (index, payout) in batch.payouts.iter().enumerate() {
+                div {
+                    id: "fill-card",
+                    span { id: "secondary", style: "text-overflow: ellipsis; max-width: 200px; white-space: nowrap; overflow: hidden;", {payout.receivers_account.clone()} }
+                    strong { id: "sub-heading", {format!("{} XNO", payout.amount)} }
+                    span {
+                        id: "secondary",
+                        {match payout_statuses.read().get(index) {
+                            Some(PayoutStatus::Failed(e)) => format!("Failed: {e}"),
+                            Some(status) => status.name().to_string(),
+                            None if payout.validate().is_empty() => "Ready".to_string(),
+                            None => "Invalid".to_string(),
+                        }}
+                    }
                 }
-            };
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+            }
+            span {
+                id: "sub-heading",
+                {match total {
+                    Some(total) => format!("Total: {} XNO ({} payouts)", raw_to_nano(total), batch.payouts.len()),
+                    None => "Total: -".to_string(),
+                }}
+            }
+            if let Some(balance_after) = balance_after {
+                span { id: "secondary", {format!("Balance after: {} XNO", raw_to_nano(balance_after))} }
+            }
+            for problem in problems {
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                span { id: "secondary", "{problem}" }
+            }
+        }
+    }
+}
+
+/// Function that represents the list of blocks received in a RECEIVE ALL session.
+#[component]
+fn ReceivedBlocks(received_blocks: Signal<Vec<(String, String)>>) -> Element {
+    rsx! {
+        for (hash, amount) in received_blocks.read().iter() {
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "fill-card",
+                span { id: "secondary" , style: "text-overflow: ellipsis;
+                  max-width: 200px; white-space: nowrap;
+                    overflow: hidden;", {hash.clone()} }
+                strong { id: "sub-heading" , {format!("{} XNO", amount.parse().map(raw_to_nano).unwrap_or(amount.clone()))} }
+            }
+        }
+    }
+}
 
-            transaction_state.set(TransactionState::Processing);
+/// Function that explains that the account will be opened by its first receive, when it isn't opened yet.
+#[component]
+fn UnopenedAccountNotice() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
 
-            let path = app_state.read().account_path.clone();
-            let config_file_path = app_state.read().config_file_path.clone();
-            let ip_address = ip_address.read().clone();
+    let frontier_future = use_resource(move || async move {
+        let _ = app_state.read().account_updates;
+        let config = app_state.read().wallet.node();
+        let account = app_state.read().wallet.nano_account.clone();
+        get_account_frontier(&config.url, &config.key, &account).await
+    });
 
-            let client = tokio::spawn(async move {
-                tokio::time::sleep(Duration::from_secs(2)).await;
-                match frost_sig::client::sign_client::run(
-                    &ip_address,
-                    PORT,
-                    &path,
-                    &config_file_path,
-                )
-                .await
-                {
-                    Ok(_) => {}
-                    Err(e) => {
-                        transaction_state.set(TransactionState::Error(e.to_string()));
-                        return;
-                    }
-                };
-            });
+    match &*frontier_future.read_unchecked() {
+        Some(Ok(None)) => rsx! {
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            span { id: "secondary", "This account hasn't been opened yet. Its first RECEIVE will open it." }
+        },
+        _ => rsx! {},
+    }
+}
 
-            tokio::spawn(async move {
-                match client.await {
-                    Ok(_) => {
-                        if !matches!(*transaction_state.read(), TransactionState::Error(_)) {
-                            transaction_state.set(TransactionState::Successful);
+/// Function that represents the picker of the new representative, with the representatives that are online.
+#[component]
+fn RepresentativePicker(representative: Signal<String>) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    let representatives_future = use_resource(move || async move {
+        let config = app_state.read().wallet.node();
+        match get_representatives_online(&config.url, &config.key).await {
+            Ok(representatives) => representatives
+                .by_weight()
+                .into_iter()
+                .take(MAX_REPRESENTATIVES)
+                .map(|(account, _)| account)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    });
+
+    rsx! {
+        div { style: "display: inline-block; margin-bottom: 14px;" }
+        div {
+            id: "column-section",
+            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "New Representative:" }
+            input {
+                id: "input",
+                value: representative(),
+                onchange: move |event| representative.set(event.value()),
+            }
+            if !representative().is_empty() && !is_valid_account(&representative()) {
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                span { id: "secondary", "This account is invalid." }
+            }
+        }
+        match &*representatives_future.read_unchecked() {
+            Some(representatives) if !representatives.is_empty() => rsx! {
+                div { style: "display: inline-block; margin-bottom: 14px;" }
+                div {
+                    id: "column-section",
+                    span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Online Representatives:" }
+                    select {
+                        id: "select",
+                        onchange: move |event| representative.set(event.value()),
+                        option { value: "", "Select a representative" }
+                        for account in representatives.iter() {
+                            option { value: "{account}", {account.clone()} }
                         }
                     }
-                    Err(e) => {
-                        transaction_state.set(TransactionState::Error(format!("{e}")));
-                    }
                 }
-            });
+            },
+            _ => rsx! {},
+        }
+    }
+}
+
+/// Function that represents the review of a representative change that every participant has to accept.
+#[component]
+fn RepresentativeReview(current: String, new: String) -> Element {
+    rsx! {
+        div { style: "display: inline-block; margin-bottom: 14px;" }
+        strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "REVIEW DELEGATION CHANGE" }
+        div {
+            id: "fill-card",
+            span { id: "secondary", "From" }
+            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+              max-width: 300px; white-space: nowrap;
+                overflow: hidden;", {current} }
+        }
+        div {
+            id: "fill-card",
+            span { id: "secondary", "To" }
+            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+              max-width: 300px; white-space: nowrap;
+                overflow: hidden;", {new} }
+        }
+        div { style: "display: inline-block; margin-bottom: 8px;" }
+        span { id: "secondary", "Only sign if the whole group agreed to delegate the account's weight to this representative." }
+    }
+}
+
+/// Function that fetches the current representative so the change can be reviewed before signing.
+async fn review_representative_change(
+    app_state: Signal<AppState>,
+    mut review: Signal<Option<String>>,
+    mut transaction_state: SyncSignal<TransactionState>,
+) {
+    let config = app_state.read().wallet.node();
+    let state = RPCState::new(&config.url);
+    let account = app_state.read().wallet.nano_account.clone();
+
+    match AccountInfo::get_from_rpc(&state, &account, &config.key).await {
+        Ok(account_info) => {
+            transaction_state.set(TransactionState::Idle);
+            review.set(Some(account_info.representative));
+        }
+        Err(_) => transaction_state.set(TransactionState::Error(
+            "Open your account before changing its representative.".to_string(),
+        )),
+    }
+}
+
+/// Function that represents the Start Transaction section of the account's dashboard.
+#[component]
+fn StartTransaction() -> Element {
+    // mutable state that represents the type of transaction selected
+    let mut transaction_type = use_signal(|| "SEND".to_string());
+
+    // mutable state that represents the account that will receive the sent Nano
+    let mut receivers_account = use_signal(|| "".to_string());
+
+    // mutable state that represents the amount of Nano sent
+    let mut amount = use_signal(|| "0".to_string());
+
+    // mutable synchronous state that represents the state of the transaction in real-time
+    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
+
+    // mutable state that represents the blocks received in a RECEIVE ALL session (hash and amount)
+    let received_blocks = use_signal(Vec::<(String, String)>::new);
+
+    // mutable state that represents the payouts of a BATCH transaction and the result of each one
+    let batch = use_signal(|| None::<PayoutBatch>);
+    let payout_statuses = use_signal(Vec::<PayoutStatus>::new);
+
+    // mutable state that represents the new representative of a CHANGE transaction
+    let representative = use_signal(|| "".to_string());
+
+    // mutable state that represents the current representative once a CHANGE was reviewed
+    let mut review = use_signal(|| None::<String>);
+    use_effect(move || {
+        let _ = representative.read();
+        review.set(None);
+    });
+
+    // represents the shared state of the application
+    let app_state = use_context::<Signal<AppState>>();
+    let network = app_state.read().wallet.settings.network.clone();
+
+    // a link opened with the wallet is paid with a SEND
+    use_effect(move || {
+        if app_state.read().payment_uri.is_some() {
+            transaction_type.set("SEND".to_string());
+        }
+    });
+
+    // closure that opens the socket that will be used for the transaction and also connects as a client
+    let open_socket_and_connect = move |_| {
+        // a representative change is reviewed before it is signed
+        if transaction_type.read().as_str() == "CHANGE" && review.read().is_none() {
+            spawn(review_representative_change(
+                app_state,
+                review,
+                transaction_state,
+            ));
+            return;
+        }
+
+        if transaction_type.read().as_str() == "BATCH" {
+            if let Some(batch) = batch() {
+                spawn(run_batch(
+                    app_state,
+                    Role::Coordinator,
+                    batch,
+                    transaction_state,
+                    payout_statuses,
+                ));
+            }
+            return;
+        }
 
-            println!("Client listening.");
+        let session = run_session(
+            app_state,
+            Role::Coordinator,
+            proposal(
+                &transaction_type.read(),
+                &receivers_account.read(),
+                &amount.read(),
+                &representative.read(),
+            ),
+            transaction_state,
+            received_blocks,
+        );
+        spawn(async move {
+            session.await;
         });
     };
 
     rsx! {
         div {
             id: "card",
-            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "JOIN TRANSACTION" }
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "START TRANSACTION" }
             div {
                 id: "column-section",
                 span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Type:" }
                 select {
                     id: "select",
+                    value: "{transaction_type}",
                     onchange: move |event| transaction_type.set(event.value()),
                     option { value: "SEND", "SEND" }
                     option { value: "RECEIVE", "RECEIVE" }
-                    option { value: "OPEN", "OPEN" }
-                }
-            }
-            div { style: "display: inline-block; margin-bottom: 14px;" }
-            div {
-                id: "column-section",
-                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "IP Address:" }
-                input {
-                    id: "input",
-                    onchange: move |event| ip_address.set(event.value()),
-
+                    option { value: "RECEIVE ALL", "RECEIVE ALL" }
+                    option { value: "CHANGE", "CHANGE" }
+                    option { value: "BATCH", "BATCH" }
                 }
             }
+            UnopenedAccountNotice {}
             match transaction_type.to_string().as_str() {
                 "SEND" => {
                     rsx! {
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        PaymentRequestInput { receivers_account, amount }
                         div { style: "display: inline-block; margin-bottom: 14px;" }
                         div {
                             id: "column-section",
                             span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Receiver's Account:" }
                             input {
                                 id: "input",
+                                value: "{receivers_account}",
                                 onchange: move |event| receivers_account.set(event.value()),
                             }
+                            if !receivers_account().is_empty() && !network.is_account(&receivers_account()) {
+                                div { style: "display: inline-block; margin-bottom: 8px;" }
+                                span { id: "secondary", "This account isn't valid on the {network.network.name()} network." }
+                            }
                         }
                         div { style: "display: inline-block; margin-bottom: 14px;" }
                         div {
@@ -624,7 +1129,7 @@ fn JoinTransaction() -> Element {
                             span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Amount (XNO):" }
                             input {
                                 id: "input",
-                                value: "0",
+                                value: "{amount}",
                                 r#type: "number",
                                 min: "0",
                                 onchange: move |event| amount.set(event.value()),
@@ -633,6 +1138,19 @@ fn JoinTransaction() -> Element {
                         }
                     }
                 }
+                "CHANGE" => {
+                    rsx! {
+                        RepresentativePicker { representative }
+                        if let Some(current) = review() {
+                            RepresentativeReview { current, new: representative() }
+                        }
+                    }
+                }
+                "BATCH" => {
+                    rsx! {
+                        PayoutBatchInput { batch, payout_statuses }
+                    }
+                }
                 _ => {
                     rsx! {
                     }
@@ -663,21 +1181,26 @@ fn JoinTransaction() -> Element {
                     }
                 }
             }
+            ReceivedBlocks { received_blocks }
             div { style: "display: inline-block; margin-bottom: 36px;" }
             div {
                 id: "column-section",
                 button {
-                    id: "secondary-button",
+                    id: "button",
                     disabled: match *transaction_state.read() {
-                        TransactionState::Idle | TransactionState::Error(_) => match (receivers_account().as_str(), ip_address().as_str(), transaction_type().as_str()) {
-                            ("", _, "SEND") => true,
-                            (_, "", "SEND") => true,
+                        TransactionState::Idle | TransactionState::Error(_) => match (receivers_account().as_str(), transaction_type().as_str()) {
+                            ("", "SEND") => true,
+                            (_, "CHANGE") => !is_valid_account(&representative()),
+                            (_, "BATCH") => batch.read().is_none(),
                             _ => false
                         },
                         _ => true,
                     },
-                    onclick: connect_to_socket,
-                    "Join",
+                    onclick: open_socket_and_connect,
+                    match (transaction_type().as_str(), review().is_some()) {
+                        ("CHANGE", false) => "Review",
+                        _ => "Start",
+                    }
                 }
             }
         }
@@ -685,75 +1208,895 @@ fn JoinTransaction() -> Element {
 }
 
 #[component]
-fn SendIcon() -> Element {
-    rsx! {
-        div {
-            style: "font-size: 40px; transform: scaleX(-1); color: #5B87CF;",
-            MaterialIcon { name: "reply" }
-        }
-    }
-}
+fn JoinTransaction() -> Element {
+    let mut transaction_type = use_signal(|| "SEND".to_string());
+    let mut receivers_account = use_signal(|| "".to_string());
+    let mut ip_address = use_signal(|| "".to_string());
+    let mut amount = use_signal(|| "0".to_string());
 
-#[component]
-fn ReceiveIcon() -> Element {
-    rsx! {
-        div {
-            style: "font-size: 40px; color: #B7D0FE;",
-            MaterialIcon { name: "reply" }
-        }
-    }
-}
+    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
+    let received_blocks = use_signal(Vec::<(String, String)>::new);
+    let batch = use_signal(|| None::<PayoutBatch>);
+    let payout_statuses = use_signal(Vec::<PayoutStatus>::new);
+    let representative = use_signal(|| "".to_string());
+    let mut review = use_signal(|| None::<String>);
+    use_effect(move || {
+        let _ = representative.read();
+        review.set(None);
+    });
 
-#[component]
-fn PersonIconBlue() -> Element {
-    rsx! {
-        div {
-            style: "font-size: 40px; transform: scaleX(-1); color: #5B87CF;",
-            MaterialIcon { name: "person" }
-        }
-    }
-}
+    let app_state = use_context::<Signal<AppState>>();
+    let network = app_state.read().wallet.settings.network.clone();
 
-#[component]
-fn PersonIconYellow() -> Element {
-    rsx! {
-        div {
-            style: "font-size: 40px; transform: scaleX(-1); color: #B7D0FE;",
-            MaterialIcon { name: "person" }
+    let connect_to_socket = move |_| {
+        if transaction_type.read().as_str() == "CHANGE" && review.read().is_none() {
+            spawn(review_representative_change(
+                app_state,
+                review,
+                transaction_state,
+            ));
+            return;
         }
-    }
-}
-
-#[component]
-fn Transactions() -> Element {
-    let transactions = use_resource(async move || {
-        let app_state = use_context::<Signal<AppState>>();
-        let config = app_state.read().config_file.clone();
-        let state = RPCState::new(&config.url);
-        let nano_account = app_state.read().nano_account.clone();
 
-        match AccountHistory::get_from_rpc(&state, &nano_account, 50u32, &config.key).await {
-            Ok(account_history) => account_history.history,
-            Err(_) => Vec::new(),
+        if transaction_type.read().as_str() == "BATCH" {
+            if let Some(batch) = batch() {
+                spawn(run_batch(
+                    app_state,
+                    Role::Participant(ip_address.read().clone()),
+                    batch,
+                    transaction_state,
+                    payout_statuses,
+                ));
+            }
+            return;
         }
-    });
+
+        let session = run_session(
+            app_state,
+            Role::Participant(ip_address.read().clone()),
+            proposal(
+                &transaction_type.read(),
+                &receivers_account.read(),
+                &amount.read(),
+                &representative.read(),
+            ),
+            transaction_state,
+            received_blocks,
+        );
+        spawn(async move {
+            session.await;
+        });
+    };
 
     rsx! {
         div {
             id: "card",
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "JOIN TRANSACTION" }
             div {
                 id: "column-section",
-                match &*transactions.read_unchecked() {
-                    Some(transaction_list) => {
-                        rsx! {
-                            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "TRANSACTION HISTORY" }
-                            for (i, transaction) in transaction_list.iter().enumerate() {
-                                div {
-                                    id: "transaction",
-                                    div {
-                                        style: "display: flex; align-items: center; gap: 12px;",
-                                        if transaction.r#type.as_str() == "send" {
-                                            SendIcon{}
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Type:" }
+                select {
+                    id: "select",
+                    onchange: move |event| transaction_type.set(event.value()),
+                    option { value: "SEND", "SEND" }
+                    option { value: "RECEIVE", "RECEIVE" }
+                    option { value: "RECEIVE ALL", "RECEIVE ALL" }
+                    option { value: "CHANGE", "CHANGE" }
+                    option { value: "BATCH", "BATCH" }
+                }
+            }
+            UnopenedAccountNotice {}
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "IP Address:" }
+                input {
+                    id: "input",
+                    onchange: move |event| ip_address.set(event.value()),
+
+                }
+            }
+            match transaction_type.to_string().as_str() {
+                "SEND" => {
+                    rsx! {
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        div {
+                            id: "column-section",
+                            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Receiver's Account:" }
+                            input {
+                                id: "input",
+                                onchange: move |event| receivers_account.set(event.value()),
+                            }
+                            if !receivers_account().is_empty() && !network.is_account(&receivers_account()) {
+                                div { style: "display: inline-block; margin-bottom: 8px;" }
+                                span { id: "secondary", "This account isn't valid on the {network.network.name()} network." }
+                            }
+                        }
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        div {
+                            id: "column-section",
+                            span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Amount (XNO):" }
+                            input {
+                                id: "input",
+                                value: "0",
+                                r#type: "number",
+                                min: "0",
+                                onchange: move |event| amount.set(event.value()),
+
+                            }
+                        }
+                    }
+                }
+                "CHANGE" => {
+                    rsx! {
+                        RepresentativePicker { representative }
+                        if let Some(current) = review() {
+                            RepresentativeReview { current, new: representative() }
+                        }
+                    }
+                }
+                "BATCH" => {
+                    rsx! {
+                        PayoutBatchInput { batch, payout_statuses }
+                    }
+                }
+                _ => {
+                    rsx! {
+                    }
+                }
+            }
+            {
+                match *transaction_state.read() {
+                    TransactionState::Processing => {
+                        rsx! {
+                            div { style: "display: inline-block; margin-bottom: 14px;" }
+                            span { id: "secondary", "Processing the transaction..." }
+                        }
+                    }
+                    TransactionState::Successful => {
+                        rsx! {
+                            div { style: "display: inline-block; margin-bottom: 14px;" }
+                            span { id: "secondary", "Transaction was successful." }
+                        }
+                    }
+                    TransactionState::Error(ref e) => {
+                        rsx! {
+                            div { style: "display: inline-block; margin-bottom: 14px;" }
+                            span { id: "secondary", "{e}" }
+                        }
+                    }
+                    _ => {
+                        rsx!{}
+                    }
+                }
+            }
+            ReceivedBlocks { received_blocks }
+            div { style: "display: inline-block; margin-bottom: 36px;" }
+            div {
+                id: "column-section",
+                button {
+                    id: "secondary-button",
+                    disabled: match *transaction_state.read() {
+                        TransactionState::Idle | TransactionState::Error(_) => match (receivers_account().as_str(), ip_address().as_str(), transaction_type().as_str()) {
+                            ("", _, "SEND") => true,
+                            (_, "", "SEND") => true,
+                            (_, "", "RECEIVE ALL") => true,
+                            (_, "", "CHANGE") => true,
+                            (_, "", "BATCH") => true,
+                            (_, _, "CHANGE") => !is_valid_account(&representative()),
+                            (_, _, "BATCH") => batch.read().is_none(),
+                            _ => false
+                        },
+                        _ => true,
+                    },
+                    onclick: connect_to_socket,
+                    match (transaction_type().as_str(), review().is_some()) {
+                        ("CHANGE", false) => "Review",
+                        _ => "Join",
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SendIcon() -> Element {
+    rsx! {
+        div {
+            style: "font-size: 40px; transform: scaleX(-1); color: #5B87CF;",
+            MaterialIcon { name: "reply" }
+        }
+    }
+}
+
+#[component]
+fn ReceiveIcon() -> Element {
+    rsx! {
+        div {
+            style: "font-size: 40px; color: #B7D0FE;",
+            MaterialIcon { name: "reply" }
+        }
+    }
+}
+
+#[component]
+fn PersonIconBlue() -> Element {
+    rsx! {
+        div {
+            style: "font-size: 40px; transform: scaleX(-1); color: #5B87CF;",
+            MaterialIcon { name: "person" }
+        }
+    }
+}
+
+#[component]
+fn PersonIconYellow() -> Element {
+    rsx! {
+        div {
+            style: "font-size: 40px; transform: scaleX(-1); color: #B7D0FE;",
+            MaterialIcon { name: "person" }
+        }
+    }
+}
+
+/// Function that represents the blocks signed by the group that weren't confirmed yet (and the last ones that were).
+#[component]
+fn OutboxSection() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let entries = use_memo(move || {
+        let _ = app_state.read().account_updates;
+        let app_state = app_state.read();
+        app_state
+            .wallet
+            .outbox
+            .entries_for(&app_state.wallet.nano_account)
+    });
+
+    // closure that publishes a block again right away
+    let rebroadcast = move |hash: String| {
+        spawn(async move {
+            let outbox = app_state.peek().wallet.outbox.clone();
+            let nodes = outbox_nodes(&app_state.peek().wallet);
+            let result = outbox.publish(&hash, &nodes).await;
+            let mut state = app_state.write();
+            state.outbox_error = result.err();
+            state.account_updates += 1;
+        });
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            div {
+                id: "column-section",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "OUTBOX" }
+                if let Some(error) = app_state.read().outbox_error.clone() {
+                    span { id: "secondary", style: "display: inline-block; margin-bottom: 14px;", {error} }
+                }
+                if entries.read().is_empty() {
+                    span { id: "secondary", "There are no signed blocks waiting to be published." }
+                }
+                for entry in entries.read().iter().cloned() {
+                    div {
+                        id: "transaction",
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading", style: "text-overflow: ellipsis; max-width: 200px; white-space: nowrap; overflow: hidden;", strong { {entry.hash.clone()} } }
+                            span { id: "secondary", {format!("{} · {}", entry.subtype.to_uppercase(), entry.status.name())} }
+                        }
+                        div {
+                            id: "fill-card",
+                            span { id: "secondary", {format!("Balance after: {} XNO", raw_to_nano(entry.block.balance))} }
+                            span { id: "secondary", {format!("Attempts: {}", entry.attempts)} }
+                        }
+                        if let Some(error) = entry.last_error.clone() {
+                            span { id: "secondary", {error} }
+                        }
+                        if matches!(entry.status, OutboxStatus::PendingPublish | OutboxStatus::Published) {
+                            div { style: "display: inline-block; margin-bottom: 8px;" }
+                            button {
+                                id: "button",
+                                onclick: move |_| rebroadcast(entry.hash.clone()),
+                                "Rebroadcast",
+                            }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                }
+            }
+        }
+    }
+}
+
+/// Function that represents the notice shown outside the Transaction section while scheduled payments are due.
+#[component]
+fn DuePaymentsNotice(menu_item: Signal<String>) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let due = use_memo(move || {
+        let _ = app_state.read().schedule_updates;
+        app_state.read().wallet.due_payments().len()
+    });
+
+    rsx! {
+        if due() > 0 {
+            div {
+                id: "card",
+                div {
+                    id: "fill-card",
+                    span { id: "sub-heading", {match due() {
+                        1 => "A scheduled payment is due.".to_string(),
+                        due => format!("{due} scheduled payments are due."),
+                    }} }
+                    button {
+                        id: "menu-button",
+                        onclick: move |_| menu_item.set("transaction".to_string()),
+                        "Review"
+                    }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+        }
+    }
+}
+
+/// Function that represents the scheduled payments that are due: the group signs each one in a session started with
+/// it (or joined pre-filled with it), or skips it.
+#[component]
+fn DuePaymentsSection() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let due = use_memo(move || {
+        let _ = app_state.read().schedule_updates;
+        app_state.read().wallet.due_payments()
+    });
+
+    // mutable state that represents the address of the coordinator when joining a session
+    let mut ip_address = use_signal(|| "".to_string());
+
+    // mutable synchronous state that represents the state of the payment in real-time
+    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
+    let received_blocks = use_signal(Vec::<(String, String)>::new);
+
+    // payments that were sent but couldn't be recorded as paid, which can't be started again
+    let mut unrecorded = use_signal(Vec::<(String, u64)>::new);
+
+    // closure that signs a due payment with the group and records it as paid
+    let pay = move |payment: ScheduledPayment, due: u64, role: Role| {
+        spawn(async move {
+            let proposal = Proposal::send(&payment.receivers_account, &payment.amount);
+            let hash = run_session(
+                app_state,
+                role,
+                Some(proposal),
+                transaction_state,
+                received_blocks,
+            )
+            .await;
+            if matches!(*transaction_state.peek(), TransactionState::Successful) {
+                let result = app_state.peek().wallet.schedule.mark_paid(&payment.id, due);
+                if let Err(e) = result {
+                    unrecorded.write().push((payment.id.clone(), due));
+                    transaction_state.set(TransactionState::Error(format!(
+                        "The payment was sent in block {}, but it couldn't be recorded as paid, so don't start it again: {e}",
+                        hash.unwrap_or("(unknown)".to_string())
+                    )));
+                }
+                app_state.write().schedule_updates += 1;
+            }
+        });
+    };
+
+    // closure that records that the group won't make a due payment
+    let mut skip = move |id: String, due: u64| {
+        let result = app_state.peek().wallet.schedule.skip(&id, due);
+        if let Err(e) = result {
+            transaction_state.set(TransactionState::Error(e));
+        }
+        app_state.write().schedule_updates += 1;
+    };
+
+    let busy = matches!(*transaction_state.read(), TransactionState::Processing);
+
+    rsx! {
+        if !due.read().is_empty() {
+            div {
+                id: "card",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "DUE PAYMENTS" }
+                span { id: "secondary", style: "display: inline-block; margin-bottom: 14px;", "One participant starts the payment and the others join it with the coordinator's address." }
+                div {
+                    id: "column-section",
+                    span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Coordinator's Address (to join):" }
+                    input {
+                        id: "input",
+                        value: "{ip_address}",
+                        onchange: move |event| ip_address.set(event.value()),
+                    }
+                }
+                div { style: "display: inline-block; margin-bottom: 14px;" }
+                for (payment, due) in due.read().iter().cloned() {
+                    div {
+                        id: "transaction",
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading", strong { {match payment.label.as_str() {
+                                "" => format!("{} XNO", payment.amount),
+                                label => format!("{label} · {} XNO", payment.amount),
+                            }} } }
+                            span { id: "secondary", {format!("{} · {}", format_timestamp(due), payment.status(due, current_time()).name())} }
+                        }
+                        span { id: "secondary", style: "text-overflow: ellipsis; max-width: 400px; white-space: nowrap; overflow: hidden;", {payment.receivers_account.clone()} }
+                        div { style: "display: inline-block; margin-bottom: 8px;" }
+                        div {
+                            style: "display: flex; flex-direction: row; gap: 8px;",
+                            button {
+                                id: "button",
+                                disabled: busy || unrecorded.read().contains(&(payment.id.clone(), due)),
+                                onclick: {
+                                    let payment = payment.clone();
+                                    move |_| pay(payment.clone(), due, Role::Coordinator)
+                                },
+                                "Start"
+                            }
+                            button {
+                                id: "button",
+                                disabled: busy || ip_address().is_empty() || unrecorded.read().contains(&(payment.id.clone(), due)),
+                                onclick: {
+                                    let payment = payment.clone();
+                                    move |_| pay(payment.clone(), due, Role::Participant(ip_address()))
+                                },
+                                "Join"
+                            }
+                            button {
+                                id: "button",
+                                disabled: busy,
+                                onclick: {
+                                    let id = payment.id.clone();
+                                    move |_| skip(id.clone(), due)
+                                },
+                                "Skip"
+                            }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                }
+                {
+                    match *transaction_state.read() {
+                        TransactionState::Processing => rsx! {
+                            span { id: "secondary", "Processing the payment..." }
+                        },
+                        TransactionState::Successful => rsx! {
+                            span { id: "secondary", "The payment was made." }
+                        },
+                        TransactionState::Error(ref e) => rsx! {
+                            span { id: "secondary", "{e}" }
+                        },
+                        _ => rsx! {},
+                    }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+        }
+    }
+}
+
+/// Function that represents the form that adds a payment to the schedule of the open account.
+#[component]
+fn ScheduledPaymentForm() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let mut receivers_account = use_signal(|| "".to_string());
+    let mut amount = use_signal(|| "".to_string());
+    let mut label = use_signal(|| "".to_string());
+    let mut cadence = use_signal(|| Cadence::Monthly);
+
+    // mutable states that represent the day ("YYYY-MM-DD") and the time ("HH:MM", UTC) of the first payment
+    let mut date = use_signal(|| format_timestamp(current_time())[..10].to_string());
+    let mut time = use_signal(|| "09:00".to_string());
+
+    // mutable state that represents the result of the last attempt to add a payment
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    let add = move |_| {
+        let Some(start) = parse_timestamp(&format!("{} {}", date(), time())) else {
+            result.set(Some(Err(
+                "Choose the day and the time of the first payment.".to_string(),
+            )));
+            return;
+        };
+        let payment = ScheduledPayment::new(
+            &app_state.peek().wallet.nano_account,
+            receivers_account().trim(),
+            &amount(),
+            &label(),
+            cadence(),
+            start,
+        );
+        let added = app_state.peek().wallet.schedule_payment(payment);
+        match added {
+            Ok(_) => {
+                result.set(Some(Ok(format!(
+                    "The first payment is on {}.",
+                    format_timestamp(start)
+                ))));
+                receivers_account.set("".to_string());
+                amount.set("".to_string());
+                label.set("".to_string());
+                app_state.write().schedule_updates += 1;
+            }
+            Err(e) => result.set(Some(Err(e))),
+        }
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "SCHEDULE A PAYMENT" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Receiver's Account:" }
+                input {
+                    id: "input",
+                    value: "{receivers_account}",
+                    onchange: move |event| receivers_account.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Amount (XNO):" }
+                input {
+                    id: "input",
+                    value: "{amount}",
+                    onchange: move |event| amount.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Label (optional):" }
+                input {
+                    id: "input",
+                    value: "{label}",
+                    onchange: move |event| label.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Repeats:" }
+                select {
+                    id: "select",
+                    value: cadence().name(),
+                    onchange: move |event| {
+                        if let Some(selected) = Cadence::from_name(&event.value()) {
+                            cadence.set(selected);
+                        }
+                    },
+                    for choice in Cadence::ALL {
+                        option { value: choice.name(), {choice.name()} }
+                    }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "First Payment (UTC):" }
+                div {
+                    style: "display: flex; flex-direction: row; gap: 8px;",
+                    input {
+                        id: "input",
+                        r#type: "date",
+                        value: "{date}",
+                        onchange: move |event| date.set(event.value()),
+                    }
+                    input {
+                        id: "input",
+                        r#type: "time",
+                        value: "{time}",
+                        onchange: move |event| time.set(event.value()),
+                    }
+                }
+            }
+            match result() {
+                Some(Ok(message)) | Some(Err(message)) => rsx! {
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                    span { id: "secondary", "{message}" }
+                },
+                None => rsx! {},
+            }
+            div { style: "display: inline-block; margin-bottom: 36px;" }
+            div {
+                id: "column-section",
+                button {
+                    id: "button",
+                    disabled: receivers_account().is_empty() || amount().is_empty(),
+                    onclick: add,
+                    "Add"
+                }
+            }
+        }
+    }
+}
+
+/// Function that pairs each payment of the calendar with its month ("YYYY-MM") when it's the first of the month.
+fn calendar_months(calendar: Vec<CalendarEntry>) -> Vec<(Option<String>, CalendarEntry)> {
+    let mut previous = String::new();
+    calendar
+        .into_iter()
+        .map(|entry| {
+            let month = format_timestamp(entry.due)[..7].to_string();
+            match month == previous {
+                true => (None, entry),
+                false => {
+                    previous = month.clone();
+                    (Some(month), entry)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Function that represents the calendar of the open account's scheduled payments: the ones of the last weeks (paid,
+/// skipped or missed) and the upcoming ones, followed by every scheduled payment.
+#[component]
+fn PaymentCalendar() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let calendar = use_memo(move || {
+        let _ = app_state.read().schedule_updates;
+        let app_state = app_state.read();
+        let now = current_time();
+        app_state.wallet.schedule.calendar(
+            &app_state.wallet.nano_account,
+            now.saturating_sub(CALENDAR_DAYS * DAY),
+            now + CALENDAR_DAYS * DAY,
+            now,
+        )
+    });
+    let payments = use_memo(move || {
+        let _ = app_state.read().schedule_updates;
+        let app_state = app_state.read();
+        app_state
+            .wallet
+            .schedule
+            .payments_for(&app_state.wallet.nano_account)
+    });
+
+    // error shown when a payment couldn't be removed
+    let mut remove_error = use_signal(|| None::<String>);
+
+    // closure that removes a payment from the schedule
+    let mut remove = move |id: String| {
+        let result = app_state.peek().wallet.schedule.remove(&id);
+        remove_error.set(result.err());
+        app_state.write().schedule_updates += 1;
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            div {
+                id: "column-section",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "CALENDAR" }
+                if calendar.read().is_empty() {
+                    span { id: "secondary", "There are no scheduled payments around today." }
+                }
+                for (month, entry) in calendar_months(calendar()) {
+                    if let Some(month) = month {
+                        span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "{month}" }
+                    }
+                    div {
+                        id: "transaction",
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading", strong { {match entry.payment.label.as_str() {
+                                "" => format!("{} XNO", entry.payment.amount),
+                                label => format!("{label} · {} XNO", entry.payment.amount),
+                            }} } }
+                            span {
+                                id: "secondary",
+                                style: match entry.status {
+                                    PaymentStatus::Missed => "color: #d32f2f;",
+                                    PaymentStatus::Due => "color: #f9a825;",
+                                    _ => "",
+                                },
+                                {format!("{} · {}", format_timestamp(entry.due), entry.status.name())}
+                            }
+                        }
+                        span { id: "secondary", style: "text-overflow: ellipsis; max-width: 400px; white-space: nowrap; overflow: hidden;", {entry.payment.receivers_account.clone()} }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                }
+            }
+        }
+        div { style: "display: inline-block; margin-bottom: 14px;" }
+        div {
+            id: "card",
+            div {
+                id: "column-section",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "SCHEDULED PAYMENTS" }
+                if payments.read().is_empty() {
+                    span { id: "secondary", "The account has no scheduled payments." }
+                }
+                for payment in payments.read().iter().cloned() {
+                    div {
+                        id: "transaction",
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading", strong { {match payment.label.as_str() {
+                                "" => format!("{} XNO", payment.amount),
+                                label => format!("{label} · {} XNO", payment.amount),
+                            }} } }
+                            span { id: "secondary", {format!("{} from {}", payment.cadence.name(), format_timestamp(payment.start))} }
+                        }
+                        span { id: "secondary", style: "text-overflow: ellipsis; max-width: 400px; white-space: nowrap; overflow: hidden;", {payment.receivers_account.clone()} }
+                        div { style: "display: inline-block; margin-bottom: 8px;" }
+                        button {
+                            id: "button",
+                            onclick: move |_| remove(payment.id.clone()),
+                            "Remove"
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                }
+                if let Some(e) = remove_error() {
+                    span { id: "secondary", {format!("The payment couldn't be removed. {e}")} }
+                }
+            }
+        }
+    }
+}
+
+/// Function that represents the section with the audit log of the account.
+#[component]
+fn AuditSection() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    // message shown after exporting the log
+    let mut export_message = use_signal(|| "".to_string());
+
+    // the records that can be read are shown even if others are damaged (the verification reports them)
+    let records = use_memo(move || {
+        let _ = app_state.read().account_updates;
+        let mut records = match app_state.read().wallet.audit_log().records() {
+            Ok(contents) => contents.records,
+            Err(_) => Vec::new(),
+        };
+        records.reverse();
+        records
+    });
+
+    let verification = use_memo(move || {
+        let _ = app_state.read().account_updates;
+        match app_state.read().wallet.audit_log().verify() {
+            Ok(count) => format!("The chain of {count} records is intact."),
+            Err(e) => e.to_string(),
+        }
+    });
+
+    // closure that exports the log to the downloads folder
+    let export = move |_| {
+        let app_state = app_state.peek();
+        let directory = dirs::download_dir().unwrap_or(app_state.wallet.audit_directory.clone());
+        let path = directory
+            .join(format!(
+                "aokiji-audit-{}.json",
+                app_state.wallet.nano_account
+            ))
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+        match app_state.wallet.audit_log().export(&path) {
+            Ok(damaged_lines) if damaged_lines.is_empty() => {
+                export_message.set(format!("Exported to {path}"))
+            }
+            Ok(damaged_lines) => export_message.set(format!(
+                "Exported to {path}, leaving out the unreadable lines {damaged_lines:?}."
+            )),
+            Err(e) => export_message.set(e.to_string()),
+        }
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            div {
+                id: "column-section",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "AUDIT LOG" }
+                span { id: "secondary", {verification.read().clone()} }
+                div { style: "display: inline-block; margin-bottom: 8px;" }
+                button { id: "button", onclick: export, "Export" }
+                if !export_message.read().is_empty() {
+                    span { id: "secondary", {export_message.read().clone()} }
+                }
+                div { style: "display: inline-block; margin-bottom: 14px;" }
+                if records.read().is_empty() {
+                    span { id: "secondary", "No group sessions were recorded yet." }
+                }
+                for record in records.read().iter().cloned() {
+                    div {
+                        id: "transaction",
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading", strong { {record.event.name()} } }
+                            span { id: "secondary", {format_timestamp(record.time)} }
+                        }
+                        span { id: "secondary", style: "overflow-wrap: anywhere;", {audit_details(&record.event)} }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                }
+            }
+        }
+    }
+}
+
+/// Function that describes the details of an audit event in one line.
+fn audit_details(event: &AuditEvent) -> String {
+    match event {
+        AuditEvent::KeygenStarted { role, coordinator } => format!("As {role} with {coordinator}"),
+        AuditEvent::KeygenFinished {
+            participants,
+            threshold,
+            public_share,
+        } => format!("{threshold} of {participants} · Public share: {public_share}"),
+        AuditEvent::KeygenFailed { error } => error.clone(),
+        AuditEvent::SessionProposed {
+            role,
+            coordinator,
+            subtype,
+            block_hash,
+            balance,
+            link,
+            ..
+        } => format!(
+            "{} as {role} with {coordinator} · Block: {block_hash} · Balance after: {} XNO · Link: {link}",
+            subtype.to_uppercase(),
+            balance.parse().map(raw_to_nano).unwrap_or(balance.clone())
+        ),
+        AuditEvent::SessionRejected { reason } => reason.clone(),
+        AuditEvent::SessionSucceeded {
+            block_hash,
+            published,
+        } => match published {
+            true => format!("Block: {block_hash} · Published"),
+            false => format!("Block: {block_hash}"),
+        },
+        AuditEvent::SessionFailed { block_hash, error } => format!("Block: {block_hash} · {error}"),
+    }
+}
+
+#[component]
+fn Transactions() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    let transactions = use_resource(move || async move {
+        let _ = app_state.read().account_updates;
+        let wallet = app_state.read().wallet.clone();
+
+        match account_history(&wallet, 50u32).await {
+            Ok(account_history) => account_history.history,
+            Err(_) => Vec::new(),
+        }
+    });
+
+    rsx! {
+        div {
+            id: "card",
+            div {
+                id: "column-section",
+                match &*transactions.read_unchecked() {
+                    Some(transaction_list) => {
+                        rsx! {
+                            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "TRANSACTION HISTORY" }
+                            for (i, transaction) in transaction_list.iter().enumerate() {
+                                div {
+                                    id: "transaction",
+                                    div {
+                                        style: "display: flex; align-items: center; gap: 12px;",
+                                        if transaction.kind.as_str() == "send" {
+                                            SendIcon{}
                                         } else {
                                             ReceiveIcon{}
                                         }
@@ -771,7 +2114,7 @@ fn Transactions() -> Element {
                                                 span { id: "secondary" , style: "text-overflow: ellipsis;
                                                   max-width: 200px; white-space: nowrap;
                                                     overflow: hidden;", strong {  {format!("{}", transaction.hash)} } }
-                                                strong { id: "sub-heading" , {format!("{}", transaction.amount.parse::<u128>().unwrap_or(0u128) as f64 / 1_000_000_000_000_000_000_000_000_000_000.0)} }
+                                                strong { id: "sub-heading" , {transaction.amount.clone()} }
                                             }
                                         }
                                     }
@@ -792,7 +2135,7 @@ fn Transactions() -> Element {
 #[component]
 fn PublicShare() -> Element {
     let app_state = use_context::<Signal<AppState>>();
-    let public_share = app_state.read().public_share.clone();
+    let public_share = app_state.read().wallet.public_share.clone();
     rsx! {
         div {
             id: "card",
@@ -818,89 +2161,516 @@ fn PublicShare() -> Element {
 }
 
 #[component]
-fn AccountInfoSection() -> Element {
-    let app_state = use_context::<Signal<AppState>>();
-    let account = app_state.read().nano_account.clone();
+fn AccountInfoSection() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let account = app_state.read().wallet.nano_account.clone();
+
+    let account_info_future = use_resource(move || {
+        let account = account.clone();
+        async move {
+            let _ = app_state.read().account_updates;
+            let config = app_state.read().wallet.node();
+            let state = RPCState::new(&config.url);
+            AccountInfo::get_from_rpc(&state, &account, &config.key).await
+        }
+    });
+
+    match &*account_info_future.read_unchecked() {
+        Some(Ok(account_info)) => {
+            rsx! {
+                div {
+                    id: "card",
+                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "FRONTIER" }
+                    div {
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+                              max-width: 390px; white-space: nowrap;
+                                overflow: hidden;", span { {account_info.frontier.clone()} } }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 28px;" }
+                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "OPEN BLOCK" }
+                    div {
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+                              max-width: 390px; white-space: nowrap;
+                                overflow: hidden;", span { {account_info.open_block.clone()} } }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 28px;" }
+                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "REPRESENTATIVE" }
+                    div {
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+                              max-width: 390px; white-space: nowrap;
+                                overflow: hidden;", span { {account_info.representative.clone()} } }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 28px;" }
+                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "BALANCE" }
+                    div {
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+                              max-width: 390px; white-space: nowrap;
+                                overflow: hidden;", span { {account_info.balance.clone()} } }
+                        }
+                    }
+                    div { style: "display: inline-block; margin-bottom: 28px;" }
+                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "BLOCK COUNT" }
+                    div {
+                        div {
+                            id: "fill-card",
+                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
+                              max-width: 390px; white-space: nowrap;
+                                overflow: hidden;", span { {account_info.block_count.clone()} } }
+                        }
+                    }
+                }
+            }
+        }
+        Some(Err(_)) => {
+            rsx! {
+                div {
+                    id: "card",
+                    span { id: "secondary", "Open your account with a transaction to see more details." }
+                }
+            }
+        }
+        None => {
+            rsx! {
+                div {
+                    id: "card",
+                    span { id: "secondary", "Loading account information..." }
+                }
+            }
+        }
+    }
+}
+
+/// Function that represents the settings of the open account that replace the global ones.
+#[component]
+fn AccountSettingsSection() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let (overrides, key, global) = {
+        let app_state = app_state.read();
+        let wallet = &app_state.wallet;
+        (
+            wallet.account_settings(),
+            wallet.account_key.clone(),
+            (wallet.config_file.url.clone(), wallet.settings.clone()),
+        )
+    };
+    let (global_url, global_settings) = global;
+
+    let mut url = use_signal(|| overrides.url.clone().unwrap_or_default());
+    let mut api_key = use_signal(|| key.clone());
+    let mut websocket_url = use_signal(|| overrides.websocket_url.clone().unwrap_or_default());
+    let mut broadcast_nodes = use_signal(|| {
+        overrides
+            .broadcast_nodes
+            .clone()
+            .map(|nodes| nodes.join(", "))
+            .unwrap_or_default()
+    });
+    let mut currency = use_signal(|| overrides.currency.clone().unwrap_or_default());
+    let mut representative = use_signal(|| overrides.representative.clone().unwrap_or_default());
+    let mut port = use_signal(|| overrides.port.map(|p| p.to_string()).unwrap_or_default());
+    // result of the save, with the check of the account's own node if it has one
+    let mut result = use_signal(|| None::<Result<Option<NodeHealth>, String>>);
+    let mut checking_node = use_signal(|| false);
+
+    let save_settings = move |_| {
+        let optional = |value: Signal<String>| match value.read().trim() {
+            "" => None,
+            value => Some(value.to_string()),
+        };
+        let port = match optional(port).map(|port| port.parse::<u32>()) {
+            Some(Ok(port)) => Some(port),
+            Some(Err(_)) => {
+                result.set(Some(
+                    Err("The coordinator port isn't a number.".to_string()),
+                ));
+                return;
+            }
+            None => None,
+        };
+
+        let overrides = AccountSettings {
+            url: optional(url),
+            websocket_url: optional(websocket_url),
+            broadcast_nodes: optional(broadcast_nodes).map(|nodes| {
+                nodes
+                    .split(',')
+                    .map(|url| url.trim().to_string())
+                    .filter(|url| !url.is_empty())
+                    .collect()
+            }),
+            currency: optional(currency).map(|currency| currency.to_lowercase()),
+            representative: optional(representative),
+            port,
+        };
+
+        let key = api_key.read().trim().to_string();
+        spawn(async move {
+            let mut wallet = app_state.read().wallet.clone();
+
+            // a node of the account is checked like the one of the RPC configuration before it's saved
+            let health = match overrides.url.is_some() || !key.is_empty() {
+                true => {
+                    let url = overrides
+                        .url
+                        .clone()
+                        .unwrap_or(wallet.config_file.url.clone());
+                    let node_key = match key.is_empty() {
+                        true => wallet.config_file.key.clone(),
+                        false => key.clone(),
+                    };
+                    checking_node.set(true);
+                    let health =
+                        check_node(&url, &node_key, wallet.settings.network.network.name()).await;
+                    checking_node.set(false);
+                    match health {
+                        Ok(health) => Some(health),
+                        Err(e) => {
+                            result.set(Some(Err(e.to_string())));
+                            return;
+                        }
+                    }
+                }
+                false => None,
+            };
+
+            let saved = wallet.save_account_settings(overrides, &key);
+            if saved.is_ok() {
+                app_state.write().wallet = wallet;
+            }
+            result.set(Some(saved.map(|_| health)));
+        });
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "ACCOUNT SETTINGS" }
+            span { id: "secondary", style: "display: inline-block; margin-bottom: 14px;", "These settings only apply to this account. Leave a setting empty to use the one of the RPC configuration." }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Url:" }
+                input {
+                    id: "input",
+                    value: url(),
+                    placeholder: global_url,
+                    onchange: move |event| url.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Key:" }
+                input {
+                    id: "input",
+                    r#type: "password",
+                    value: api_key(),
+                    onchange: move |event| api_key.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "WebSocket Url:" }
+                input {
+                    id: "input",
+                    value: websocket_url(),
+                    placeholder: global_settings.websocket_url,
+                    onchange: move |event| websocket_url.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Broadcast Nodes:" }
+                input {
+                    id: "input",
+                    value: broadcast_nodes(),
+                    placeholder: global_settings.broadcast_nodes.join(", "),
+                    onchange: move |event| broadcast_nodes.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Price Currency:" }
+                input {
+                    id: "input",
+                    value: currency(),
+                    placeholder: global_settings.price.currency,
+                    onchange: move |event| currency.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Representative When Opening:" }
+                input {
+                    id: "input",
+                    value: representative(),
+                    placeholder: "Online representative with most weight",
+                    onchange: move |event| representative.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Coordinator Port:" }
+                input {
+                    id: "input",
+                    r#type: "number",
+                    value: port(),
+                    placeholder: PORT.to_string(),
+                    onchange: move |event| port.set(event.value()),
+                }
+            }
+            match (checking_node(), &*result.read()) {
+                (true, _) => rsx! {
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                    span { id: "secondary", "Checking the node..." }
+                },
+                (false, Some(Ok(Some(health)))) => rsx! {
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                    span {
+                        id: "secondary",
+                        {format!("Saved. Connected to {} on the {} network ({} ms, {} blocks). Open the dashboard again to reconnect with the new node.", health.vendor, health.network, health.latency.as_millis(), health.block_count)}
+                    }
+                    for warning in health.warnings.iter() {
+                        span { id: "secondary", {warning.clone()} }
+                    }
+                },
+                (false, Some(Ok(None))) => rsx! {
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                    span { id: "secondary", "Saved. Open the dashboard again to reconnect with the new node." }
+                },
+                (false, Some(Err(e))) => rsx! {
+                    div { style: "display: inline-block; margin-bottom: 14px;" }
+                    span { id: "secondary", {format!("Not saved. {e}")} }
+                },
+                (false, None) => rsx! {},
+            }
+            div { style: "display: inline-block; margin-bottom: 36px;" }
+            div {
+                id: "column-section",
+                button {
+                    id: "button",
+                    disabled: checking_node(),
+                    onclick: save_settings,
+                    "Save",
+                }
+            }
+        }
+    }
+}
+
+/// Function that represents the spending policy this participant enforces before signing blocks of the open account.
+#[component]
+fn SpendingPolicySection() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
 
-    let account_info_future = use_resource(move || {
-        let account = account.clone();
-        async move {
-            let config = app_state.read().config_file.clone();
-            let state = RPCState::new(&config.url);
-            AccountInfo::get_from_rpc(&state, &account, &config.key).await
-        }
+    let policy = {
+        let app_state = app_state.read();
+        app_state
+            .wallet
+            .settings
+            .policies
+            .get(&app_state.wallet.nano_account)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let mut max_per_transaction = use_signal(|| policy.max_per_transaction.unwrap_or_default());
+    let mut daily_limit = use_signal(|| policy.daily_limit.unwrap_or_default());
+    let mut weekly_limit = use_signal(|| policy.weekly_limit.unwrap_or_default());
+    let mut allowed_recipients = use_signal(|| policy.allowed_recipients.join(", "));
+    let mut forbid_representative_change = use_signal(|| policy.forbid_representative_change);
+    let mut start_hour = use_signal(|| {
+        policy
+            .time_window
+            .map(|window| window.start_hour.to_string())
+            .unwrap_or_default()
     });
+    let mut end_hour = use_signal(|| {
+        policy
+            .time_window
+            .map(|window| window.end_hour.to_string())
+            .unwrap_or_default()
+    });
+    let mut error = use_signal(|| None::<String>);
 
-    match &*account_info_future.read_unchecked() {
-        Some(Ok(account_info)) => {
-            rsx! {
+    let save_policy = move |_| {
+        let optional = |value: Signal<String>| match value.read().trim() {
+            "" => None,
+            value => Some(value.to_string()),
+        };
+        let recipients: Vec<String> = allowed_recipients
+            .read()
+            .split(',')
+            .map(|account| account.trim().to_string())
+            .filter(|account| !account.is_empty())
+            .collect();
+        if let Some(account) = recipients.iter().find(|account| !is_valid_account(account)) {
+            error.set(Some(format!("{account} is not a valid account.")));
+            return;
+        }
+        let time_window = match (start_hour.read().trim(), end_hour.read().trim()) {
+            ("", "") => None,
+            (start, end) => match (start.parse::<u8>(), end.parse::<u8>()) {
+                (Ok(start_hour), Ok(end_hour)) => Some(TimeWindow {
+                    start_hour,
+                    end_hour,
+                }),
+                _ => {
+                    error.set(Some("The signing hours must be whole numbers.".to_string()));
+                    return;
+                }
+            },
+        };
+        if let Some(Err(e)) = time_window.map(|window| window.validate()) {
+            error.set(Some(e));
+            return;
+        }
+
+        let policy = SpendingPolicy {
+            max_per_transaction: optional(max_per_transaction),
+            daily_limit: optional(daily_limit),
+            weekly_limit: optional(weekly_limit),
+            allowed_recipients: recipients,
+            forbid_representative_change: *forbid_representative_change.read(),
+            time_window,
+        };
+        for limit in [
+            &policy.max_per_transaction,
+            &policy.daily_limit,
+            &policy.weekly_limit,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if nano_to_raw(limit).is_none() {
+                error.set(Some(format!("{limit} is not a valid amount.")));
+                return;
+            }
+        }
+
+        let mut wallet = app_state.read().wallet.clone();
+        let account = wallet.nano_account.clone();
+        match policy == SpendingPolicy::default() {
+            true => wallet.settings.policies.remove(&account),
+            false => wallet.settings.policies.insert(account, policy),
+        };
+
+        if let Err(e) = wallet.save_settings() {
+            error.set(Some(e));
+            return;
+        }
+        app_state.write().wallet.settings = wallet.settings;
+        error.set(None);
+    };
+
+    rsx! {
+        div {
+            id: "card",
+            strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "SPENDING POLICY" }
+            span { id: "secondary", style: "display: inline-block; margin-bottom: 14px;", "Your client refuses to sign blocks of this account that break these rules. Leave a rule empty to allow everything." }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Max per Transaction (XNO):" }
+                input {
+                    id: "input",
+                    value: max_per_transaction(),
+                    onchange: move |event| max_per_transaction.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Daily Limit (XNO):" }
+                input {
+                    id: "input",
+                    value: daily_limit(),
+                    onchange: move |event| daily_limit.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Weekly Limit (XNO):" }
+                input {
+                    id: "input",
+                    value: weekly_limit(),
+                    onchange: move |event| weekly_limit.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Allowed Recipients:" }
+                input {
+                    id: "input",
+                    value: allowed_recipients(),
+                    placeholder: "Accounts separated by commas",
+                    onchange: move |event| allowed_recipients.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Signing Hours (UTC):" }
                 div {
-                    id: "card",
-                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "FRONTIER" }
-                    div {
-                        div {
-                            id: "fill-card",
-                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
-                              max-width: 390px; white-space: nowrap;
-                                overflow: hidden;", span { {account_info.frontier.clone()} } }
-                        }
-                    }
-                    div { style: "display: inline-block; margin-bottom: 28px;" }
-                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "OPEN BLOCK" }
-                    div {
-                        div {
-                            id: "fill-card",
-                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
-                              max-width: 390px; white-space: nowrap;
-                                overflow: hidden;", span { {account_info.open_block.clone()} } }
-                        }
-                    }
-                    div { style: "display: inline-block; margin-bottom: 28px;" }
-                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "REPRESENTATIVE" }
-                    div {
-                        div {
-                            id: "fill-card",
-                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
-                              max-width: 390px; white-space: nowrap;
-                                overflow: hidden;", span { {account_info.representative.clone()} } }
-                        }
-                    }
-                    div { style: "display: inline-block; margin-bottom: 28px;" }
-                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "BALANCE" }
-                    div {
-                        div {
-                            id: "fill-card",
-                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
-                              max-width: 390px; white-space: nowrap;
-                                overflow: hidden;", span { {account_info.balance.clone()} } }
-                        }
+                    style: "display: flex; flex-direction: row; gap: 8px;",
+                    input {
+                        id: "input",
+                        r#type: "number",
+                        min: "0",
+                        max: "23",
+                        value: start_hour(),
+                        placeholder: "From",
+                        onchange: move |event| start_hour.set(event.value()),
                     }
-                    div { style: "display: inline-block; margin-bottom: 28px;" }
-                    strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "BLOCK COUNT" }
-                    div {
-                        div {
-                            id: "fill-card",
-                            span { id: "sub-heading" , style: "text-overflow: ellipsis;
-                              max-width: 390px; white-space: nowrap;
-                                overflow: hidden;", span { {account_info.block_count.clone()} } }
-                        }
+                    input {
+                        id: "input",
+                        r#type: "number",
+                        min: "0",
+                        max: "23",
+                        value: end_hour(),
+                        placeholder: "To",
+                        onchange: move |event| end_hour.set(event.value()),
                     }
                 }
             }
-        }
-        Some(Err(_)) => {
-            rsx! {
-                div {
-                    id: "card",
-                    span { id: "secondary", "Open your account with a transaction to see more details." }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "fill-card",
+                span { id: "secondary", "Forbid representative changes" }
+                input {
+                    r#type: "checkbox",
+                    checked: forbid_representative_change(),
+                    onchange: move |event| forbid_representative_change.set(event.checked()),
                 }
             }
-        }
-        None => {
-            rsx! {
-                div {
-                    id: "card",
-                    span { id: "secondary", "Loading account information..." }
+            if let Some(error) = error() {
+                div { style: "display: inline-block; margin-bottom: 14px;" }
+                span { id: "secondary", {error} }
+            }
+            div { style: "display: inline-block; margin-bottom: 36px;" }
+            div {
+                id: "column-section",
+                button {
+                    id: "button",
+                    onclick: save_policy,
+                    "Save",
                 }
             }
         }
@@ -911,25 +2681,126 @@ fn AccountInfoSection() -> Element {
 fn TransactionConfig() -> Element {
     let mut app_state = use_context::<Signal<AppState>>();
 
-    let config = app_state.read().config_file.clone();
+    let config = app_state.read().wallet.config_file.clone();
+    let price_settings = app_state.read().wallet.settings.price.clone();
 
     let mut rpc_url = use_signal(|| config.url.clone());
     let mut api_key = use_signal(|| config.key.clone());
+    let mut price_sources = use_signal(|| price_settings.sources.clone());
+    let mut price_mode = use_signal(|| price_settings.mode);
+    let mut price_currency = use_signal(|| price_settings.currency.clone());
+    let mut websocket_url = use_signal(|| app_state.read().wallet.settings.websocket_url.clone());
+    let work_settings = app_state.read().wallet.settings.work.clone();
+    let mut work_source = use_signal(|| work_settings.source);
+    let mut work_threads = use_signal(|| work_settings.threads);
+    let mut work_precompute = use_signal(|| work_settings.precompute);
+    let mut broadcast_nodes =
+        use_signal(|| app_state.read().wallet.settings.broadcast_nodes.join(", "));
+    let daemon_settings = app_state.read().wallet.settings.daemon.clone();
+    let mut daemon_url = use_signal(|| daemon_settings.url.clone());
+    let mut daemon_token = use_signal(|| daemon_settings.token.clone());
+    let network_settings = app_state.read().wallet.settings.network.clone();
+    let mut network = use_signal(|| network_settings.network);
+    let mut address_prefix = use_signal(|| network_settings.prefix.clone());
 
+    // result of the check of the node done before saving (`None` until something is saved)
+    let mut node_check = use_signal(|| None::<Result<NodeHealth, String>>);
+    let mut checking_node = use_signal(|| false);
+
+    // closure that checks the node and only saves the configuration if it can be used
     let save_config = move |_| {
-        let mut config = app_state.read().config_file.clone();
-        let config_file_path = app_state.read().config_file_path.clone();
-        config.key = api_key.read().clone();
-        config.url = rpc_url.read().clone();
+        spawn(async move {
+            let url = rpc_url.read().trim().to_string();
+            let key = api_key.read().clone();
+            let expected_network = network.read().name();
+
+            checking_node.set(true);
+            let health = check_node(&url, &key, expected_network)
+                .await
+                .map_err(|e| e.to_string());
+            checking_node.set(false);
+            if health.is_err() {
+                node_check.set(Some(health));
+                return;
+            }
+
+            // the changes are applied to a copy so nothing changes if they can't be saved
+            let mut wallet = app_state.read().wallet.clone();
+            wallet.config_file.key = key;
+            wallet.config_file.url = url;
+
+            let settings = &mut wallet.settings;
+            settings.price.sources = price_sources.read().clone();
+            settings.price.mode = *price_mode.read();
+            settings.price.currency = price_currency.read().clone();
+            settings.websocket_url = websocket_url.read().trim().to_string();
+            settings.work.source = *work_source.read();
+            settings.work.threads = *work_threads.read();
+            settings.work.precompute = *work_precompute.read();
+            settings.broadcast_nodes = broadcast_nodes
+                .read()
+                .split(',')
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .collect();
+            settings.daemon.url = daemon_url.read().trim().to_string();
+            settings.daemon.token = daemon_token.read().trim().to_string();
+            settings.network = NetworkSettings {
+                network: *network.read(),
+                prefix: address_prefix
+                    .read()
+                    .trim()
+                    .trim_end_matches('_')
+                    .to_string(),
+            };
 
-        config.to_file_sync(&config_file_path).unwrap();
-        app_state.write().config_file = config;
+            if let Err(e) = wallet.save() {
+                node_check.set(Some(Err(e)));
+                return;
+            }
+            // the open account is shown with the prefix of the new network
+            if let Some(public_key) = account_to_public_key(&wallet.nano_account) {
+                wallet.nano_account = wallet.settings.network.account(&public_key);
+            }
+            app_state.write().wallet = wallet;
+            node_check.set(Some(health));
+        });
     };
 
     rsx! {
         div {
             id: "card",
             strong { id: "secondary" , style: "display: inline-block; margin-bottom: 36px;", "RPC CONFIGURATION" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Network:" }
+                select {
+                    id: "select",
+                    value: network().name(),
+                    // switching the network fills in the links of its default node
+                    onchange: move |event| {
+                        let selected = Network::from_name(&event.value()).unwrap_or_default();
+                        network.set(selected);
+                        rpc_url.set(selected.default_rpc_url().to_string());
+                        websocket_url.set(selected.default_websocket_url().to_string());
+                    },
+                    for option in Network::ALL {
+                        option { value: option.name(), {option.name().to_uppercase()} }
+                    }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Address Prefix:" }
+                input {
+                    id: "input",
+                    value: address_prefix(),
+                    placeholder: "nano",
+                    onchange: move |event| address_prefix.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
             div {
                 id: "column-section",
                 span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Key:" }
@@ -951,15 +2822,182 @@ fn TransactionConfig() -> Element {
 
                 }
             }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "WebSocket Url:" }
+                input {
+                    id: "input",
+                    value: websocket_url(),
+                    placeholder: "Leave empty to poll the RPC",
+                    onchange: move |event| websocket_url.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Broadcast Nodes:" }
+                input {
+                    id: "input",
+                    value: broadcast_nodes(),
+                    placeholder: "Other RPC links separated by commas",
+                    onchange: move |event| broadcast_nodes.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Daemon Url:" }
+                input {
+                    id: "input",
+                    value: daemon_url(),
+                    placeholder: "Leave empty to sign from this application",
+                    onchange: move |event| daemon_url.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Daemon Token:" }
+                input {
+                    id: "input",
+                    r#type: "password",
+                    value: daemon_token(),
+                    onchange: move |event| daemon_token.set(event.value()),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Price Currency:" }
+                select {
+                    id: "select",
+                    value: price_currency(),
+                    onchange: move |event| price_currency.set(event.value()),
+                    option { value: "eur", "EUR" }
+                    option { value: "usd", "USD" }
+                    option { value: "gbp", "GBP" }
+                    option { value: "jpy", "JPY" }
+                    option { value: "brl", "BRL" }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Price Sources:" }
+                for source in PriceSource::ALL {
+                    div {
+                        id: "fill-card",
+                        span { id: "secondary", {source.name()} }
+                        input {
+                            r#type: "checkbox",
+                            checked: price_sources.read().contains(&source),
+                            onchange: move |event| {
+                                let mut sources = price_sources.write();
+                                sources.retain(|s| *s != source);
+                                if event.checked() {
+                                    sources.push(source);
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Price Mode:" }
+                select {
+                    id: "select",
+                    value: match price_mode() {
+                        PriceMode::FirstAvailable => "FIRST",
+                        PriceMode::Median => "MEDIAN",
+                    },
+                    onchange: move |event| price_mode.set(match event.value().as_str() {
+                        "MEDIAN" => PriceMode::Median,
+                        _ => PriceMode::FirstAvailable,
+                    }),
+                    option { value: "FIRST", "FIRST AVAILABLE" }
+                    option { value: "MEDIAN", "MEDIAN OF SOURCES" }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Work Source:" }
+                select {
+                    id: "select",
+                    value: match work_source() {
+                        WorkSourceKind::Remote => "REMOTE",
+                        WorkSourceKind::Local => "LOCAL",
+                        WorkSourceKind::RemoteThenLocal => "REMOTE_THEN_LOCAL",
+                    },
+                    onchange: move |event| work_source.set(match event.value().as_str() {
+                        "REMOTE" => WorkSourceKind::Remote,
+                        "LOCAL" => WorkSourceKind::Local,
+                        _ => WorkSourceKind::RemoteThenLocal,
+                    }),
+                    option { value: "REMOTE_THEN_LOCAL", "NODE, THEN THIS COMPUTER" }
+                    option { value: "REMOTE", "NODE" }
+                    option { value: "LOCAL", "THIS COMPUTER" }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "column-section",
+                span { id: "sub-heading", style: "display: inline-block; margin-bottom: 8px;", "Work Threads:" }
+                input {
+                    id: "input",
+                    r#type: "number",
+                    min: "0",
+                    value: work_threads().to_string(),
+                    placeholder: "0 uses every thread",
+                    onchange: move |event| work_threads.set(event.value().parse().unwrap_or(0)),
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 14px;" }
+            div {
+                id: "fill-card",
+                span { id: "secondary", "Precompute the work of the next block" }
+                input {
+                    r#type: "checkbox",
+                    checked: work_precompute(),
+                    onchange: move |event| work_precompute.set(event.checked()),
+                }
+            }
             div { style: "display: inline-block; margin-bottom: 36px;" }
             div {
                 id: "column-section",
                 button {
                     id: "button",
+                    disabled: checking_node(),
                     onclick: save_config,
                     "Save",
                 }
             }
+            {
+                match (checking_node(), &*node_check.read()) {
+                    (true, _) => rsx! {
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        span { id: "secondary", "Checking the node..." }
+                    },
+                    (false, Some(Ok(health))) => rsx! {
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        span {
+                            id: "secondary",
+                            {format!("Saved. Connected to {} on the {} network ({} ms, {} blocks).", health.vendor, health.network, health.latency.as_millis(), health.block_count)}
+                        }
+                        for warning in health.warnings.iter() {
+                            span { id: "secondary", {warning.clone()} }
+                        }
+                    },
+                    (false, Some(Err(e))) => rsx! {
+                        div { style: "display: inline-block; margin-bottom: 14px;" }
+                        span { id: "secondary", {format!("Not saved. {e}")} }
+                    },
+                    (false, None) => rsx! {},
+                }
+            }
         }
     }
 }
@@ -1,15 +1,11 @@
 //! This file contains the different building blocks that form the home page of the application.
 
-use crate::{AppState, Route, TransactionState, MAIN_CSS, PORT};
+use crate::{AppState, Route, TransactionState, MAIN_CSS};
 use dioxus::prelude::*;
 use dioxus_material_icons::MaterialIconStylesheet;
 use dioxus_router::hooks::use_navigator;
-use frost_sig::{client::SignInput, nano::account::public_key_to_nano_account};
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-    time::Duration,
-};
+use frost_sig::FrostState;
+use wallet_core::{Role, Session};
 
 #[component]
 pub fn Home() -> Element {
@@ -18,6 +14,8 @@ pub fn Home() -> Element {
         MaterialIconStylesheet{}
         div {
             id: "page",
+            ConfigurationErrors{}
+            PendingPayment{}
             CreateAccountSession{}
             div { style: "display: inline-block; margin-bottom: 28px;" }
             OpenAccount{}
@@ -25,6 +23,51 @@ pub fn Home() -> Element {
     }
 }
 
+/// Function that represents the problems found in the configuration when the application started.
+#[component]
+fn ConfigurationErrors() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let errors = app_state.read().wallet.config_errors.clone();
+
+    rsx! {
+        if !errors.is_empty() {
+            div {
+                id: "card",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "CONFIGURATION PROBLEMS" }
+                span { id: "secondary", style: "display: inline-block; margin-bottom: 14px;", "The default values are used for what couldn't be read, and the files are kept as they are until the configuration is saved again." }
+                for error in errors {
+                    div { style: "display: inline-block; margin-bottom: 8px;" }
+                    span { id: "secondary", {error} }
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 28px;" }
+        }
+    }
+}
+
+/// Function that represents the payment request the wallet was asked to pay before an account was opened.
+#[component]
+fn PendingPayment() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let payment_uri = app_state.read().payment_uri.clone();
+
+    rsx! {
+        if let Some(payment_uri) = payment_uri {
+            div {
+                id: "card",
+                strong { id: "secondary" , style: "display: inline-block; margin-bottom: 14px;", "PAYMENT REQUEST" }
+                span { id: "secondary", style: "display: inline-block; margin-bottom: 14px; overflow-wrap: anywhere;", "Open the account that pays {payment_uri} and the SEND form will be filled with it." }
+                button {
+                    id: "button",
+                    onclick: move |_| app_state.write().payment_uri = None,
+                    "Dismiss"
+                }
+            }
+            div { style: "display: inline-block; margin-bottom: 28px;" }
+        }
+    }
+}
+
 #[component]
 fn CreateAccountSession() -> Element {
     let mut participants = use_signal(|| "2".to_string());
@@ -32,118 +75,42 @@ fn CreateAccountSession() -> Element {
     let mut path = use_signal(|| "account.json".to_string());
     let mut operation_type = use_signal(|| "OPEN".to_string());
     let mut ip_address = use_signal(|| "localhost".to_string());
-    let mut is_completed = use_signal_sync(|| false);
-    let mut transaction_state = use_signal_sync(|| TransactionState::Idle);
+    let mut transaction_state = use_signal(|| TransactionState::Idle);
 
     let mut app_state = use_context::<Signal<AppState>>();
     let nav = use_navigator();
 
-    use_effect(move || {
-        if is_completed() {
-            app_state.write().account_path = path.read().to_string();
-
-            let sign_input: SignInput = {
-                match File::open(path.read().to_string()) {
-                    Ok(file) => {
-                        let mut buf_reader = BufReader::new(file);
-                        let mut contents = String::new();
-                        buf_reader.read_to_string(&mut contents).unwrap();
-
-                        serde_json::from_str::<SignInput>(&contents).unwrap()
-                    }
-                    Err(_) => {
-                        nav.push(Route::Home {});
-                        SignInput::default()
-                    }
-                }
-            };
-
-            app_state.write().nano_account =
-                public_key_to_nano_account(&sign_input.public_aggregated_key.to_bytes());
-            app_state.write().frost_state = sign_input.state;
-            nav.push(Route::Dashboard {});
-        }
-    });
-
-    let open_and_connect_to_socket = move |_| {
-        let participants = participants.read().parse::<u32>().unwrap_or(0);
-        let threshold = threshold.read().parse::<u32>().unwrap_or(0);
+    // closure that creates the account with the group and opens it once it's saved
+    let create_account = move |role: Role| {
+        let group = FrostState::new(
+            participants.read().parse::<u32>().unwrap_or(0),
+            threshold.read().parse::<u32>().unwrap_or(0),
+        );
         let path = path.read().clone();
 
-        let server = tokio::spawn(async move {
+        spawn(async move {
             transaction_state.set(TransactionState::Processing);
-            match frost_sig::server::keygen_server::run("localhost", PORT, participants, threshold)
+            let mut wallet = app_state.peek().wallet.clone();
+            let (session, _) = Session::new();
+            match session
+                .create_account(&mut wallet, &role, group, &path)
                 .await
             {
-                Ok(_) => {}
-                Err(_) => {
-                    transaction_state.set(TransactionState::Error(
-                        "Failed to process the transaction".to_string(),
-                    ));
-                }
-            };
-        });
-
-        let client = tokio::spawn(async move {
-            tokio::time::sleep(Duration::from_secs(2)).await;
-            match frost_sig::client::keygen_client::run("localhost", PORT, &path).await {
-                Ok(_) => {}
-                Err(_) => {
-                    transaction_state.set(TransactionState::Error(
-                        "Failed to process the transaction".to_string(),
-                    ));
-                }
-            };
-        });
-
-        tokio::spawn(async move {
-            match (client.await, server.await) {
-                (Ok(_), Ok(_)) => {
-                    if !matches!(*transaction_state.read(), TransactionState::Error(_)) {
-                        transaction_state.set(TransactionState::Successful);
-                        is_completed.set(true);
-                    }
-                }
-                (_, _) => {
-                    transaction_state.set(TransactionState::Error(format!(
-                        "Error while creating the account."
-                    )));
+                Ok(_) => {
+                    transaction_state.set(TransactionState::Successful);
+                    app_state.write().wallet = wallet;
+                    nav.push(Route::Dashboard {});
                 }
+                Err(_) => transaction_state.set(TransactionState::Error(
+                    "Error while creating the account.".to_string(),
+                )),
             }
         });
-
-        println!("Server and Clients listening.");
     };
 
-    let connect_to_socket = move |_| {
-        let path = path.read().clone();
-        let ip_address = ip_address.read().clone();
+    let open_and_connect_to_socket = move |_| create_account(Role::Coordinator);
 
-        let client = tokio::spawn(async move {
-            match frost_sig::client::keygen_client::run(&ip_address, PORT, &path).await {
-                Ok(_) => {}
-                Err(_) => {
-                    transaction_state.set(TransactionState::Error(format!(
-                        "Error while creating the account."
-                    )));
-                }
-            };
-        });
-
-        tokio::spawn(async move {
-            match client.await {
-                Ok(_) => {
-                    if !matches!(*transaction_state.read(), TransactionState::Error(_)) {
-                        transaction_state.set(TransactionState::Successful);
-                        is_completed.set(true);
-                    }
-                }
-                Err(e) => {
-                    transaction_state.set(TransactionState::Error(format!("{e}")));
-                }
-            }
-        });
-    };
+    let connect_to_socket = move |_| create_account(Role::Participant(ip_address.read().clone()));
 
     rsx! {
         div {
@@ -303,47 +270,15 @@ fn OpenAccount() -> Element {
     let nav = use_navigator();
 
     let open_dashboard_with_account = move |_| {
-        app_state.write().account_path = path.read().to_string();
-
         is_processing.set(TransactionState::Processing);
 
-        let sign_input: SignInput = {
-            match File::open(path.read().to_string()) {
-                Ok(file) => {
-                    let mut buf_reader = BufReader::new(file);
-                    let mut contents = String::new();
-                    match buf_reader.read_to_string(&mut contents) {
-                        Ok(_) => {}
-                        Err(e) => {
-                            is_processing.set(TransactionState::Error(e.to_string()));
-                            return;
-                        }
-                    };
-                    is_processing.set(TransactionState::Successful);
-
-                    match serde_json::from_str::<SignInput>(&contents) {
-                        Ok(input) => input,
-                        Err(_) => {
-                            is_processing.set(TransactionState::Error(
-                                "File has invalid format.".to_string(),
-                            ));
-                            return;
-                        }
-                    }
-                }
-                Err(_) => {
-                    is_processing.set(TransactionState::Error(
-                        "Couldn't open the file.".to_string(),
-                    ));
-                    return;
-                }
-            }
-        };
+        let result = app_state.write().wallet.open_account(&path.read());
+        if let Err(e) = result {
+            is_processing.set(TransactionState::Error(e));
+            return;
+        }
+        is_processing.set(TransactionState::Successful);
 
-        app_state.write().nano_account =
-            public_key_to_nano_account(&sign_input.public_aggregated_key.to_bytes());
-        app_state.write().frost_state = sign_input.state;
-        app_state.write().public_share = hex::encode(sign_input.own_public_share.as_bytes());
         nav.push(Route::Dashboard {});
     };
 
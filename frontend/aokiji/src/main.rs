@@ -1,14 +1,21 @@
-//! This file initializes the application and contains shared data structures and constants.
+//! This file initializes the desktop application and contains the data structures shared by its pages.
 
 use dioxus::{
-    desktop::{Config, WindowBuilder},
+    desktop::{tao::event::Event, use_wry_event_handler, window, Config, WindowBuilder},
     prelude::*,
 };
-use dioxus_desktop::{tao::platform::macos::WindowBuilderExtMacOS, LogicalSize};
+#[cfg(target_os = "macos")]
+use dioxus_desktop::tao::platform::macos::WindowBuilderExtMacOS;
+use dioxus_desktop::{tao::window::Icon, LogicalSize};
 use dioxus_router::prelude::*;
-use dirs::config_dir;
-use frost_sig::{client::ConfigFile, FrostState};
-use std::{env::current_dir, error::Error, fs::create_dir_all, path::PathBuf};
+use std::sync::Arc;
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver},
+    Mutex,
+};
+use wallet_core::{
+    get_config_directory, register_uri_handler, single_instance, uri_argument, Instance, Wallet,
+};
 
 mod dashboard;
 use dashboard::Dashboard;
@@ -25,9 +32,6 @@ const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
 /// Asset that represents the path to the font's css file (in this case Satoshi).
 const SATOSHI_CSS: Asset = asset!("assets/satoshi.css");
 
-/// Constant value of the port used for socket connections.
-pub const PORT: u32 = 6705;
-
 /// Enum that represents the different routes of the application.
 #[derive(Clone, Routable, Debug, PartialEq)]
 pub enum Route {
@@ -40,6 +44,29 @@ pub enum Route {
     Dashboard {},
 }
 
+/// Struct that represents unites the variables shared across the application.
+#[derive(Clone, Debug, Default)]
+pub struct AppState {
+    /// Wallet with the open account.
+    pub wallet: Wallet,
+
+    /// Counter increased every time the open account changes on the network.
+    pub account_updates: u64,
+
+    /// Counter increased every time the scheduled payments change or may have become due.
+    pub schedule_updates: u64,
+
+    /// Error of the last pass over the outbox (e.g. its file couldn't be read or saved).
+    pub outbox_error: Option<String>,
+
+    /// `nano:` link the wallet was asked to pay, waiting for the SEND form of an open account.
+    pub payment_uri: Option<String>,
+}
+
+/// Struct that represents the links the instances started after this one handed to it.
+#[derive(Clone)]
+struct ForwardedLinks(Arc<Mutex<UnboundedReceiver<String>>>);
+
 /// Enum that represents the real-time state of an operation.
 #[derive(Clone)]
 pub enum TransactionState {
@@ -49,95 +76,129 @@ pub enum TransactionState {
     Error(String),
 }
 
-/// Struct that represents unites the variables shared across the application.
-#[derive(Clone, Debug)]
-pub struct AppState {
-    /// Path of the account's file.
-    pub account_path: String,
-
-    /// Nano account currently open.
-    pub nano_account: String,
-
-    /// Public share that identifies the user inside the group.
-    pub public_share: String,
-
-    /// FROST parameters of the account.
-    pub frost_state: FrostState,
+/// Main function of the application.
+fn main() {
+    // a link clicked while the wallet runs is handed to the open window instead of starting another one
+    let uri = uri_argument(std::env::args().skip(1));
+    let instance = get_config_directory()
+        .map_err(|e| e.to_string())
+        .and_then(|directory| single_instance(&directory, uri.as_deref()));
+    let links = match instance {
+        Ok(Instance::Primary(links)) => links,
+        Ok(Instance::Forwarded) => return,
+        // the wallet still starts, it just won't receive the links clicked while it runs
+        Err(e) => {
+            eprintln!("{e}");
+            unbounded_channel().1
+        }
+    };
+    let links = ForwardedLinks(Arc::new(Mutex::new(links)));
 
-    /// Configuration file.
-    pub config_file: ConfigFile,
+    // the wallet opens the `nano:` links clicked anywhere on the desktop
+    std::thread::spawn(|| {
+        if let Err(e) = register_uri_handler() {
+            eprintln!("{e}");
+        }
+    });
 
-    /// Configuration file path.
-    pub config_file_path: String,
+    dioxus::LaunchBuilder::new()
+        .with_cfg(desktop_config())
+        .with_context(links)
+        .launch(App);
 }
 
-impl Default for AppState {
-    /// Function that returns a default value for the `AppState`.
-    fn default() -> Self {
-        let config_file_path = {
-            let config_path = get_config_directory().unwrap_or(PathBuf::default());
-            config_path
-                .join("config.json")
-                .into_os_string()
-                .into_string()
-                .unwrap_or("".to_string())
-        };
-
-        Self {
-            account_path: "".to_string(),
-            nano_account: "".to_string(),
-            frost_state: FrostState::new(0, 0),
-            public_share: "".to_string(),
-            config_file: ConfigFile::from_file_sync(&config_file_path).unwrap_or(ConfigFile::new()),
-            config_file_path,
-        }
+/// Function that returns the configuration of the desktop window with the options of the platform it runs on.
+fn desktop_config() -> Config {
+    let window = WindowBuilder::new()
+        .with_title("Aokiji")
+        .with_max_inner_size(LogicalSize::new(505.0, 900.0))
+        .with_min_inner_size(LogicalSize::new(505.0, 400.0))
+        .with_resizable(true)
+        .with_decorations(true)
+        .with_always_on_top(false)
+        .with_focused(true)
+        .with_transparent(false);
+
+    #[cfg(target_os = "macos")]
+    let window = window.with_has_shadow(true);
+
+    let config = Config::new().with_window(window);
+    match window_icon() {
+        Some(icon) => config.with_icon(icon),
+        None => config,
     }
 }
 
-/// Function that gets or creates the config file directory according to the operating system.
-fn get_config_directory() -> Result<PathBuf, Box<dyn Error>> {
-    let app = "Aokiji";
-
-    let config_dir = match config_dir() {
-        Some(config_dir) => config_dir.join(app),
-        _ => current_dir()?.join(app),
-    };
-
-    if !config_dir.exists() {
-        create_dir_all(&config_dir)?;
-    }
-
-    Ok(config_dir)
+/// Function that returns the icon of the window (macOS takes it from the bundle instead).
+#[cfg(not(target_os = "macos"))]
+fn window_icon() -> Option<Icon> {
+    let image = image::load_from_memory(include_bytes!("../assets/icons/256x256.png"))
+        .ok()?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    Icon::from_rgba(image.into_raw(), width, height).ok()
 }
 
-/// Main function of the application.
-fn main() {
-    dioxus::LaunchBuilder::new()
-        .with_cfg(
-            Config::new().with_window(
-                WindowBuilder::new()
-                    .with_title("Aokiji")
-                    .with_max_inner_size(LogicalSize::new(505.0, 900.0))
-                    .with_min_inner_size(LogicalSize::new(505.0, 400.0))
-                    .with_resizable(true)
-                    .with_decorations(true)
-                    .with_has_shadow(true)
-                    .with_always_on_top(false)
-                    .with_focused(true)
-                    .with_transparent(false),
-            ),
-        )
-        .launch(App);
+/// Function that returns the icon of the window (macOS takes it from the bundle instead).
+#[cfg(target_os = "macos")]
+fn window_icon() -> Option<Icon> {
+    None
 }
 
 /// Default app component that will define the font, layout and background but also initialize the app state.
 #[component]
 fn App() -> Element {
-    use_context_provider(|| Signal::new(AppState::default()));
+    let mut app_state = use_context_provider(|| {
+        Signal::new(AppState {
+            payment_uri: uri_argument(std::env::args().skip(1)),
+            ..AppState::default()
+        })
+    });
+
+    // the links of the instances started later are shown in this window
+    let links = use_context::<ForwardedLinks>();
+    use_future(move || {
+        let links = links.clone();
+        async move {
+            let mut links = links.0.lock().await;
+            while let Some(link) = links.recv().await {
+                if let Some(uri) = uri_argument([link]) {
+                    app_state.write().payment_uri = Some(uri);
+                }
+                window().set_focus();
+            }
+        }
+    });
+
+    // on macOS the links arrive as an event, even the one the wallet was started with
+    use_wry_event_handler(move |event, _| {
+        if let Event::Opened { urls } = event {
+            if let Some(uri) = uri_argument(urls.iter().map(|url| url.to_string())) {
+                app_state.write().payment_uri = Some(uri);
+            }
+        }
+    });
 
     rsx! {
         document::Link { rel: "stylesheet", href: APP_CSS }
         document::Link { rel: "stylesheet", href: SATOSHI_CSS }
+        NetworkBanner {}
         Router::<Route>{}
     }
 }
+
+/// Function that represents the banner shown on every page while the wallet isn't on the live network.
+#[component]
+fn NetworkBanner() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let banner = app_state.read().wallet.settings.network.network.banner();
+
+    rsx! {
+        if let Some(banner) = banner {
+            div {
+                style: "position: sticky; top: 0; z-index: 10; padding: 10px; text-align: center; font-weight: bold; color: white; background-color: #d32f2f;",
+                "{banner}"
+            }
+        }
+    }
+}
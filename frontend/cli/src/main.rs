@@ -0,0 +1,822 @@
+//! This file contains the command line interface used to run the group operations on servers and in scripts.
+//! Every command prints a JSON object, and failures print `{"error": ...}` with a non-zero exit code.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use frost_sig::FrostState;
+use routes::{
+    account_to_public_key, current_time, format_timestamp, parse_timestamp, raw_to_nano, Cadence,
+    Network, PayoutBatch, PayoutStatus, ScheduledPayment, DAY,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use wallet_core::{
+    daemon_token, get_balance, get_history, AccountSettings, Daemon, Proposal, Role, Session,
+    TransactionKind, Wallet, DAEMON_PORT, DAEMON_TOKEN_FILE,
+};
+
+/// Struct that represents the arguments of the command line interface.
+#[derive(Parser, Debug)]
+#[command(
+    name = "aokiji-cli",
+    version,
+    about = "Manage Nano accounts shared by a group."
+)]
+struct Cli {
+    /// Directory with the configuration of the wallet (defaults to the one used by the desktop application).
+    #[arg(long, global = true)]
+    config_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Enum that represents the commands of the command line interface.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create a shared account with the group.
+    #[command(subcommand)]
+    Keygen(KeygenCommand),
+
+    /// Sign a block with the group.
+    #[command(subcommand)]
+    Sign(SignCommand),
+
+    /// Show the balance of an account.
+    Balance(AccountArgs),
+
+    /// Show the last blocks of an account.
+    History {
+        #[command(flatten)]
+        account: AccountArgs,
+
+        /// Number of blocks listed.
+        #[arg(long, default_value_t = 50)]
+        count: u32,
+    },
+
+    /// Check that an account file holds the expected account and public share.
+    VerifyShare {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        /// Public share the file must hold (hex).
+        #[arg(long)]
+        share: Option<String>,
+
+        /// Account the file must belong to.
+        #[arg(long)]
+        account: Option<String>,
+    },
+
+    /// Show or change the configuration of the wallet.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Show or change the payments an account makes on a schedule.
+    #[command(subcommand)]
+    Schedule(ScheduleCommand),
+
+    /// Keep accounts open in the background and serve the JSON-RPC API on localhost.
+    Daemon {
+        #[arg(long, default_value_t = DAEMON_PORT)]
+        port: u16,
+
+        /// Path of the file of each account kept open.
+        #[arg(long, required = true)]
+        account_file: Vec<String>,
+    },
+}
+
+/// Enum that represents the ways of taking part in a key generation session.
+#[derive(Subcommand, Debug)]
+enum KeygenCommand {
+    /// Open the session as the coordinator and take part in it.
+    Open {
+        #[arg(long)]
+        participants: u32,
+
+        #[arg(long)]
+        threshold: u32,
+
+        /// Path where the account file is saved.
+        #[arg(long)]
+        path: String,
+    },
+
+    /// Join the session opened by the coordinator.
+    Join {
+        /// Address of the coordinator.
+        #[arg(long)]
+        ip: String,
+
+        /// Path where the account file is saved.
+        #[arg(long)]
+        path: String,
+    },
+}
+
+/// Enum that represents the ways of taking part in a signing session.
+#[derive(Subcommand, Debug)]
+enum SignCommand {
+    /// Open the session as the coordinator and take part in it.
+    Start(SignArgs),
+
+    /// Join the session opened by the coordinator.
+    Join {
+        /// Address of the coordinator.
+        #[arg(long)]
+        ip: String,
+
+        #[command(flatten)]
+        sign: SignArgs,
+    },
+}
+
+/// Enum that represents the types of blocks the group can sign.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum BlockType {
+    Send,
+    Receive,
+    Open,
+    Change,
+
+    /// Receive every receivable block, one round each.
+    ReceiveAll,
+
+    /// Send every payout of a CSV file, one round each.
+    Batch,
+}
+
+/// Struct that represents the block every participant builds before signing it.
+#[derive(Args, Debug)]
+struct SignArgs {
+    /// Path of the account file.
+    #[arg(long)]
+    account_file: String,
+
+    #[arg(long = "type", value_enum)]
+    block_type: BlockType,
+
+    /// Account that receives the Nano (send).
+    #[arg(long, default_value = "")]
+    to: String,
+
+    /// Amount of Nano sent (send).
+    #[arg(long, default_value = "0")]
+    amount: String,
+
+    /// Payment request to pay (send): a `nano:` link with the receiver and, if it has one, the amount.
+    #[arg(long, conflicts_with = "to")]
+    uri: Option<String>,
+
+    /// Scheduled payment to make (send): its oldest due payment is signed and recorded as paid.
+    #[arg(long, conflicts_with_all = ["to", "uri"])]
+    scheduled: Option<String>,
+
+    /// New representative (change), or the representative of a new account (open).
+    #[arg(long, default_value = "")]
+    representative: String,
+
+    /// CSV file with an `address,amount` row per payout (batch).
+    #[arg(long, required_if_eq("block_type", "batch"))]
+    csv: Option<PathBuf>,
+}
+
+/// Struct that represents the account a read-only command is about.
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+struct AccountArgs {
+    /// Path of the account file.
+    #[arg(long)]
+    account_file: Option<String>,
+
+    /// Address of the account.
+    #[arg(long)]
+    address: Option<String>,
+}
+
+/// Enum that represents the configuration commands.
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Show the configuration.
+    Show,
+
+    /// Change the given values of the configuration.
+    Set {
+        /// RPC link of the node.
+        #[arg(long)]
+        url: Option<String>,
+
+        /// API key of the node.
+        #[arg(long)]
+        key: Option<String>,
+
+        /// WebSocket link of the node.
+        #[arg(long)]
+        websocket_url: Option<String>,
+
+        /// Other RPC links the signed blocks are published to (replaces the current list).
+        #[arg(long)]
+        broadcast_node: Option<Vec<String>>,
+
+        /// Network of the node: live, beta, test or dev (also sets the default links unless they are given).
+        #[arg(long)]
+        network: Option<String>,
+
+        /// Prefix of the accounts on the network (e.g. "nano").
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+
+    /// Show or change the settings of an account that replace the global ones (empty values use the global ones).
+    Account {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        /// RPC link of the account's node.
+        #[arg(long)]
+        url: Option<String>,
+
+        /// API key of the account's node.
+        #[arg(long)]
+        key: Option<String>,
+
+        /// WebSocket link of the account's node.
+        #[arg(long)]
+        websocket_url: Option<String>,
+
+        /// Other RPC links the account's blocks are published to (replaces the current list).
+        #[arg(long)]
+        broadcast_node: Option<Vec<String>>,
+
+        /// Currency the account's balance is converted to.
+        #[arg(long)]
+        currency: Option<String>,
+
+        /// Representative used when the account is opened.
+        #[arg(long)]
+        representative: Option<String>,
+
+        /// Port the coordinator of the account's signing sessions listens on (0 to use the default).
+        #[arg(long)]
+        port: Option<u32>,
+
+        /// Remove every setting of the account before applying the given ones.
+        #[arg(long)]
+        reset: bool,
+    },
+}
+
+/// Enum that represents the commands of the scheduled payments.
+#[derive(Subcommand, Debug)]
+enum ScheduleCommand {
+    /// Show the payments of an account from the given number of days ago to as many days ahead.
+    List {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        #[arg(long, default_value_t = 30)]
+        days: u64,
+    },
+
+    /// Add a payment to the schedule of an account.
+    Add {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        /// Account that receives the Nano.
+        #[arg(long)]
+        to: String,
+
+        /// Amount of Nano sent.
+        #[arg(long)]
+        amount: String,
+
+        /// How often the payment repeats: once, daily, weekly or monthly.
+        #[arg(long, default_value = "monthly")]
+        cadence: String,
+
+        /// Time of the first payment, "YYYY-MM-DD" or "YYYY-MM-DD HH:MM" (UTC).
+        #[arg(long)]
+        start: String,
+
+        /// What the payment is for.
+        #[arg(long, default_value = "")]
+        label: String,
+    },
+
+    /// Remove a payment from the schedule of an account.
+    Remove {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        #[arg(long)]
+        id: String,
+    },
+
+    /// Record that the oldest due payment won't be made.
+    Skip {
+        /// Path of the account file.
+        #[arg(long)]
+        account_file: String,
+
+        #[arg(long)]
+        id: String,
+    },
+}
+
+/// Main function of the command line interface.
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let wallet = match &cli.config_dir {
+        Some(config_dir) => Wallet::from_directory(config_dir.clone()),
+        None => Wallet::default(),
+    };
+
+    match run(cli.command, wallet).await {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", json!({ "error": e }));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Function that runs a command and returns its output.
+async fn run(command: Command, mut wallet: Wallet) -> Result<Value, String> {
+    // only the configuration commands run until the problems found while loading it are fixed
+    if !wallet.config_errors.is_empty() && !matches!(command, Command::Config(_)) {
+        return Err(format!(
+            "The configuration has problems (fix them with `config set`): {}",
+            wallet.config_errors.join(" ")
+        ));
+    }
+
+    match command {
+        Command::Keygen(command) => keygen(command, wallet).await,
+        Command::Sign(SignCommand::Start(sign_args)) => {
+            sign(sign_args, Role::Coordinator, wallet).await
+        }
+        Command::Sign(SignCommand::Join {
+            ip,
+            sign: sign_args,
+        }) => sign(sign_args, Role::Participant(ip), wallet).await,
+        Command::Balance(account) => {
+            let address = address(&mut wallet, account)?;
+            to_value(get_balance(&wallet.node(), &address).await)
+        }
+        Command::History { account, count } => {
+            let address = address(&mut wallet, account)?;
+            to_value(get_history(&wallet.node(), &address, count).await)
+        }
+        Command::VerifyShare {
+            account_file,
+            share,
+            account,
+        } => {
+            wallet.open_account(&account_file)?;
+            let share_matches = share
+                .map(|share| share.eq_ignore_ascii_case(&wallet.public_share))
+                .unwrap_or(true);
+            let account_matches = account
+                .map(|account| {
+                    account_to_public_key(&account).is_some()
+                        && account_to_public_key(&account)
+                            == account_to_public_key(&wallet.nano_account)
+                })
+                .unwrap_or(true);
+            let output = json!({
+                "account": wallet.nano_account,
+                "public_share": wallet.public_share,
+                "participants": wallet.frost_state.participants,
+                "threshold": wallet.frost_state.threshold,
+                "valid": share_matches && account_matches,
+            });
+            match share_matches && account_matches {
+                true => Ok(output),
+                false => Err(format!(
+                    "The account file doesn't match the expected {}.",
+                    match share_matches {
+                        true => "account",
+                        false => "public share",
+                    }
+                )),
+            }
+        }
+        Command::Config(command) => config(command, wallet),
+        Command::Schedule(command) => schedule(command, wallet),
+        Command::Daemon { port, account_file } => daemon(port, account_file, wallet).await,
+    }
+}
+
+/// Function that turns the result of a command into JSON.
+fn to_value<T: Serialize>(result: Result<T, String>) -> Result<Value, String> {
+    result.and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string()))
+}
+
+/// Function that returns the address of the account a read-only command is about.
+fn address(wallet: &mut Wallet, account: AccountArgs) -> Result<String, String> {
+    match (account.address, account.account_file) {
+        (Some(address), _) => Ok(address),
+        (None, Some(account_file)) => {
+            wallet.open_account(&account_file)?;
+            Ok(wallet.nano_account.clone())
+        }
+        (None, None) => Err("Choose an account file or an address.".to_string()),
+    }
+}
+
+/// Function that takes part in a key generation session and opens the account it creates.
+async fn keygen(command: KeygenCommand, mut wallet: Wallet) -> Result<Value, String> {
+    let (role, group, path) = match command {
+        KeygenCommand::Open {
+            participants,
+            threshold,
+            path,
+        } => (
+            Role::Coordinator,
+            FrostState::new(participants, threshold),
+            path,
+        ),
+        KeygenCommand::Join { ip, path } => (Role::Participant(ip), FrostState::new(0, 0), path),
+    };
+
+    let (session, _) = Session::new();
+    session
+        .create_account(&mut wallet, &role, group, &path)
+        .await?;
+
+    Ok(json!({
+        "account": wallet.nano_account,
+        "public_share": wallet.public_share,
+        "participants": wallet.frost_state.participants,
+        "threshold": wallet.frost_state.threshold,
+        "account_file": path,
+    }))
+}
+
+/// Function that builds a block and signs it with the group.
+async fn sign(sign_args: SignArgs, role: Role, mut wallet: Wallet) -> Result<Value, String> {
+    wallet.open_account(&sign_args.account_file)?;
+    let (session, _) = Session::new();
+
+    if (sign_args.uri.is_some() || sign_args.scheduled.is_some())
+        && sign_args.block_type != BlockType::Send
+    {
+        return Err("A payment can only be made with a send block.".to_string());
+    }
+    let scheduled = match &sign_args.scheduled {
+        Some(id) => Some(wallet.due_payment(id)?),
+        None => None,
+    };
+    let (receivers_account, amount) = match (&sign_args.uri, &scheduled) {
+        (Some(uri), _) => {
+            let request = wallet.payment_request(uri)?;
+            let amount = request.amount_in_nano().unwrap_or(sign_args.amount);
+            (request.account, amount)
+        }
+        (None, Some((payment, _))) => (payment.receivers_account.clone(), payment.amount.clone()),
+        (None, None) => (sign_args.to, sign_args.amount),
+    };
+
+    let kind = match sign_args.block_type {
+        BlockType::Send => TransactionKind::Send,
+        BlockType::Receive => TransactionKind::Receive,
+        BlockType::Open => TransactionKind::Open,
+        BlockType::Change => TransactionKind::Change,
+        BlockType::ReceiveAll => {
+            let received = session.receive_all(&mut wallet, &role).await?;
+            let blocks: Vec<Value> = received
+                .iter()
+                .map(|(hash, amount)| json!({ "hash": hash, "amount": raw_to_nano(*amount) }))
+                .collect();
+            return Ok(json!({ "account": wallet.nano_account, "received": blocks }));
+        }
+        BlockType::Batch => {
+            let path = sign_args.csv.unwrap_or_default();
+            let csv = std::fs::read_to_string(&path)
+                .map_err(|e| format!("{} couldn't be read: {e}", path.display()))?;
+            return send_batch(&session, &PayoutBatch::parse(&csv)?, &role, wallet).await;
+        }
+    };
+    let proposal = Proposal {
+        kind,
+        receivers_account,
+        amount,
+        representative: sign_args.representative,
+    };
+
+    let hash = session.sign(&mut wallet, &role, &proposal).await?;
+    if let Some((payment, due)) = scheduled {
+        wallet.schedule.mark_paid(&payment.id, due)?;
+    }
+    let status = wallet
+        .outbox
+        .entries()
+        .into_iter()
+        .find(|entry| entry.hash == hash)
+        .map(|entry| entry.status.name());
+
+    Ok(json!({
+        "hash": hash,
+        "account": wallet.nano_account,
+        "balance": wallet.known_balance.map(raw_to_nano),
+        "status": status,
+    }))
+}
+
+/// Function that signs the send blocks of a batch of payouts with the group and lists the result of each row.
+async fn send_batch(
+    session: &Session,
+    batch: &PayoutBatch,
+    role: &Role,
+    mut wallet: Wallet,
+) -> Result<Value, String> {
+    let statuses = session.send_batch(&mut wallet, role, batch).await?;
+    if let Some((payout, e)) = batch
+        .payouts
+        .iter()
+        .zip(&statuses)
+        .find_map(|(payout, status)| Some((payout, status.error()?)))
+    {
+        return Err(format!(
+            "Line {} couldn't be sent: {e} The rows before it were sent and the ones after it weren't.",
+            payout.line
+        ));
+    }
+
+    let payouts: Vec<Value> = batch
+        .payouts
+        .iter()
+        .zip(&statuses)
+        .map(|(payout, status)| {
+            let hash = match status {
+                PayoutStatus::Sent { hash } => Some(hash),
+                _ => None,
+            };
+            json!({
+                "line": payout.line,
+                "to": payout.receivers_account,
+                "amount": payout.amount,
+                "status": status.name(),
+                "hash": hash,
+            })
+        })
+        .collect();
+    Ok(json!({
+        "account": wallet.nano_account,
+        "total": batch.total().map(raw_to_nano),
+        "balance": wallet.known_balance.map(raw_to_nano),
+        "payouts": payouts,
+    }))
+}
+
+/// Function that shows or changes the scheduled payments of an account.
+fn schedule(command: ScheduleCommand, mut wallet: Wallet) -> Result<Value, String> {
+    match command {
+        ScheduleCommand::List { account_file, days } => {
+            wallet.open_account(&account_file)?;
+            let now = current_time();
+            let calendar: Vec<Value> = wallet
+                .schedule
+                .calendar(
+                    &wallet.nano_account,
+                    now.saturating_sub(days * DAY),
+                    now + days * DAY,
+                    now,
+                )
+                .into_iter()
+                .map(|entry| {
+                    json!({
+                        "id": entry.payment.id,
+                        "to": entry.payment.receivers_account,
+                        "amount": entry.payment.amount,
+                        "label": entry.payment.label,
+                        "cadence": entry.payment.cadence.name(),
+                        "due": format_timestamp(entry.due),
+                        "status": entry.status.name(),
+                    })
+                })
+                .collect();
+            Ok(json!({ "account": wallet.nano_account, "payments": calendar }))
+        }
+        ScheduleCommand::Add {
+            account_file,
+            to,
+            amount,
+            cadence,
+            start,
+            label,
+        } => {
+            wallet.open_account(&account_file)?;
+            let cadence = Cadence::from_name(&cadence)
+                .ok_or(format!("{cadence} isn't once, daily, weekly or monthly."))?;
+            let start = parse_timestamp(&start)
+                .ok_or(format!("{start} isn't a date written as YYYY-MM-DD HH:MM."))?;
+            let payment =
+                ScheduledPayment::new(&wallet.nano_account, &to, &amount, &label, cadence, start);
+            let id = payment.id.clone();
+            wallet.schedule_payment(payment)?;
+            Ok(json!({ "id": id, "first_payment": format_timestamp(start) }))
+        }
+        ScheduleCommand::Remove { account_file, id } => {
+            wallet.open_account(&account_file)?;
+            owned_payment(&wallet, &id)?;
+            wallet.schedule.remove(&id)?;
+            Ok(json!({ "removed": id }))
+        }
+        ScheduleCommand::Skip { account_file, id } => {
+            wallet.open_account(&account_file)?;
+            owned_payment(&wallet, &id)?;
+            let (_, due) = wallet.due_payment(&id)?;
+            wallet.schedule.skip(&id, due)?;
+            Ok(json!({ "skipped": id, "due": format_timestamp(due) }))
+        }
+    }
+}
+
+/// Function that checks that a scheduled payment is made by the open account.
+fn owned_payment(wallet: &Wallet, id: &str) -> Result<(), String> {
+    match wallet
+        .schedule
+        .payments_for(&wallet.nano_account)
+        .iter()
+        .any(|payment| payment.id == id)
+    {
+        true => Ok(()),
+        false => Err(format!("The account has no scheduled payment {id}.")),
+    }
+}
+
+/// Function that shows or changes the configuration of the wallet.
+fn config(command: ConfigCommand, mut wallet: Wallet) -> Result<Value, String> {
+    if let ConfigCommand::Account {
+        account_file,
+        url,
+        key,
+        websocket_url,
+        broadcast_node,
+        currency,
+        representative,
+        port,
+        reset,
+    } = command
+    {
+        return account_config(
+            &mut wallet,
+            &account_file,
+            AccountSettings {
+                url,
+                websocket_url,
+                broadcast_nodes: broadcast_node,
+                currency,
+                representative,
+                port,
+            },
+            key,
+            reset,
+        );
+    }
+
+    if let ConfigCommand::Set {
+        url,
+        key,
+        websocket_url,
+        broadcast_node,
+        network,
+        prefix,
+    } = command
+    {
+        if let Some(network) = network {
+            let network =
+                Network::from_name(&network).ok_or(format!("{network} is not a known network."))?;
+            wallet.settings.network.network = network;
+            wallet.config_file.url = network.default_rpc_url().to_string();
+            wallet.settings.websocket_url = network.default_websocket_url().to_string();
+        }
+        if let Some(prefix) = prefix {
+            wallet.settings.network.prefix = prefix.trim_end_matches('_').to_string();
+        }
+        if let Some(url) = url {
+            wallet.config_file.url = url;
+        }
+        if let Some(key) = key {
+            wallet.config_file.key = key;
+        }
+        if let Some(websocket_url) = websocket_url {
+            wallet.settings.websocket_url = websocket_url;
+        }
+        if let Some(broadcast_nodes) = broadcast_node {
+            wallet.settings.broadcast_nodes = broadcast_nodes;
+        }
+
+        wallet.save()?;
+    }
+
+    Ok(json!({
+        "config_file": wallet.config_file_path,
+        "settings_file": wallet.settings_path,
+        "url": wallet.config_file.url,
+        "key_set": !wallet.config_file.key.is_empty(),
+        "websocket_url": wallet.settings.websocket_url,
+        "broadcast_nodes": wallet.settings.broadcast_nodes,
+        "network": wallet.settings.network.network.name(),
+        "prefix": wallet.settings.network.prefix(),
+        "version": wallet.settings.version,
+        "errors": wallet.config_errors,
+    }))
+}
+
+/// Function that applies the given settings of an account over its current ones (an empty value removes the setting)
+/// and shows the values the account ends up using.
+fn account_config(
+    wallet: &mut Wallet,
+    account_file: &str,
+    changes: AccountSettings,
+    key: Option<String>,
+    reset: bool,
+) -> Result<Value, String> {
+    wallet.open_account(account_file)?;
+    let mut overrides = match reset {
+        true => AccountSettings::default(),
+        false => wallet.account_settings(),
+    };
+    let given = |value: Option<String>, current: &mut Option<String>| {
+        if let Some(value) = value {
+            *current = Some(value).filter(|value| !value.is_empty());
+        }
+    };
+    given(changes.url, &mut overrides.url);
+    given(changes.websocket_url, &mut overrides.websocket_url);
+    given(changes.currency, &mut overrides.currency);
+    given(changes.representative, &mut overrides.representative);
+    if let Some(broadcast_nodes) = changes.broadcast_nodes {
+        overrides.broadcast_nodes = Some(broadcast_nodes).filter(|nodes| !nodes.is_empty());
+    }
+    if let Some(port) = changes.port {
+        overrides.port = Some(port).filter(|port| *port != 0);
+    }
+    let key = match (reset, key) {
+        (_, Some(key)) => key,
+        (true, None) => String::new(),
+        (false, None) => wallet.account_key.clone(),
+    };
+
+    if reset || overrides != wallet.account_settings() || key != wallet.account_key {
+        wallet.save_account_settings(overrides.clone(), &key)?;
+    }
+
+    let node = wallet.node();
+    Ok(json!({
+        "account": wallet.nano_account,
+        "overrides": overrides,
+        "url": node.url,
+        "key_set": !node.key.is_empty(),
+        "websocket_url": wallet.websocket_url(),
+        "broadcast_nodes": wallet.broadcast_nodes(),
+        "currency": wallet.price_settings().currency,
+        "representative": wallet.default_representative(),
+        "port": wallet.port(),
+    }))
+}
+
+/// Function that keeps the accounts open in the daemon until it's interrupted.
+/// The link of the API and the file with its token are printed once the daemon is listening.
+async fn daemon(port: u16, account_files: Vec<String>, wallet: Wallet) -> Result<Value, String> {
+    let mut wallets = Vec::new();
+    for account_file in &account_files {
+        let mut account = wallet.clone();
+        account.open_account(account_file)?;
+        wallets.push(account);
+    }
+
+    let token = daemon_token(&wallet)?;
+    let daemon = Daemon::start(port, &token, wallets.clone()).await?;
+    println!(
+        "{}",
+        json!({
+            "url": daemon.url(),
+            "token_file": Path::new(&wallet.config_file_path).with_file_name(DAEMON_TOKEN_FILE),
+            "accounts": wallets.iter().map(|w| w.nano_account.clone()).collect::<Vec<_>>(),
+        })
+    );
+
+    tokio::select! {
+        _ = daemon.run() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    Ok(json!({ "stopped": true }))
+}
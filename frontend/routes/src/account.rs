@@ -0,0 +1,121 @@
+//! This file contains the conversions between Nano accounts and public keys.
+
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+
+/// Define the alphabet used by Nano to encode accounts.
+const ALPHABET: &[u8; 32] = b"13456789abcdefghijkmnopqrstuwxyz";
+
+/// Define the prefixes accepted for Nano accounts.
+const PREFIXES: [&str; 2] = ["nano_", "xrb_"];
+
+/// Function that computes the 5 byte checksum of a public key (blake2b, reversed).
+fn checksum(public_key: &[u8; 32]) -> [u8; 5] {
+    let mut hasher = Blake2bVar::new(5).expect("5 is a valid blake2b output size");
+    hasher.update(public_key);
+    let mut checksum = [0u8; 5];
+    hasher
+        .finalize_variable(&mut checksum)
+        .expect("the buffer has the requested size");
+    checksum.reverse();
+    checksum
+}
+
+/// Function that encodes bytes with Nano's base32 alphabet, starting from the most significant bits.
+fn encode(bytes: &[u8], bits: usize) -> String {
+    let mut encoded = String::with_capacity(bits / 5);
+    for i in (0..bits / 5).rev() {
+        let mut value = 0usize;
+        for bit in 0..5 {
+            let position = i * 5 + 4 - bit;
+            let byte = bytes[bytes.len() - 1 - position / 8];
+            value = (value << 1) | ((byte >> (position % 8)) & 1) as usize;
+        }
+        encoded.push(ALPHABET[value] as char);
+    }
+    encoded
+}
+
+/// Function that decodes Nano's base32 alphabet into `length` bytes.
+fn decode(encoded: &str, length: usize) -> Option<Vec<u8>> {
+    let mut bytes = vec![0u8; length];
+    let bits = encoded.len() * 5;
+    for (i, c) in encoded.bytes().enumerate() {
+        let value = ALPHABET.iter().position(|a| *a == c)?;
+        for bit in 0..5 {
+            let position = bits - 1 - (i * 5 + bit);
+            if (value >> (4 - bit)) & 1 == 1 {
+                if position / 8 >= length {
+                    return None;
+                }
+                bytes[length - 1 - position / 8] |= 1 << (position % 8);
+            }
+        }
+    }
+    Some(bytes)
+}
+
+/// Function that converts a public key to a Nano account with the given prefix (e.g. "nano_").
+pub fn public_key_to_account_with_prefix(public_key: &[u8; 32], prefix: &str) -> String {
+    let mut padded = [0u8; 33];
+    padded[1..].copy_from_slice(public_key);
+
+    format!(
+        "{prefix}{}{}",
+        // 4 bits of padding make the 256 bit key fit in 52 characters
+        &encode(&padded, 260),
+        encode(&checksum(public_key), 40)
+    )
+}
+
+/// Function that converts a public key to a Nano account.
+pub fn public_key_to_account(public_key: &[u8; 32]) -> String {
+    public_key_to_account_with_prefix(public_key, PREFIXES[0])
+}
+
+/// Function that converts a Nano account to its public key, validating the checksum.
+/// Besides "nano_" and "xrb_", any lowercase prefix is accepted since test and dev networks may use their own.
+pub fn account_to_public_key(account: &str) -> Option<[u8; 32]> {
+    let encoded = match PREFIXES
+        .iter()
+        .find_map(|prefix| account.strip_prefix(prefix))
+    {
+        Some(encoded) => encoded,
+        None => {
+            let (prefix, encoded) = account.split_once('_')?;
+            if prefix.is_empty() || !prefix.bytes().all(|b| b.is_ascii_lowercase()) {
+                return None;
+            }
+            encoded
+        }
+    };
+    account_body_to_public_key(encoded)
+}
+
+/// Function that returns the prefix of an account without the underscore (e.g. "nano").
+pub fn account_prefix(account: &str) -> Option<&str> {
+    account.split_once('_').map(|(prefix, _)| prefix)
+}
+
+/// Function that converts the part of an account after the prefix to its public key, validating the checksum.
+pub fn account_body_to_public_key(encoded: &str) -> Option<[u8; 32]> {
+    if encoded.len() != 60 || !encoded.starts_with(['1', '3']) {
+        return None;
+    }
+
+    let key = decode(&encoded[..52], 33)?;
+    let public_key: [u8; 32] = key[1..].try_into().ok()?;
+    let expected = decode(&encoded[52..], 5)?;
+
+    match checksum(&public_key).as_slice() == expected.as_slice() {
+        true => Some(public_key),
+        false => None,
+    }
+}
+
+/// Function that checks if a Nano account is valid.
+pub fn is_valid_account(account: &str) -> bool {
+    account_to_public_key(account).is_some()
+}
@@ -0,0 +1,246 @@
+//! This file contains the append-only audit log of the group sessions.
+//! Each record stores the hash of the previous one, so editing or removing a record breaks the chain.
+
+use crate::{current_time, RpcError};
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+/// Enum that represents the events recorded in the audit log.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A key generation session was started or joined.
+    KeygenStarted { role: String, coordinator: String },
+
+    /// A key generation session created the account.
+    KeygenFinished {
+        participants: u32,
+        threshold: u32,
+        public_share: String,
+    },
+
+    /// A key generation session failed.
+    KeygenFailed { error: String },
+
+    /// A block was proposed for signing and this participant agreed to sign it.
+    SessionProposed {
+        role: String,
+        coordinator: String,
+        subtype: String,
+        block_hash: String,
+        previous: String,
+        representative: String,
+        balance: String,
+        link: String,
+        participants: u32,
+        threshold: u32,
+        public_share: String,
+    },
+
+    /// This participant refused to sign a block (e.g. it broke the spending policy).
+    SessionRejected { reason: String },
+
+    /// A signing session finished.
+    SessionSucceeded { block_hash: String, published: bool },
+
+    /// A signing session failed.
+    SessionFailed { block_hash: String, error: String },
+}
+
+/// Struct that represents a record of the audit log.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub sequence: u64,
+
+    /// Time of the event (seconds since the unix epoch).
+    pub time: u64,
+
+    pub account: String,
+    pub event: AuditEvent,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+/// Struct that represents the records read from an audit log and the lines that couldn't be read.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuditContents {
+    pub records: Vec<AuditRecord>,
+    pub damaged_lines: Vec<usize>,
+}
+
+/// Struct that represents an audit log stored as a file with one JSON record per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditEvent {
+    /// Function that returns the name of the event shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditEvent::KeygenStarted { .. } => "Key generation started",
+            AuditEvent::KeygenFinished { .. } => "Key generation finished",
+            AuditEvent::KeygenFailed { .. } => "Key generation failed",
+            AuditEvent::SessionProposed { .. } => "Signing proposed",
+            AuditEvent::SessionRejected { .. } => "Signing refused",
+            AuditEvent::SessionSucceeded { .. } => "Signing succeeded",
+            AuditEvent::SessionFailed { .. } => "Signing failed",
+        }
+    }
+}
+
+/// Function that computes the hash of a record from its contents and the previous hash.
+fn record_hash(
+    sequence: u64,
+    time: u64,
+    account: &str,
+    event: &AuditEvent,
+    previous_hash: &str,
+) -> Result<String, RpcError> {
+    let contents = serde_json::to_string(&(sequence, time, account, event))?;
+
+    let mut hasher = Blake2bVar::new(32).expect("32 is a valid blake2b output size");
+    hasher.update(previous_hash.as_bytes());
+    hasher.update(contents.as_bytes());
+    let mut hash = [0u8; 32];
+    hasher
+        .finalize_variable(&mut hash)
+        .expect("the buffer has the requested size");
+    Ok(hex::encode_upper(hash))
+}
+
+/// Function that reads the records of a log, leaving out (and listing) the lines that can't be read.
+fn parse_contents(contents: &str) -> AuditContents {
+    let mut parsed = AuditContents::default();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(record) => parsed.records.push(record),
+            Err(_) => parsed.damaged_lines.push(i + 1),
+        }
+    }
+    parsed
+}
+
+impl AuditContents {
+    /// Function that describes the lines that couldn't be read (`None` if every line was read).
+    pub fn damage(&self) -> Option<String> {
+        match self.damaged_lines.is_empty() {
+            true => None,
+            false => Some(format!(
+                "The audit log has unreadable records on lines {}.",
+                self.damaged_lines
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+impl AuditLog {
+    /// Function that opens the audit log stored in a file (it's created with the first record).
+    pub fn open(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+        }
+    }
+
+    /// Function that reads the records of the log, oldest first, with the lines that couldn't be read.
+    pub fn records(&self) -> Result<AuditContents, RpcError> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(parse_contents(&contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(AuditContents::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Function that appends an event to the log, chaining it to the last record that can be read.
+    /// The file is locked while the record is written, since the desktop application and the daemon share it.
+    pub fn append(&self, account: &str, event: AuditEvent) -> Result<AuditRecord, RpcError> {
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&self.path)?;
+        file.lock()?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // a last line without its newline is a record whose write was interrupted, so it's dropped
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            let complete = contents.rfind('\n').map(|i| i + 1).unwrap_or_default();
+            file.set_len(complete as u64)?;
+            contents.truncate(complete);
+        }
+        file.seek(SeekFrom::End(0))?;
+
+        let last = parse_contents(&contents).records.pop();
+        let sequence = last.as_ref().map(|r| r.sequence + 1).unwrap_or_default();
+        let previous_hash = last.map(|r| r.hash).unwrap_or("0".repeat(64));
+        let time = current_time();
+
+        let record = AuditRecord {
+            sequence,
+            time,
+            account: account.to_string(),
+            hash: record_hash(sequence, time, account, &event, &previous_hash)?,
+            event,
+            previous_hash,
+        };
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+
+        Ok(record)
+    }
+
+    /// Function that checks that no record was edited, removed, reordered or damaged, returning how many there are.
+    pub fn verify(&self) -> Result<usize, RpcError> {
+        let contents = self.records()?;
+        if let Some(damage) = contents.damage() {
+            return Err(damage.into());
+        }
+        let mut previous_hash = "0".repeat(64);
+
+        for (i, record) in contents.records.iter().enumerate() {
+            let hash = record_hash(
+                record.sequence,
+                record.time,
+                &record.account,
+                &record.event,
+                &record.previous_hash,
+            )?;
+            if record.sequence != i as u64
+                || record.previous_hash != previous_hash
+                || record.hash != hash
+            {
+                return Err(format!("The audit log was tampered with at record {i}.").into());
+            }
+            previous_hash = hash;
+        }
+
+        Ok(contents.records.len())
+    }
+
+    /// Function that exports the records that can be read as a JSON array to the given file,
+    /// returning the lines that couldn't be read and were left out.
+    pub fn export(&self, path: &str) -> Result<Vec<usize>, RpcError> {
+        let contents = self.records()?;
+        fs::write(path, serde_json::to_string_pretty(&contents.records)?)?;
+        Ok(contents.damaged_lines)
+    }
+}
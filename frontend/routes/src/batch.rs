@@ -0,0 +1,204 @@
+//! This file contains the batch payouts: a CSV of recipients (`address,amount` per line, the amount in Nano) the group
+//! pays with a chain of send blocks. The coordinator proposes the chain built from its file and every participant
+//! checks it against its own copy of the file before signing.
+
+use crate::{is_valid_account, nano_to_raw, raw_to_nano};
+use serde::{Deserialize, Serialize};
+
+/// Constant value for the maximum number of payouts in a batch.
+pub const MAX_PAYOUTS: usize = 100;
+
+/// Struct that represents a row of a batch payout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    /// Line of the file the row was read from.
+    pub line: usize,
+
+    pub receivers_account: String,
+
+    /// Amount of Nano sent, written in decimal.
+    pub amount: String,
+}
+
+/// Struct that represents the payouts the group signs as a chain of send blocks, in the order they're sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PayoutBatch {
+    pub payouts: Vec<Payout>,
+}
+
+/// Enum that represents the result of a row of a batch payout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutStatus {
+    /// The row waits for its turn.
+    Pending,
+
+    /// The group is signing the send block of the row.
+    Signing,
+
+    /// The send block with the given hash was signed and handed to the node.
+    Sent { hash: String },
+
+    /// Signing the row failed.
+    Failed(String),
+
+    /// The row wasn't sent because a previous one failed.
+    NotSent,
+}
+
+impl PayoutStatus {
+    /// Function that returns the name of the status shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PayoutStatus::Pending => "Pending",
+            PayoutStatus::Signing => "Signing",
+            PayoutStatus::Sent { .. } => "Sent",
+            PayoutStatus::Failed(_) => "Failed",
+            PayoutStatus::NotSent => "Not sent",
+        }
+    }
+
+    /// Function that returns why the row failed (`None` if it didn't).
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            PayoutStatus::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Payout {
+    /// Function that returns the amount of the payout in raw (`None` if it isn't a valid amount).
+    pub fn raw(&self) -> Option<u128> {
+        nano_to_raw(&self.amount).filter(|&raw| raw > 0)
+    }
+
+    /// Function that checks the payout, returning the problems found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if !is_valid_account(&self.receivers_account) {
+            errors.push(format!(
+                "\"{}\" isn't a valid Nano account.",
+                self.receivers_account
+            ));
+        }
+        if self.raw().is_none() {
+            errors.push(format!("\"{}\" is not a valid amount.", self.amount));
+        }
+        errors
+    }
+}
+
+impl PayoutBatch {
+    /// Function that reads the payouts from a CSV file with an `address,amount` row per payout.
+    /// A header on the first line, empty lines and lines starting with `#` are skipped, and fields may be quoted.
+    /// Any other row is a payout, so the rows that aren't valid are reported by `validate`.
+    pub fn parse(csv: &str) -> Result<Self, String> {
+        let mut payouts = Vec::new();
+        for (index, line) in csv.trim_start_matches('\u{feff}').lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let fields = csv_fields(line)
+                .ok_or(format!("Line {line_number} has a quote that isn't closed."))?;
+            let [receivers_account, amount] = fields.as_slice() else {
+                return Err(format!(
+                    "Line {line_number} must have an address and an amount."
+                ));
+            };
+
+            // the first line is a header when it holds neither an account nor an amount
+            if line_number == 1
+                && !is_valid_account(receivers_account)
+                && nano_to_raw(amount).is_none()
+            {
+                continue;
+            }
+            payouts.push(Payout {
+                line: line_number,
+                receivers_account: receivers_account.clone(),
+                amount: amount.clone(),
+            });
+        }
+
+        match payouts.len() {
+            0 => Err("The file has no payouts.".to_string()),
+            count if count > MAX_PAYOUTS => Err(format!(
+                "The file has {count} payouts but a batch can have at most {MAX_PAYOUTS}."
+            )),
+            _ => Ok(Self { payouts }),
+        }
+    }
+
+    /// Function that returns whether both batches pay the same amounts to the same accounts in the same order.
+    pub fn pays_the_same(&self, other: &[Payout]) -> bool {
+        self.payouts.len() == other.len()
+            && self.payouts.iter().zip(other).all(|(payout, other)| {
+                payout.receivers_account == other.receivers_account && payout.raw() == other.raw()
+            })
+    }
+
+    /// Function that returns the sum of the payouts in raw (`None` if an amount is invalid or the sum overflows).
+    pub fn total(&self) -> Option<u128> {
+        self.payouts
+            .iter()
+            .try_fold(0u128, |total, payout| total.checked_add(payout.raw()?))
+    }
+
+    /// Function that returns the balance left after the payouts (`None` if the balance isn't enough).
+    pub fn balance_after(&self, balance: u128) -> Option<u128> {
+        balance.checked_sub(self.total()?)
+    }
+
+    /// Function that checks every payout and that the balance pays them all, returning the problems found.
+    pub fn validate(&self, balance: u128) -> Vec<String> {
+        let mut errors: Vec<String> = self
+            .payouts
+            .iter()
+            .flat_map(|payout| {
+                payout
+                    .validate()
+                    .into_iter()
+                    .map(|e| format!("Line {}: {e}", payout.line))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            match self.total() {
+                Some(total) if total > balance => errors.push(format!(
+                    "The payouts add up to {} Nano but the balance is {} Nano.",
+                    raw_to_nano(total),
+                    raw_to_nano(balance)
+                )),
+                Some(_) => {}
+                None => errors.push("The payouts add up to more Nano than exists.".to_string()),
+            }
+        }
+        errors
+    }
+}
+
+/// Function that splits a line of a CSV file into its trimmed fields (`None` if a quote isn't closed).
+fn csv_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            (c, _) => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field.trim().to_string());
+    Some(fields)
+}
@@ -0,0 +1,279 @@
+//! This file contains the local representation of Nano state blocks: construction, hashing and verification.
+//! Blocks are built from the account's state instead of trusting the ones assembled from node responses.
+
+use crate::{account_to_public_key, RpcError};
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+use ed25519_dalek_blake2b::{PublicKey, Signature, Verifier};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// Define the number of raw in one Nano.
+pub const RAW_PER_NANO: u128 = 10u128.pow(30);
+
+/// Define the preamble that is hashed before the fields of a state block.
+const STATE_BLOCK_PREAMBLE: [u8; 32] = {
+    let mut preamble = [0u8; 32];
+    preamble[31] = 6;
+    preamble
+};
+
+/// Struct that represents a Nano state block.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct StateBlock {
+    #[serde(rename = "type", default = "state_type")]
+    pub block_type: String,
+    pub account: String,
+    pub previous: String,
+    pub representative: String,
+    #[serde(serialize_with = "raw_to_string", deserialize_with = "raw_from_string")]
+    pub balance: u128,
+    pub link: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work: Option<String>,
+}
+
+/// Struct that represents what the wallet knows about an account before building its next block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountState {
+    /// Account the block belongs to.
+    pub account: String,
+
+    /// Hash of the last block of the account (`None` if it wasn't opened yet).
+    pub frontier: Option<String>,
+
+    /// Balance of the account in raw.
+    pub balance: u128,
+
+    /// Current representative of the account.
+    pub representative: String,
+}
+
+fn state_type() -> String {
+    "state".to_string()
+}
+
+fn raw_to_string<S: Serializer>(raw: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&raw.to_string())
+}
+
+fn raw_from_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    raw.parse().map_err(serde::de::Error::custom)
+}
+
+/// Function that converts an amount of Nano written in decimal (e.g. "1.5") to raw without losing precision.
+pub fn nano_to_raw(amount: &str) -> Option<u128> {
+    let amount = amount.trim();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > 30
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole: u128 = match whole {
+        "" => 0,
+        whole => whole.parse().ok()?,
+    };
+    let fraction: u128 = match fraction {
+        "" => 0,
+        fraction => fraction.parse::<u128>().ok()? * 10u128.pow(30 - fraction.len() as u32),
+    };
+    whole.checked_mul(RAW_PER_NANO)?.checked_add(fraction)
+}
+
+/// Function that converts raw to Nano written in decimal without trailing zeros.
+pub fn raw_to_nano(raw: u128) -> String {
+    let whole = raw / RAW_PER_NANO;
+    let fraction = raw % RAW_PER_NANO;
+    match fraction {
+        0 => whole.to_string(),
+        fraction => format!(
+            "{whole}.{}",
+            format!("{fraction:030}").trim_end_matches('0')
+        ),
+    }
+}
+
+/// Function that parses a 32 byte hex value.
+fn parse_hash(hash: &str) -> Option<[u8; 32]> {
+    hex::decode(hash).ok()?.try_into().ok()
+}
+
+/// Function that parses the link of a block, which is either a hash or an account.
+fn parse_link(link: &str) -> Option<[u8; 32]> {
+    parse_hash(link).or_else(|| account_to_public_key(link))
+}
+
+impl StateBlock {
+    /// Function that creates an unsigned block.
+    fn unsigned(
+        account: &str,
+        previous: &str,
+        representative: &str,
+        balance: u128,
+        link: &str,
+    ) -> Self {
+        Self {
+            block_type: state_type(),
+            account: account.to_string(),
+            previous: previous.to_uppercase(),
+            representative: representative.to_string(),
+            balance,
+            link: link.to_uppercase(),
+            signature: None,
+            work: None,
+        }
+    }
+
+    /// Function that builds the block that sends `amount` raw to `destination`.
+    pub fn send(state: &AccountState, destination: &str, amount: u128) -> Result<Self, RpcError> {
+        let previous = state
+            .frontier
+            .as_ref()
+            .ok_or("Open your account before sending.")?;
+        let destination =
+            account_to_public_key(destination).ok_or("The destination account is invalid.")?;
+        let balance = state
+            .balance
+            .checked_sub(amount)
+            .ok_or("The balance is not enough for this transaction.")?;
+
+        Ok(Self::unsigned(
+            &state.account,
+            previous,
+            &state.representative,
+            balance,
+            &hex::encode_upper(destination),
+        ))
+    }
+
+    /// Function that builds the block that receives `amount` raw sent by the block `source`.
+    /// If the account wasn't opened yet this is its open block.
+    pub fn receive(state: &AccountState, source: &str, amount: u128) -> Result<Self, RpcError> {
+        parse_hash(source).ok_or("The receivable block's hash is invalid.")?;
+        let balance = state
+            .balance
+            .checked_add(amount)
+            .ok_or("The balance would overflow.")?;
+        let previous = state.frontier.clone().unwrap_or("0".repeat(64));
+
+        Ok(Self::unsigned(
+            &state.account,
+            &previous,
+            &state.representative,
+            balance,
+            source,
+        ))
+    }
+
+    /// Function that builds the block that changes the representative of the account.
+    pub fn change(state: &AccountState, representative: &str) -> Result<Self, RpcError> {
+        let previous = state
+            .frontier
+            .as_ref()
+            .ok_or("Open your account before changing its representative.")?;
+        account_to_public_key(representative).ok_or("The representative's account is invalid.")?;
+
+        Ok(Self::unsigned(
+            &state.account,
+            previous,
+            representative,
+            state.balance,
+            &"0".repeat(64),
+        ))
+    }
+
+    /// Function that returns the subtype of the block given the account's state before it.
+    pub fn subtype(&self, previous: &AccountState) -> &'static str {
+        match previous.frontier {
+            None => "open",
+            Some(_) if self.balance < previous.balance => "send",
+            Some(_) if self.balance > previous.balance => "receive",
+            Some(_) => "change",
+        }
+    }
+
+    /// Function that reads a block from its JSON (either an object or a string with the object).
+    pub fn from_json(block: &Value) -> Result<Self, RpcError> {
+        Ok(match block.as_str() {
+            Some(block) => serde_json::from_str(block)?,
+            None => serde_json::from_value(block.clone())?,
+        })
+    }
+
+    /// Function that computes the hash of the block (blake2b over the preamble and the fields).
+    pub fn hash(&self) -> Result<[u8; 32], RpcError> {
+        let account = account_to_public_key(&self.account).ok_or("The account is invalid.")?;
+        let previous = parse_hash(&self.previous).ok_or("The previous hash is invalid.")?;
+        let representative = account_to_public_key(&self.representative)
+            .ok_or("The representative's account is invalid.")?;
+        let link = parse_link(&self.link).ok_or("The link is invalid.")?;
+
+        let mut hasher = Blake2bVar::new(32).expect("32 is a valid blake2b output size");
+        hasher.update(&STATE_BLOCK_PREAMBLE);
+        hasher.update(&account);
+        hasher.update(&previous);
+        hasher.update(&representative);
+        hasher.update(&self.balance.to_be_bytes());
+        hasher.update(&link);
+
+        let mut hash = [0u8; 32];
+        hasher
+            .finalize_variable(&mut hash)
+            .expect("the buffer has the requested size");
+        Ok(hash)
+    }
+
+    /// Function that returns the hash of the block as hex.
+    pub fn hash_hex(&self) -> Result<String, RpcError> {
+        Ok(hex::encode_upper(self.hash()?))
+    }
+
+    /// Function that checks if the block is signed by the given public key (the aggregated key of the account).
+    pub fn verify_signature(&self, public_key: &[u8; 32]) -> Result<(), RpcError> {
+        let signature = self.signature.as_ref().ok_or("The block is not signed.")?;
+        let signature = hex::decode(signature).map_err(|_| "The signature is not valid hex.")?;
+        let signature =
+            Signature::try_from(signature.as_slice()).map_err(|_| "The signature is malformed.")?;
+        let public_key =
+            PublicKey::from_bytes(public_key).map_err(|_| "The public key is malformed.")?;
+
+        public_key
+            .verify(&self.hash()?, &signature)
+            .map_err(|_| "The signature doesn't match the account's aggregated key.".into())
+    }
+
+    /// Function that checks if a block that is about to be published is the expected one, signed by the account.
+    /// Comparing the hashes covers `previous`, the balance, the representative and the link at once.
+    pub fn verify_against(&self, expected: &StateBlock) -> Result<(), RpcError> {
+        if self.account != expected.account {
+            return Err("The block belongs to another account.".into());
+        }
+        if !self.previous.eq_ignore_ascii_case(&expected.previous) {
+            return Err("The block's previous doesn't match the known frontier.".into());
+        }
+        if self.balance != expected.balance {
+            return Err(format!(
+                "The block's balance ({}) doesn't match the expected balance ({}).",
+                self.balance, expected.balance
+            )
+            .into());
+        }
+        if self.hash()? != expected.hash()? {
+            return Err("The block doesn't match the one that was reviewed.".into());
+        }
+
+        let public_key = account_to_public_key(&self.account).ok_or("The account is invalid.")?;
+        self.verify_signature(&public_key)
+    }
+}
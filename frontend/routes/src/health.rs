@@ -0,0 +1,97 @@
+//! This file contains the health check run against a node before the wallet starts using it.
+
+use crate::{get_accounts_frontiers, get_block_count, get_node_version, RpcError};
+use std::time::{Duration, Instant};
+
+/// Define the network of the nodes the wallet uses by default.
+pub const LIVE_NETWORK: &str = "live";
+
+/// Define the account used for the authenticated call of the check (the genesis account always exists).
+const GENESIS_ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+/// Define the latency above which the node is reported as slow.
+const SLOW_LATENCY: Duration = Duration::from_secs(2);
+
+/// Struct that represents what was learned about a node while checking it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeHealth {
+    /// Vendor and version of the node (e.g. "Nano V27.1").
+    pub vendor: String,
+
+    /// Network the node belongs to ("live", "beta" or "test").
+    pub network: String,
+
+    /// Number of blocks in the node's ledger.
+    pub block_count: String,
+
+    /// Slowest answer of the node during the check.
+    pub latency: Duration,
+
+    /// Problems that don't prevent the node from being used.
+    pub warnings: Vec<String>,
+}
+
+/// Function that checks a node before it's used: it must answer `version` and `block_count`, accept the key in an
+/// authenticated call and belong to the expected network.
+pub async fn check_node(
+    url: &str,
+    key: &str,
+    expected_network: &str,
+) -> Result<NodeHealth, RpcError> {
+    let (version, version_latency) = timed(get_node_version(url, key))
+        .await
+        .map_err(|e| format!("The node didn't answer: {e}"))?;
+    let network = version.network.unwrap_or_default();
+    if !network.eq_ignore_ascii_case(expected_network) {
+        return Err(format!(
+            "The node belongs to the {} network instead of the {expected_network} network.",
+            match network.is_empty() {
+                true => "unknown",
+                false => network.as_str(),
+            }
+        )
+        .into());
+    }
+
+    let (block_count, count_latency) = timed(get_block_count(url, key))
+        .await
+        .map_err(|e| format!("The node couldn't count its blocks: {e}"))?;
+    let (_, authenticated_latency) = timed(get_accounts_frontiers(
+        url,
+        key,
+        &[GENESIS_ACCOUNT.to_string()],
+    ))
+    .await
+    .map_err(|e| format!("The node refused the request with this key: {e}"))?;
+
+    let latency = version_latency
+        .max(count_latency)
+        .max(authenticated_latency);
+    let mut warnings = Vec::new();
+    if latency > SLOW_LATENCY {
+        warnings.push(format!(
+            "The node is slow to answer ({} ms).",
+            latency.as_millis()
+        ));
+    }
+    if block_count.count.parse::<u64>().unwrap_or(0) == 0 {
+        warnings.push("The node's ledger is empty.".to_string());
+    }
+
+    Ok(NodeHealth {
+        vendor: version.node_vendor.unwrap_or_default(),
+        network,
+        block_count: block_count.count,
+        latency,
+        warnings,
+    })
+}
+
+/// Function that waits for a request and returns its result with the time it took.
+async fn timed<T>(
+    request: impl std::future::Future<Output = Result<T, RpcError>>,
+) -> Result<(T, Duration), RpcError> {
+    let start = Instant::now();
+    let response = request.await?;
+    Ok((response, start.elapsed()))
+}
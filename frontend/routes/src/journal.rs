@@ -0,0 +1,119 @@
+//! This file contains the journal of the blocks this participant agreed to sign.
+//! Signing two different blocks with the same previous creates a fork, so each frontier is locked on the first block.
+
+use crate::{current_time, RpcError, SharedList, StateBlock};
+use serde::{Deserialize, Serialize};
+
+/// Define the error shown when a frontier is already locked on another block.
+pub const FRONTIER_LOCKED_ERROR: &str =
+    "Another transaction for this frontier is already in progress or signed.";
+
+/// Enum that represents the state of a block in the journal.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalStatus {
+    /// A signing session for the block is running.
+    InProgress,
+
+    /// The block was signed.
+    Signed,
+}
+
+/// Struct that represents a block in the journal.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub hash: String,
+    pub block: StateBlock,
+    pub status: JournalStatus,
+
+    /// Amount of raw the block sends (0 if it doesn't send).
+    #[serde(default)]
+    pub sent: u128,
+
+    /// Time the frontier was locked (seconds since the unix epoch).
+    pub created_at: u64,
+}
+
+/// Struct that represents the journal of signed blocks, stored in a file shared by every process of the wallet.
+#[derive(Debug, Clone, Default)]
+pub struct SigningJournal {
+    entries: SharedList<JournalEntry>,
+}
+
+impl SigningJournal {
+    /// Function that opens the journal stored in a file (it's created when the first block is locked).
+    /// A journal that can't be read is an error, since treating it as empty would unlock every frontier.
+    pub fn open(path: &str) -> Result<Self, String> {
+        Ok(Self {
+            entries: SharedList::open("journal", path)?,
+        })
+    }
+
+    /// Function that returns the journal stored in a file without reading it, so every change fails until it can be
+    /// read.
+    pub fn at(path: &str) -> Self {
+        Self {
+            entries: SharedList::at("journal", path),
+        }
+    }
+
+    /// Function that returns every block in the journal.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.entries()
+    }
+
+    /// Function that locks the block's frontier before signing it, returning the block's hash.
+    /// Signing the same block again is allowed, but any other block with the same previous is refused.
+    pub fn lock(&self, block: &StateBlock, sent: u128) -> Result<String, RpcError> {
+        let hash = block.hash_hex()?;
+        self.entries.update(|entries| {
+            let same_frontier = entries.iter().find(|entry| {
+                entry.block.account == block.account
+                    && entry.block.previous.eq_ignore_ascii_case(&block.previous)
+            });
+            match same_frontier {
+                Some(entry) if entry.hash == hash => Ok(()),
+                Some(_) => Err(FRONTIER_LOCKED_ERROR.to_string()),
+                None => {
+                    entries.push(JournalEntry {
+                        hash: hash.clone(),
+                        block: block.clone(),
+                        status: JournalStatus::InProgress,
+                        sent,
+                        created_at: current_time(),
+                    });
+                    Ok(())
+                }
+            }
+        })?;
+        Ok(hash)
+    }
+
+    /// Function that returns the amount an account sent in the blocks locked since `since` (unix seconds).
+    pub fn sent_since(&self, account: &str, since: u64) -> Result<u128, String> {
+        Ok(self
+            .entries
+            .read()?
+            .iter()
+            .filter(|entry| entry.block.account == account && entry.created_at >= since)
+            .map(|entry| entry.sent)
+            .sum())
+    }
+
+    /// Function that marks a block as signed, keeping its frontier locked for good.
+    pub fn mark_signed(&self, hash: &str) -> Result<(), String> {
+        self.entries.update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.hash == hash) {
+                entry.status = JournalStatus::Signed;
+            }
+            Ok(())
+        })
+    }
+
+    /// Function that unlocks the frontier of a block whose session failed before it was signed.
+    pub fn release(&self, hash: &str) -> Result<(), String> {
+        self.entries.update(|entries| {
+            entries.retain(|entry| entry.hash != hash || entry.status == JournalStatus::Signed);
+            Ok(())
+        })
+    }
+}
@@ -1,2 +1,56 @@
 mod nano;
 pub use nano::*;
+
+mod price;
+pub use price::*;
+
+mod websocket;
+pub use websocket::*;
+
+mod account;
+pub use account::*;
+
+mod work;
+pub use work::*;
+
+mod relay;
+pub use relay::*;
+
+mod block;
+pub use block::*;
+
+mod outbox;
+pub use outbox::*;
+
+mod storage;
+pub use storage::*;
+
+mod journal;
+pub use journal::*;
+
+mod policy;
+pub use policy::*;
+
+mod time;
+pub use time::*;
+
+mod audit;
+pub use audit::*;
+
+mod health;
+pub use health::*;
+
+mod network;
+pub use network::*;
+
+mod uri;
+pub use uri::*;
+
+mod qr;
+pub use qr::*;
+
+mod schedule;
+pub use schedule::*;
+
+mod batch;
+pub use batch::*;
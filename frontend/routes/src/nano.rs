@@ -1,71 +1,65 @@
 //! This file contains all the structures and api calls to the Nano RPC API
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use std::{collections::HashMap, error::Error};
+
+/// Define the error type returned by the RPC calls that don't panic.
+pub type RpcError = Box<dyn Error + Send + Sync>;
 
 /// Define the current Nano RPC API link as constant (The link can be changed since it follows Nano RPC guidelines).
 const RPC_URL: &str = "https://rpc.nano.to";
-const NANO_PRICE_URL: &str =
-    "https://api.coingecko.com/api/v3/simple/price?ids=nano&vs_currencies=eur";
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct NanoPriceEuro {
-    pub eur: Option<f32>,
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct NanoPriceResponse {
-    pub nano: Option<NanoPriceEuro>,
-}
+/// Define the error the node returns for an account that wasn't opened yet.
+const ACCOUNT_NOT_FOUND_ERROR: &str = "Account not found";
 
 /// Define the struct for the get_version API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct VersionResponse {
-    rpc_version: Option<String>,
-    store_version: Option<String>,
-    protocol_version: Option<String>,
-    node_vendor: Option<String>,
-    store_vendor: Option<String>,
-    network: Option<String>,
+    pub rpc_version: Option<String>,
+    pub store_version: Option<String>,
+    pub protocol_version: Option<String>,
+    pub node_vendor: Option<String>,
+    pub store_vendor: Option<String>,
+    pub network: Option<String>,
 }
 
 /// Define the struct for the get_account_info API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AccountInfoResponse {
-    frontier: Option<String>,
-    open_block: Option<String>,
-    representative_block: Option<String>,
-    balance: Option<String>,
-    balance_nano: Option<String>,
-    modified_timestamp: Option<String>,
-    block_count: Option<String>,
-    account_version: Option<String>,
-    confirmation_height: Option<String>,
-    confirmation_height_frontier: Option<String>,
+    pub frontier: Option<String>,
+    pub open_block: Option<String>,
+    pub representative_block: Option<String>,
+    pub balance: Option<String>,
+    pub balance_nano: Option<String>,
+    pub modified_timestamp: Option<String>,
+    pub block_count: Option<String>,
+    pub account_version: Option<String>,
+    pub confirmation_height: Option<String>,
+    pub confirmation_height_frontier: Option<String>,
 }
 
 /// Define the struct for each node of the history in the get_account_history API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AccountHistoryNode {
     #[serde(rename = "type")]
-    history_type: Option<String>,
-    account: Option<String>,
-    amount: Option<String>,
-    local_timestamp: Option<String>,
-    height: Option<String>,
-    hash: Option<String>,
-    confirmed: Option<String>,
+    pub history_type: Option<String>,
+    pub account: Option<String>,
+    pub amount: Option<String>,
+    pub local_timestamp: Option<String>,
+    pub height: Option<String>,
+    pub hash: Option<String>,
+    pub confirmed: Option<String>,
     #[serde(default)]
-    username: Option<String>,
+    pub username: Option<String>,
 }
 
 /// Define the struct for the get_account_history API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AccountHistoryResponse {
-    account: Option<String>,
-    history: Option<Vec<AccountHistoryNode>>,
+    pub account: Option<String>,
+    pub history: Option<Vec<AccountHistoryNode>>,
     #[serde(default)]
-    previous: Option<String>,
+    pub previous: Option<String>,
 }
 
 /// Define the struct for the get_account_balance API call response.
@@ -80,90 +74,286 @@ pub struct AccountBalanceResponse {
 }
 
 /// Define the struct for the account_create API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AccountCreateResponse {
-    account: Option<String>,
+    pub account: Option<String>,
 }
 
 /// Define the struct for the account_destroy API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AccountDestroyResponse {
-    remove: Option<String>,
+    pub removed: Option<String>,
 }
 
 /// Define the struct for the get_wallet_info API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WalletInfoResponse {
-    balance: Option<String>,
-    pending: Option<String>,
-    recievable: Option<String>,
-    accounts_count: Option<String>,
-    adhoc_count: Option<String>,
-    deterministic_count: Option<String>,
-    deterministic_index: Option<String>,
-    accounts_block_count: Option<String>,
-    accounts_cemented_block_count: Option<String>,
+    pub balance: Option<String>,
+    pub pending: Option<String>,
+    pub receivable: Option<String>,
+    pub accounts_count: Option<String>,
+    pub adhoc_count: Option<String>,
+    pub deterministic_count: Option<String>,
+    pub deterministic_index: Option<String>,
+    pub accounts_block_count: Option<String>,
+    pub accounts_cemented_block_count: Option<String>,
 }
 
 /// Define the struct for each node of the history in the get_account_history API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WalletHistoryNode {
     #[serde(rename = "type")]
-    history_type: Option<String>,
-    account: Option<String>,
-    amount: Option<String>,
-    local_timestamp: Option<String>,
-    hash: Option<String>,
+    pub history_type: Option<String>,
+    pub account: Option<String>,
+    pub amount: Option<String>,
+    pub local_timestamp: Option<String>,
+    pub hash: Option<String>,
 }
 
 /// Define the struct for the get_account_history API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WalletHistoryResponse {
-    history: Option<Vec<AccountHistoryNode>>,
+    pub history: Option<Vec<AccountHistoryNode>>,
 }
 
 /// Define the struct for the wallet_create API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WalletCreateResponse {
-    wallet: Option<String>,
+    pub wallet: Option<String>,
 }
 
 /// Define the struct for the wallet_destroy API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct WalletDestroyResponse {
-    destroy: Option<String>,
+    pub destroy: Option<String>,
 }
 
 /// Define the struct for the content of the block in the get_block_info API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BlockInfoContent {
     #[serde(rename = "type")]
-    content_type: Option<String>,
-    account: Option<String>,
-    previous: Option<String>,
-    representative: Option<String>,
-    balance: Option<String>,
-    balance_nano: Option<String>,
-    link: Option<String>,
-    link_as_account: Option<String>,
-    signature: Option<String>,
-    work: Option<String>,
+    pub content_type: Option<String>,
+    pub account: Option<String>,
+    pub previous: Option<String>,
+    pub representative: Option<String>,
+    pub balance: Option<String>,
+    pub balance_nano: Option<String>,
+    pub link: Option<String>,
+    pub link_as_account: Option<String>,
+    pub signature: Option<String>,
+    pub work: Option<String>,
 }
 
 /// Define the struct for the get_block_info API call response.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BlockInfoResponse {
-    block_account: Option<String>,
-    amount: Option<String>,
-    amount_nano: Option<String>,
-    balance: Option<String>,
-    balance_nano: Option<String>,
-    height: Option<String>,
-    local_timestamp: Option<String>,
-    successor: Option<String>,
-    confirmed: Option<String>,
-    content: Option<BlockInfoContent>,
-    subtype: Option<String>,
+    pub block_account: Option<String>,
+    pub amount: Option<String>,
+    pub amount_nano: Option<String>,
+    pub balance: Option<String>,
+    pub balance_nano: Option<String>,
+    pub height: Option<String>,
+    pub local_timestamp: Option<String>,
+    pub successor: Option<String>,
+    pub confirmed: Option<String>,
+    pub contents: Option<BlockInfoContent>,
+    pub subtype: Option<String>,
+}
+
+/// Define the struct for each block in the receivable API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ReceivableBlock {
+    pub amount: String,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Define the struct for the receivable API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ReceivableResponse {
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub blocks: HashMap<String, ReceivableBlock>,
+}
+
+/// Function that deserializes the empty string the node returns instead of an empty object.
+fn empty_string_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + Default,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match value {
+        serde_json::Value::String(s) if s.is_empty() => Ok(T::default()),
+        value => serde_json::from_value(value).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Define the struct for the work_generate API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WorkGenerateResponse {
+    pub work: String,
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    #[serde(default)]
+    pub multiplier: Option<String>,
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+/// Define the struct for each representative in the representatives_online API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnlineRepresentative {
+    #[serde(default)]
+    pub weight: String,
+}
+
+/// Define the struct for the representatives_online API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct RepresentativesOnlineResponse {
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub representatives: HashMap<String, OnlineRepresentative>,
+}
+
+/// Define the struct for each account in the accounts_balances API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct AccountBalanceEntry {
+    pub balance: String,
+    #[serde(default)]
+    pub pending: String,
+    #[serde(default)]
+    pub receivable: String,
+}
+
+/// Define the struct for the accounts_balances API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct AccountsBalancesResponse {
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub balances: HashMap<String, AccountBalanceEntry>,
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub errors: HashMap<String, String>,
+}
+
+/// Define the struct for the accounts_frontiers API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct AccountsFrontiersResponse {
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub frontiers: HashMap<String, String>,
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub errors: HashMap<String, String>,
+}
+
+/// Define the struct for the blocks_info API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct BlocksInfoResponse {
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub blocks: HashMap<String, BlockInfoResponse>,
+    #[serde(default, deserialize_with = "empty_string_as_default")]
+    pub blocks_not_found: Vec<String>,
+}
+
+/// Define the struct for the telemetry API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct TelemetryResponse {
+    #[serde(default)]
+    pub block_count: String,
+    #[serde(default)]
+    pub cemented_count: String,
+    #[serde(default)]
+    pub unchecked_count: String,
+    #[serde(default)]
+    pub account_count: String,
+    #[serde(default)]
+    pub bandwidth_cap: String,
+    #[serde(default)]
+    pub peer_count: String,
+    #[serde(default)]
+    pub protocol_version: String,
+    #[serde(default)]
+    pub uptime: String,
+    #[serde(default)]
+    pub genesis_block: String,
+    #[serde(default)]
+    pub major_version: String,
+    #[serde(default)]
+    pub minor_version: String,
+    #[serde(default)]
+    pub patch_version: String,
+    #[serde(default)]
+    pub pre_release_version: String,
+    #[serde(default)]
+    pub maker: String,
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub active_difficulty: String,
+}
+
+/// Define the struct for the block_count API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct BlockCountResponse {
+    pub count: String,
+    pub unchecked: String,
+    #[serde(default)]
+    pub cemented: String,
+}
+
+/// Define the struct for the confirmation_quorum API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct ConfirmationQuorumResponse {
+    pub quorum_delta: String,
+    pub online_weight_quorum_percent: String,
+    pub online_weight_minimum: String,
+    pub online_stake_total: String,
+    #[serde(default)]
+    pub peers_stake_total: String,
+    #[serde(default)]
+    pub trended_stake_total: String,
+}
+
+/// Define the struct for the account_representative API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct AccountRepresentativeResponse {
+    pub representative: String,
+}
+
+/// Define the struct for the validate_account_number API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct ValidateAccountNumberResponse {
+    pub valid: String,
+}
+
+/// Define the struct for the process API call response.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+pub struct ProcessResponse {
+    pub hash: String,
+}
+
+/// Implement functions for RepresentativesOnlineResponse.
+impl RepresentativesOnlineResponse {
+    /// Function that returns the online representatives ordered by voting weight (highest first).
+    pub fn by_weight(&self) -> Vec<(String, u128)> {
+        let mut representatives: Vec<_> = self
+            .representatives
+            .iter()
+            .map(|(account, rep)| (account.clone(), rep.weight.parse::<u128>().unwrap_or(0)))
+            .collect();
+        representatives.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        representatives
+    }
+}
+
+/// Implement functions for ReceivableResponse.
+impl ReceivableResponse {
+    /// Function that returns the receivable blocks sorted by hash, so every participant sees the same order.
+    pub fn sorted(&self) -> Vec<(String, ReceivableBlock)> {
+        let mut blocks: Vec<_> = self
+            .blocks
+            .iter()
+            .map(|(hash, block)| (hash.clone(), block.clone()))
+            .collect();
+        blocks.sort_by(|a, b| a.0.cmp(&b.0));
+        blocks
+    }
 }
 
 /// Implement functions for AccountBalanceResponse.
@@ -181,11 +371,36 @@ impl AccountBalanceResponse {
     }
 }
 
-pub async fn get_nano_price_euro() -> NanoPriceResponse {
+impl Default for AccountBalanceResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Function that sends an action to the given Nano RPC and parses the response, returning the node's error if there is one.
+async fn rpc_request<T: DeserializeOwned>(
+    url: &str,
+    key: &str,
+    mut data: serde_json::Value,
+) -> Result<T, RpcError> {
+    if !key.is_empty() {
+        data["key"] = key.into();
+    }
+
     let client = reqwest::Client::new();
-    let response = client.get(NANO_PRICE_URL).send().await.unwrap();
+    let response = client
+        .post(url)
+        .json(&data)
+        .send()
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+
+    if let Some(error) = response.get("error").and_then(|e| e.as_str()) {
+        return Err(error.to_string().into());
+    }
 
-    response.json::<NanoPriceResponse>().await.unwrap()
+    Ok(serde_json::from_value(response)?)
 }
 
 /// Function that gets the version information from the Nano API.
@@ -197,6 +412,13 @@ pub async fn get_version() -> VersionResponse {
     response.json::<VersionResponse>().await.unwrap()
 }
 
+/// Function that gets the version information of the node behind the given Nano RPC.
+pub async fn get_node_version(url: &str, key: &str) -> Result<VersionResponse, RpcError> {
+    let data = serde_json::json!({ "action": "version" });
+
+    rpc_request(url, key, data).await
+}
+
 /// Function that gets an account's information from the Nano API.
 pub async fn get_account_info(account: &str) -> AccountInfoResponse {
     let client = reqwest::Client::new();
@@ -233,7 +455,12 @@ pub async fn get_account_balance(account: &str) -> AccountBalanceResponse {
 /// Function that gets a block's info from the Nano API.
 pub async fn get_block_info(hash: &str) -> BlockInfoResponse {
     let client = reqwest::Client::new();
-    let data: HashMap<_, _> = [("action", "block_info"), ("hash", hash)].into();
+    let data: HashMap<_, _> = [
+        ("action", "block_info"),
+        ("hash", hash),
+        ("json_block", "true"),
+    ]
+    .into();
     let response = client.post(RPC_URL).json(&data).send().await.unwrap();
 
     response.json::<BlockInfoResponse>().await.unwrap()
@@ -252,7 +479,7 @@ pub async fn account_create(wallet: &str) -> AccountCreateResponse {
 pub async fn account_remove(wallet: &str, account: &str) -> AccountDestroyResponse {
     let client = reqwest::Client::new();
     let data: HashMap<_, _> = [
-        ("action", "account_create"),
+        ("action", "account_remove"),
         ("wallet", wallet),
         ("account", account),
     ]
@@ -297,3 +524,189 @@ pub async fn wallet_destroy() -> WalletDestroyResponse {
 
     response.json::<WalletDestroyResponse>().await.unwrap()
 }
+
+/// Function that gets the frontier of an account from the given Nano RPC (`None` if the account isn't opened).
+/// Only the node's "Account not found" means the account isn't opened, every other error is returned.
+pub async fn get_account_frontier(
+    url: &str,
+    key: &str,
+    account: &str,
+) -> Result<Option<String>, RpcError> {
+    let data = serde_json::json!({ "action": "account_info", "account": account });
+    match rpc_request::<serde_json::Value>(url, key, data).await {
+        Ok(response) => match response["frontier"].as_str() {
+            Some(frontier) => Ok(Some(frontier.to_string())),
+            None => Err("The node didn't return the account's frontier.".into()),
+        },
+        Err(e) if e.to_string() == ACCOUNT_NOT_FOUND_ERROR => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Function that gets the blocks an account can receive (with their amounts and sources) from the given Nano RPC.
+pub async fn get_receivable(
+    url: &str,
+    key: &str,
+    account: &str,
+    count: u32,
+) -> Result<ReceivableResponse, RpcError> {
+    let data = serde_json::json!({
+        "action": "receivable",
+        "account": account,
+        "count": count.to_string(),
+        "source": "true",
+    });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the representatives that are online (with their voting weight) from the given Nano RPC.
+pub async fn get_representatives_online(
+    url: &str,
+    key: &str,
+) -> Result<RepresentativesOnlineResponse, RpcError> {
+    let data = serde_json::json!({ "action": "representatives_online", "weight": "true" });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that asks the given Nano RPC to generate work for a root with the given difficulty (hex).
+pub async fn generate_work(
+    url: &str,
+    key: &str,
+    root: &str,
+    difficulty: &str,
+) -> Result<WorkGenerateResponse, RpcError> {
+    let data = serde_json::json!({
+        "action": "work_generate",
+        "hash": root,
+        "difficulty": difficulty,
+    });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that asks the given Nano RPC to stop generating work for a root.
+pub async fn cancel_work(url: &str, key: &str, root: &str) -> Result<serde_json::Value, RpcError> {
+    let data = serde_json::json!({ "action": "work_cancel", "hash": root });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that checks if a block was confirmed by the network using the given Nano RPC.
+pub async fn is_block_confirmed(url: &str, key: &str, hash: &str) -> Result<bool, RpcError> {
+    let data = serde_json::json!({ "action": "block_info", "hash": hash, "json_block": "true" });
+    let response: serde_json::Value = rpc_request(url, key, data).await?;
+
+    Ok(response["confirmed"] == "true")
+}
+
+/// Function that publishes a signed block to the network through the given Nano RPC, returning its hash.
+pub async fn process_block(
+    url: &str,
+    key: &str,
+    subtype: &str,
+    block: &crate::StateBlock,
+) -> Result<String, RpcError> {
+    let data = serde_json::json!({
+        "action": "process",
+        "json_block": "true",
+        "subtype": subtype.to_lowercase(),
+        "block": block,
+    });
+    let response: ProcessResponse =
+        rpc_request(url, key, data)
+            .await
+            .map_err(|e| match e.is::<serde_json::Error>() {
+                true => "The node didn't return the block's hash.".into(),
+                false => e,
+            })?;
+
+    Ok(response.hash)
+}
+
+/// Function that gets the balance and receivable amount of several accounts from the given Nano RPC.
+pub async fn get_accounts_balances(
+    url: &str,
+    key: &str,
+    accounts: &[String],
+) -> Result<AccountsBalancesResponse, RpcError> {
+    let data = serde_json::json!({ "action": "accounts_balances", "accounts": accounts });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the frontier of several accounts from the given Nano RPC (unopened accounts are left out).
+pub async fn get_accounts_frontiers(
+    url: &str,
+    key: &str,
+    accounts: &[String],
+) -> Result<AccountsFrontiersResponse, RpcError> {
+    let data = serde_json::json!({ "action": "accounts_frontiers", "accounts": accounts });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the information of several blocks (with their contents as JSON) from the given Nano RPC.
+pub async fn get_blocks_info(
+    url: &str,
+    key: &str,
+    hashes: &[String],
+) -> Result<BlocksInfoResponse, RpcError> {
+    let data = serde_json::json!({
+        "action": "blocks_info",
+        "hashes": hashes,
+        "json_block": "true",
+        "include_not_found": "true",
+    });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the telemetry of the node behind the given Nano RPC.
+pub async fn get_telemetry(url: &str, key: &str) -> Result<TelemetryResponse, RpcError> {
+    let data = serde_json::json!({ "action": "telemetry" });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the number of blocks in the ledger of the given Nano RPC.
+pub async fn get_block_count(url: &str, key: &str) -> Result<BlockCountResponse, RpcError> {
+    let data = serde_json::json!({ "action": "block_count" });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the online voting weight and the quorum seen by the given Nano RPC.
+pub async fn get_confirmation_quorum(
+    url: &str,
+    key: &str,
+) -> Result<ConfirmationQuorumResponse, RpcError> {
+    let data = serde_json::json!({ "action": "confirmation_quorum" });
+
+    rpc_request(url, key, data).await
+}
+
+/// Function that gets the representative of an account from the given Nano RPC.
+pub async fn get_account_representative(
+    url: &str,
+    key: &str,
+    account: &str,
+) -> Result<String, RpcError> {
+    let data = serde_json::json!({ "action": "account_representative", "account": account });
+    let response: AccountRepresentativeResponse = rpc_request(url, key, data).await?;
+
+    Ok(response.representative)
+}
+
+/// Function that asks the given Nano RPC if an account number is valid.
+pub async fn validate_account_number(
+    url: &str,
+    key: &str,
+    account: &str,
+) -> Result<bool, RpcError> {
+    let data = serde_json::json!({ "action": "validate_account_number", "account": account });
+    let response: ValidateAccountNumberResponse = rpc_request(url, key, data).await?;
+
+    Ok(response.valid == "1")
+}
@@ -0,0 +1,132 @@
+//! This file contains the network profiles: which Nano network the wallet talks to and what changes with it.
+
+use crate::{
+    account_prefix, account_to_public_key, public_key_to_account_with_prefix, WorkThresholds,
+    LIVE_NETWORK,
+};
+use serde::{Deserialize, Serialize};
+
+/// Enum that represents the Nano networks the wallet can use.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    /// The network with real funds.
+    #[default]
+    Live,
+
+    /// The public beta network.
+    Beta,
+
+    /// The public test network.
+    Test,
+
+    /// A local node started with `--network=dev`.
+    Dev,
+}
+
+/// Struct that represents the network settings chosen by the user.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct NetworkSettings {
+    #[serde(default)]
+    pub network: Network,
+
+    /// Prefix of the accounts without the underscore (empty to use "nano").
+    #[serde(default)]
+    pub prefix: String,
+}
+
+impl Network {
+    /// Constant with every available network.
+    pub const ALL: [Network; 4] = [Network::Live, Network::Beta, Network::Test, Network::Dev];
+
+    /// Function that returns the name of the network, as reported by the node's `version`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Live => LIVE_NETWORK,
+            Network::Beta => "beta",
+            Network::Test => "test",
+            Network::Dev => "dev",
+        }
+    }
+
+    /// Function that returns the network with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|network| network.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Function that returns the minimum difficulties of the network (as defined by the node).
+    pub fn work_thresholds(&self) -> WorkThresholds {
+        match self {
+            Network::Live | Network::Test => WorkThresholds::LIVE,
+            Network::Beta => WorkThresholds {
+                send: 0xfffff00000000000,
+                receive: 0xffffe00000000000,
+            },
+            Network::Dev => WorkThresholds {
+                send: 0xffc0000000000000,
+                receive: 0xf000000000000000,
+            },
+        }
+    }
+
+    /// Function that returns the RPC link used by default on the network.
+    /// The live network uses a public node, the others a node running on this computer with its default port.
+    pub fn default_rpc_url(&self) -> &'static str {
+        match self {
+            Network::Live => "https://rpc.nano.to",
+            Network::Beta => "http://127.0.0.1:55000",
+            Network::Test => "http://127.0.0.1:17076",
+            Network::Dev => "http://127.0.0.1:45000",
+        }
+    }
+
+    /// Function that returns the WebSocket link used by default on the network (empty to poll the RPC).
+    pub fn default_websocket_url(&self) -> &'static str {
+        match self {
+            Network::Live => "",
+            Network::Beta => "ws://127.0.0.1:57000",
+            Network::Test => "ws://127.0.0.1:17078",
+            Network::Dev => "ws://127.0.0.1:47000",
+        }
+    }
+
+    /// Function that returns the warning shown while the wallet isn't on the live network.
+    pub fn banner(&self) -> Option<String> {
+        match self {
+            Network::Live => None,
+            _ => Some(format!(
+                "{} NETWORK: the funds of this wallet aren't real.",
+                self.name().to_uppercase()
+            )),
+        }
+    }
+}
+
+impl NetworkSettings {
+    /// Function that returns the prefix of the accounts without the underscore.
+    pub fn prefix(&self) -> &str {
+        match self.prefix.trim_end_matches('_') {
+            "" => "nano",
+            prefix => prefix,
+        }
+    }
+
+    /// Function that converts a public key to an account of the network.
+    pub fn account(&self, public_key: &[u8; 32]) -> String {
+        public_key_to_account_with_prefix(public_key, &format!("{}_", self.prefix()))
+    }
+
+    /// Function that checks if an account is valid and belongs to the network (the old "xrb" prefix is accepted
+    /// wherever "nano" is).
+    pub fn is_account(&self, account: &str) -> bool {
+        let prefix = account_prefix(account).unwrap_or_default();
+        account_to_public_key(account).is_some()
+            && (prefix == self.prefix() || (prefix == "xrb" && self.prefix() == "nano"))
+    }
+
+    /// Function that returns the minimum difficulties of the network.
+    pub fn work_thresholds(&self) -> WorkThresholds {
+        self.network.work_thresholds()
+    }
+}
@@ -0,0 +1,284 @@
+//! This file contains the outbox that keeps every signed block until the network confirms it.
+//! A signing session is expensive to repeat, so a block that couldn't be published is retried instead of lost.
+
+use crate::{current_time, is_block_confirmed, process_block, RpcError, SharedList, StateBlock};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, time::Duration};
+
+/// Define the time waited before the first retry (doubled after each attempt).
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+
+/// Define the maximum time waited between retries.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(600);
+
+/// Enum that represents the state of a signed block in the outbox.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// The block was signed but no node accepted it yet.
+    PendingPublish,
+
+    /// A node accepted the block and it's waiting for confirmation.
+    Published,
+
+    /// The network confirmed the block.
+    Confirmed,
+
+    /// The nodes refused the block (e.g. bad signature or balance mismatch).
+    Rejected(String),
+
+    /// Another block with the same previous was published first.
+    Fork,
+}
+
+/// Struct that represents a signed block in the outbox.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OutboxEntry {
+    pub hash: String,
+    pub subtype: String,
+    pub block: StateBlock,
+    pub status: OutboxStatus,
+    pub attempts: u32,
+
+    /// Time of the last attempt to publish the block (seconds since the unix epoch).
+    pub last_attempt: u64,
+
+    /// Time the block was signed (seconds since the unix epoch).
+    pub created_at: u64,
+
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// Struct that represents a node the outbox publishes to.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OutboxNode {
+    pub url: String,
+    pub key: String,
+}
+
+/// Struct that represents the outbox, shared between the relay and the interface and stored in a file shared by every
+/// process of the wallet.
+#[derive(Debug, Clone, Default)]
+pub struct Outbox {
+    entries: SharedList<OutboxEntry>,
+}
+
+impl OutboxStatus {
+    /// Function that checks if the block needs no more attempts.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            OutboxStatus::Confirmed | OutboxStatus::Rejected(_) | OutboxStatus::Fork
+        )
+    }
+
+    /// Function that returns the name of the status shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutboxStatus::PendingPublish => "Pending publish",
+            OutboxStatus::Published => "Published",
+            OutboxStatus::Confirmed => "Confirmed",
+            OutboxStatus::Rejected(_) => "Rejected",
+            OutboxStatus::Fork => "Fork",
+        }
+    }
+}
+
+/// Function that returns the time waited before the next attempt after `attempts` attempts.
+pub fn retry_delay(attempts: u32) -> Duration {
+    RETRY_BASE_DELAY
+        .checked_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+        .unwrap_or(RETRY_MAX_DELAY)
+        .min(RETRY_MAX_DELAY)
+}
+
+/// Function that classifies the answer of a node to `process` (`Err` keeps the block pending).
+pub fn status_from_process_error(error: &str) -> Option<OutboxStatus> {
+    let lowercase = error.to_lowercase();
+    if lowercase.contains("fork") {
+        Some(OutboxStatus::Fork)
+    } else if lowercase.contains("old block") {
+        // the node already has it, which is what a rebroadcast wants
+        Some(OutboxStatus::Published)
+    } else if [
+        "bad signature",
+        "balance mismatch",
+        "unreceivable",
+        "block is invalid",
+    ]
+    .iter()
+    .any(|e| lowercase.contains(e))
+    {
+        Some(OutboxStatus::Rejected(error.to_string()))
+    } else {
+        None
+    }
+}
+
+impl Outbox {
+    /// Function that opens the outbox stored in a file (it's created when the first block is added).
+    /// An outbox that can't be read is an error, since treating it as empty would lose the blocks waiting in it.
+    pub fn open(path: &str) -> Result<Self, String> {
+        Ok(Self {
+            entries: SharedList::open("outbox", path)?,
+        })
+    }
+
+    /// Function that returns the outbox stored in a file without reading it, so every change fails until it can be
+    /// read.
+    pub fn at(path: &str) -> Self {
+        Self {
+            entries: SharedList::at("outbox", path),
+        }
+    }
+
+    /// Function that returns every block in the outbox.
+    pub fn entries(&self) -> Vec<OutboxEntry> {
+        self.entries.entries()
+    }
+
+    /// Function that returns the blocks of an account, newest first.
+    pub fn entries_for(&self, account: &str) -> Vec<OutboxEntry> {
+        let mut entries: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.block.account == account)
+            .collect();
+        entries.sort_by_key(|entry| Reverse(entry.created_at));
+        entries
+    }
+
+    /// Function that changes an entry and stores the outbox.
+    fn update(&self, hash: &str, change: impl FnOnce(&mut OutboxEntry)) -> Result<(), String> {
+        self.entries.update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.hash == hash) {
+                change(entry);
+            }
+            Ok(())
+        })
+    }
+
+    /// Function that adds a signed block to the outbox before it's published, returning its hash.
+    pub fn add(&self, subtype: &str, block: &StateBlock) -> Result<String, RpcError> {
+        let hash = block.hash_hex()?;
+        self.entries.update(|entries| {
+            if !entries.iter().any(|entry| entry.hash == hash) {
+                entries.push(OutboxEntry {
+                    hash: hash.clone(),
+                    subtype: subtype.to_lowercase(),
+                    block: block.clone(),
+                    status: OutboxStatus::PendingPublish,
+                    attempts: 0,
+                    last_attempt: 0,
+                    created_at: current_time(),
+                    last_error: None,
+                });
+            }
+            Ok(())
+        })?;
+
+        Ok(hash)
+    }
+
+    /// Function that records the result of an attempt to publish a block.
+    pub fn record_attempt(&self, hash: &str, result: Result<(), String>) -> Result<(), String> {
+        self.update(hash, |entry| {
+            entry.attempts += 1;
+            entry.last_attempt = current_time();
+            match result {
+                Ok(_) => {
+                    entry.last_error = None;
+                    if !entry.status.is_final() {
+                        entry.status = OutboxStatus::Published;
+                    }
+                }
+                Err(e) => {
+                    if let Some(status) = status_from_process_error(&e) {
+                        entry.status = status;
+                    }
+                    entry.last_error = Some(e);
+                }
+            }
+        })
+    }
+
+    /// Function that publishes a block to the first node that accepts it (also used to rebroadcast).
+    /// Returns the block's new status (`None` if it isn't in the outbox) or an error if it couldn't be recorded.
+    pub async fn publish(
+        &self,
+        hash: &str,
+        nodes: &[OutboxNode],
+    ) -> Result<Option<OutboxStatus>, String> {
+        let Some(entry) = self
+            .entries
+            .read()?
+            .into_iter()
+            .find(|entry| entry.hash == hash)
+        else {
+            return Ok(None);
+        };
+
+        let mut result = Err("There are no nodes to publish to.".to_string());
+        for node in nodes {
+            result = process_block(&node.url, &node.key, &entry.subtype, &entry.block)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+            // a node that refuses the block for good doesn't need to be asked again by the others
+            match &result {
+                Ok(_) => break,
+                Err(e) if status_from_process_error(e).is_some() => break,
+                Err(_) => {}
+            }
+        }
+        self.record_attempt(hash, result)?;
+
+        Ok(self
+            .entries
+            .read()?
+            .into_iter()
+            .find(|entry| entry.hash == hash)
+            .map(|entry| entry.status))
+    }
+
+    /// Function that checks if any of the nodes confirmed a published block.
+    async fn check_confirmation(&self, hash: &str, nodes: &[OutboxNode]) -> Result<bool, String> {
+        for node in nodes {
+            if let Ok(true) = is_block_confirmed(&node.url, &node.key, hash).await {
+                self.update(hash, |entry| {
+                    entry.status = OutboxStatus::Confirmed;
+                    entry.last_error = None;
+                })?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Function that retries the blocks that are due and detects the ones that were confirmed.
+    /// Returns whether any entry changed status, or an error if the outbox couldn't be read or saved.
+    pub async fn process(&self, nodes: &[OutboxNode]) -> Result<bool, String> {
+        let mut changed = false;
+
+        for entry in self.entries.read()? {
+            if entry.status.is_final() {
+                continue;
+            }
+            if entry.status == OutboxStatus::Published
+                && self.check_confirmation(&entry.hash, nodes).await?
+            {
+                changed = true;
+                continue;
+            }
+
+            // pending blocks are published again and unconfirmed ones rebroadcast, with backoff
+            let due = entry.last_attempt + retry_delay(entry.attempts).as_secs();
+            if current_time() >= due {
+                let status = self.publish(&entry.hash, nodes).await?;
+                changed |= status.as_ref() != Some(&entry.status);
+            }
+        }
+
+        Ok(changed)
+    }
+}
@@ -0,0 +1,164 @@
+//! This file contains the spending policies each participant checks before contributing a signature share.
+
+use crate::{
+    account_to_public_key, nano_to_raw, public_key_to_account, raw_to_nano, AccountState,
+    StateBlock, DAY,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Struct that represents the hours of the day (UTC, from 0 to 23) in which blocks can be signed.
+/// A window whose start is after its end goes through midnight (e.g. 22 to 6) and one that starts and ends at the
+/// same hour is the whole day.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+/// Struct that represents the rules an account's blocks must follow before this participant signs them.
+/// Amounts are written in Nano and every empty rule allows everything.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct SpendingPolicy {
+    #[serde(default)]
+    pub max_per_transaction: Option<String>,
+
+    #[serde(default)]
+    pub daily_limit: Option<String>,
+
+    #[serde(default)]
+    pub weekly_limit: Option<String>,
+
+    /// Accounts that can receive Nano (empty allows any account).
+    #[serde(default)]
+    pub allowed_recipients: Vec<String>,
+
+    #[serde(default)]
+    pub forbid_representative_change: bool,
+
+    #[serde(default)]
+    pub time_window: Option<TimeWindow>,
+}
+
+/// Struct that represents what the policy needs to know about the blocks signed before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpendingHistory {
+    /// Raw sent in the last 24 hours.
+    pub last_day: u128,
+
+    /// Raw sent in the last 7 days.
+    pub last_week: u128,
+}
+
+impl TimeWindow {
+    /// Function that checks the hours of the window.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.start_hour < 24 && self.end_hour < 24 {
+            true => Ok(()),
+            false => Err("The hours of the time window must be between 0 and 23.".to_string()),
+        }
+    }
+
+    /// Function that checks if a time (seconds since the unix epoch) is inside the window.
+    pub fn contains(&self, time: u64) -> bool {
+        let hour = ((time % DAY) / 3600) as u8;
+        match self.start_hour.cmp(&self.end_hour) {
+            Ordering::Equal => true,
+            Ordering::Less => (self.start_hour..self.end_hour).contains(&hour),
+            Ordering::Greater => hour >= self.start_hour || hour < self.end_hour,
+        }
+    }
+}
+
+/// Function that parses an optional limit written in Nano.
+fn parse_limit(limit: &Option<String>, name: &str) -> Result<Option<u128>, String> {
+    match limit.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(limit) => nano_to_raw(limit)
+            .map(Some)
+            .ok_or_else(|| format!("The {name} of the policy is not a valid amount.")),
+    }
+}
+
+impl SpendingPolicy {
+    /// Function that checks if the block follows the policy, explaining why it doesn't.
+    /// `state` is the account before the block and `now` the current time (seconds since the unix epoch).
+    pub fn check(
+        &self,
+        state: &AccountState,
+        block: &StateBlock,
+        history: SpendingHistory,
+        now: u64,
+    ) -> Result<(), String> {
+        if let Some(window) = self.time_window {
+            window.validate()?;
+            if !window.contains(now) {
+                return Err(format!(
+                    "The policy only allows signing between {:02}:00 and {:02}:00 (UTC).",
+                    window.start_hour, window.end_hour
+                ));
+            }
+        }
+
+        // any block can change the representative, not only change blocks
+        if self.forbid_representative_change
+            && state.frontier.is_some()
+            && block.representative != state.representative
+        {
+            return Err("The policy forbids changing the representative.".to_string());
+        }
+
+        let sent = state.balance.saturating_sub(block.balance);
+        if sent == 0 {
+            return Ok(());
+        }
+
+        if !self.allowed_recipients.is_empty() {
+            let link = hex::decode(&block.link)
+                .ok()
+                .and_then(|link| <[u8; 32]>::try_from(link).ok());
+            let recipient = link
+                .map(|link| public_key_to_account(&link))
+                .unwrap_or_default();
+            // the keys are compared so the prefix of the allowed accounts doesn't matter
+            if !self
+                .allowed_recipients
+                .iter()
+                .any(|allowed| link.is_some() && account_to_public_key(allowed) == link)
+            {
+                return Err(format!("The policy doesn't allow sending to {recipient}."));
+            }
+        }
+
+        let limits = [
+            (
+                parse_limit(&self.max_per_transaction, "maximum per transaction")?,
+                sent,
+                "per transaction",
+            ),
+            (
+                parse_limit(&self.daily_limit, "daily limit")?,
+                history.last_day.saturating_add(sent),
+                "per day",
+            ),
+            (
+                parse_limit(&self.weekly_limit, "weekly limit")?,
+                history.last_week.saturating_add(sent),
+                "per week",
+            ),
+        ];
+        for (limit, amount, period) in limits {
+            if let Some(limit) = limit {
+                if amount > limit {
+                    return Err(format!(
+                        "The policy allows sending at most {} XNO {period} ({} XNO with this transaction).",
+                        raw_to_nano(limit),
+                        raw_to_nano(amount)
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
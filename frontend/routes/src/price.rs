@@ -0,0 +1,326 @@
+//! This file contains the price providers used to convert Nano balances to fiat currencies.
+
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error, future::Future, pin::Pin};
+
+/// Define the error type returned by the price providers.
+pub type PriceError = Box<dyn Error + Send + Sync>;
+
+/// Define the future returned by the price providers.
+pub type PriceFuture<'a> = Pin<Box<dyn Future<Output = Result<f64, PriceError>> + Send + 'a>>;
+
+/// Define the CoinGecko API link as constant.
+const COINGECKO_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+/// Define the CoinPaprika API link as constant.
+const COINPAPRIKA_URL: &str = "https://api.coinpaprika.com/v1/tickers/xno-nano";
+
+/// Define the Kraken API link as constant.
+const KRAKEN_URL: &str = "https://api.kraken.com/0/public/Ticker";
+
+/// Define the CryptoCompare API link as constant.
+const CRYPTOCOMPARE_URL: &str = "https://min-api.cryptocompare.com/data/price";
+
+/// Trait that represents a source for the price of Nano in a given fiat currency.
+pub trait PriceProvider: Send + Sync {
+    /// Function that returns the name of the provider.
+    fn name(&self) -> &'static str;
+
+    /// Function that gets the price of one Nano in the given currency (e.g. "eur").
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a>;
+}
+
+/// Struct that gets the price from CoinGecko.
+pub struct CoinGecko;
+
+/// Struct that gets the price from CoinPaprika.
+pub struct CoinPaprika;
+
+/// Struct that gets the price from Kraken.
+pub struct Kraken;
+
+/// Struct that gets the price from CryptoCompare.
+pub struct CryptoCompare;
+
+/// Struct that never contacts any service and is used when the wallet should stay offline.
+pub struct Offline;
+
+/// Struct that asks every provider and returns the median of the prices that were received.
+pub struct MedianPrice {
+    pub providers: Vec<Box<dyn PriceProvider>>,
+}
+
+/// Struct that asks every provider in order and returns the first price that was received.
+pub struct FirstAvailablePrice {
+    pub providers: Vec<Box<dyn PriceProvider>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CoinPaprikaQuote {
+    price: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct CoinPaprikaResponse {
+    quotes: HashMap<String, CoinPaprikaQuote>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KrakenTicker {
+    c: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KrakenResponse {
+    error: Vec<String>,
+    result: Option<HashMap<String, KrakenTicker>>,
+}
+
+impl PriceProvider for CoinGecko {
+    fn name(&self) -> &'static str {
+        "CoinGecko"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            let currency = currency.to_lowercase();
+            let response = reqwest::Client::new()
+                .get(COINGECKO_URL)
+                .query(&[("ids", "nano"), ("vs_currencies", currency.as_str())])
+                .send()
+                .await?
+                .json::<HashMap<String, HashMap<String, f64>>>()
+                .await?;
+
+            response
+                .get("nano")
+                .and_then(|prices| prices.get(&currency))
+                .copied()
+                .ok_or_else(|| format!("CoinGecko has no price for {currency}.").into())
+        })
+    }
+}
+
+impl PriceProvider for CoinPaprika {
+    fn name(&self) -> &'static str {
+        "CoinPaprika"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            let currency = currency.to_uppercase();
+            let response = reqwest::Client::new()
+                .get(COINPAPRIKA_URL)
+                .query(&[("quotes", currency.as_str())])
+                .send()
+                .await?
+                .json::<CoinPaprikaResponse>()
+                .await?;
+
+            response
+                .quotes
+                .get(&currency)
+                .map(|quote| quote.price)
+                .ok_or_else(|| format!("CoinPaprika has no price for {currency}.").into())
+        })
+    }
+}
+
+impl PriceProvider for Kraken {
+    fn name(&self) -> &'static str {
+        "Kraken"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            let pair = format!("NANO{}", currency.to_uppercase());
+            let response = reqwest::Client::new()
+                .get(KRAKEN_URL)
+                .query(&[("pair", pair.as_str())])
+                .send()
+                .await?
+                .json::<KrakenResponse>()
+                .await?;
+
+            if let Some(error) = response.error.first() {
+                return Err(error.clone().into());
+            }
+
+            response
+                .result
+                .and_then(|result| result.into_values().next())
+                .and_then(|ticker| ticker.c.first().and_then(|p| p.parse::<f64>().ok()))
+                .ok_or_else(|| format!("Kraken has no price for {pair}.").into())
+        })
+    }
+}
+
+impl PriceProvider for CryptoCompare {
+    fn name(&self) -> &'static str {
+        "CryptoCompare"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            let currency = currency.to_uppercase();
+            let response = reqwest::Client::new()
+                .get(CRYPTOCOMPARE_URL)
+                .query(&[("fsym", "XNO"), ("tsyms", currency.as_str())])
+                .send()
+                .await?
+                .json::<HashMap<String, f64>>()
+                .await?;
+
+            response
+                .get(&currency)
+                .copied()
+                .ok_or_else(|| format!("CryptoCompare has no price for {currency}.").into())
+        })
+    }
+}
+
+impl PriceProvider for Offline {
+    fn name(&self) -> &'static str {
+        "Offline"
+    }
+
+    fn get_price<'a>(&'a self, _currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async { Err("Price fetching is disabled.".into()) })
+    }
+}
+
+impl PriceProvider for MedianPrice {
+    fn name(&self) -> &'static str {
+        "Median"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            let prices = join_all(self.providers.iter().map(|p| p.get_price(currency))).await;
+            let prices: Vec<f64> = prices.into_iter().filter_map(Result::ok).collect();
+
+            median(prices).ok_or_else(|| "None of the price providers answered.".into())
+        })
+    }
+}
+
+impl PriceProvider for FirstAvailablePrice {
+    fn name(&self) -> &'static str {
+        "First Available"
+    }
+
+    fn get_price<'a>(&'a self, currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move {
+            for provider in &self.providers {
+                if let Ok(price) = provider.get_price(currency).await {
+                    return Ok(price);
+                }
+            }
+
+            Err("None of the price providers answered.".into())
+        })
+    }
+}
+
+/// Function that returns the median of a list of prices (or `None` if the list is empty).
+pub fn median(mut prices: Vec<f64>) -> Option<f64> {
+    prices.retain(|price| price.is_finite());
+    prices.sort_by(|a, b| a.total_cmp(b));
+
+    match prices.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(prices[n / 2]),
+        n => Some((prices[n / 2 - 1] + prices[n / 2]) / 2.),
+    }
+}
+
+/// Enum that represents the price sources that can be selected in the settings.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    CoinGecko,
+    CoinPaprika,
+    Kraken,
+    CryptoCompare,
+    Offline,
+}
+
+/// Enum that represents how the selected price sources are combined.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceMode {
+    /// Use the first source that answers.
+    #[default]
+    FirstAvailable,
+
+    /// Ask every source and use the median of the answers.
+    Median,
+}
+
+/// Struct that represents the price settings chosen by the user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PriceSettings {
+    pub sources: Vec<PriceSource>,
+    #[serde(default)]
+    pub mode: PriceMode,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+fn default_currency() -> String {
+    "eur".to_string()
+}
+
+impl PriceSource {
+    /// Constant with every available price source.
+    pub const ALL: [PriceSource; 5] = [
+        PriceSource::CoinGecko,
+        PriceSource::CoinPaprika,
+        PriceSource::Kraken,
+        PriceSource::CryptoCompare,
+        PriceSource::Offline,
+    ];
+
+    /// Function that returns the provider that implements the source.
+    pub fn provider(&self) -> Box<dyn PriceProvider> {
+        match self {
+            PriceSource::CoinGecko => Box::new(CoinGecko),
+            PriceSource::CoinPaprika => Box::new(CoinPaprika),
+            PriceSource::Kraken => Box::new(Kraken),
+            PriceSource::CryptoCompare => Box::new(CryptoCompare),
+            PriceSource::Offline => Box::new(Offline),
+        }
+    }
+
+    /// Function that returns the name of the source.
+    pub fn name(&self) -> &'static str {
+        self.provider().name()
+    }
+}
+
+impl Default for PriceSettings {
+    /// Function that returns the default price settings (CoinGecko only, as before).
+    fn default() -> Self {
+        Self {
+            sources: vec![PriceSource::CoinGecko],
+            mode: PriceMode::FirstAvailable,
+            currency: default_currency(),
+        }
+    }
+}
+
+impl PriceSettings {
+    /// Function that builds the provider described by the settings.
+    pub fn provider(&self) -> Box<dyn PriceProvider> {
+        let providers = self.sources.iter().map(|s| s.provider()).collect();
+
+        match self.mode {
+            PriceMode::FirstAvailable => Box::new(FirstAvailablePrice { providers }),
+            PriceMode::Median => Box::new(MedianPrice { providers }),
+        }
+    }
+}
+
+/// Function that gets the price of Nano according to the user's price settings.
+pub async fn get_nano_price(settings: &PriceSettings) -> Option<f64> {
+    settings.provider().get_price(&settings.currency).await.ok()
+}
@@ -0,0 +1,33 @@
+//! This file contains the QR codes the wallet shows for its account and payment requests and the ones it reads from
+//! images.
+
+use qrcode::{render::svg, EcLevel, QrCode};
+
+/// Function that draws a QR code with the given text as an SVG image.
+pub fn qr_code_svg(text: &str) -> Result<String, String> {
+    let code = QrCode::with_error_correction_level(text.as_bytes(), EcLevel::M)
+        .map_err(|e| format!("The QR code couldn't be created: {e}"))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .quiet_zone(true)
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .build())
+}
+
+/// Function that reads the text of the first QR code found in an image (PNG, JPEG, ...).
+pub fn read_qr_code(image: &[u8]) -> Result<String, String> {
+    let image = image::load_from_memory(image)
+        .map_err(|e| format!("The image couldn't be opened: {e}"))?
+        .into_luma8();
+    let mut image = rqrr::PreparedImage::prepare(image);
+    let grid = image
+        .detect_grids()
+        .into_iter()
+        .next()
+        .ok_or("There's no QR code in the image.")?;
+    grid.decode()
+        .map(|(_, text)| text)
+        .map_err(|e| format!("The QR code couldn't be read: {e}"))
+}
@@ -0,0 +1,275 @@
+//! This file contains the local RPC relay that sits between the signing session and the node.
+//! frost-sig talks to the relay as if it were the node, which lets the wallet answer `work_generate`
+//! itself and check every block before it is forwarded with `process`.
+//! The relay adds the node's API key to the requests, so it only answers on a random path known by the session.
+
+use crate::{
+    account_to_public_key, validate_work, Outbox, RpcError, StateBlock, WorkCache, WorkSource,
+    WorkThresholds,
+};
+use axum::{
+    extract::State,
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+/// Struct that represents the options of the relay.
+pub struct RelayOptions {
+    /// RPC link of the node the requests are forwarded to.
+    pub node_url: String,
+
+    /// API key added to the forwarded requests that don't have one (empty to forward them as they are).
+    pub node_key: String,
+
+    /// Source used to answer `work_generate` and to fix blocks with invalid work (`None` forwards to the node).
+    pub work_source: Option<Arc<dyn WorkSource>>,
+
+    /// Work computed ahead of time.
+    pub work_cache: WorkCache,
+
+    /// Minimum difficulties of the node's network.
+    pub thresholds: WorkThresholds,
+
+    /// Outbox that keeps the signed blocks until they are confirmed.
+    pub outbox: Outbox,
+}
+
+/// Struct that represents a running relay (it stops when dropped).
+pub struct RpcRelay {
+    url: String,
+    state: Arc<RelayState>,
+    task: JoinHandle<()>,
+}
+
+/// Struct that represents the state shared by the relay's handlers.
+struct RelayState {
+    options: RelayOptions,
+    client: reqwest::Client,
+    expected: Mutex<Option<StateBlock>>,
+    published: Mutex<Vec<StateBlock>>,
+}
+
+impl RpcRelay {
+    /// Function that starts the relay on a free localhost port and a random path.
+    pub async fn start(options: RelayOptions) -> Result<Self, RpcError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        // other local processes can reach the port, but not spend the API key without the path
+        let path = format!("/{}", hex::encode(rand::random::<[u8; 32]>()));
+        let url = format!("http://{}{path}", listener.local_addr()?);
+
+        let state = Arc::new(RelayState {
+            options,
+            client: reqwest::Client::new(),
+            expected: Mutex::new(None),
+            published: Mutex::new(Vec::new()),
+        });
+        let router = Router::new()
+            .route(&path, post(handle))
+            .with_state(state.clone());
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Ok(Self { url, state, task })
+    }
+
+    /// Function that returns the link that should be used instead of the node's (it includes the secret path).
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Function that sets the block the next `process` must publish (built and reviewed locally).
+    /// Any other block is rejected before it reaches the node.
+    pub fn expect(&self, block: StateBlock) {
+        if let Ok(mut expected) = self.state.expected.lock() {
+            *expected = Some(block);
+        }
+    }
+
+    /// Function that returns the blocks the node accepted through the relay.
+    pub fn published(&self) -> Vec<StateBlock> {
+        self.state
+            .published
+            .lock()
+            .map(|published| published.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for RpcRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Function that returns the root a block's work is computed over (the previous block or, when opening, the account's key).
+pub fn block_root(block: &Value) -> Option<String> {
+    let previous = block["previous"].as_str().unwrap_or_default();
+    match previous.chars().all(|c| c == '0') {
+        true => account_to_public_key(block["account"].as_str()?).map(hex::encode_upper),
+        false => Some(previous.to_uppercase()),
+    }
+}
+
+/// Function that handles each request sent to the relay.
+async fn handle(
+    State(state): State<Arc<RelayState>>,
+    headers: HeaderMap,
+    Json(mut request): Json<Value>,
+) -> Json<Value> {
+    let result = match request["action"].as_str().unwrap_or_default() {
+        "work_generate" => match generate_work(&state, &request).await {
+            Some(response) => Ok(response),
+            None => forward(&state, &headers, &request).await,
+        },
+        "process" => match verify_block(&state, &request) {
+            Ok(_) => match ensure_valid_work(&state, &mut request).await {
+                Ok(_) => publish(&state, &headers, &request).await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        },
+        _ => forward(&state, &headers, &request).await,
+    };
+
+    match result {
+        Ok(response) => Json(response),
+        Err(e) => Json(json!({ "error": e.to_string() })),
+    }
+}
+
+/// Function that forwards a request to the node and returns its response.
+async fn forward(
+    state: &RelayState,
+    headers: &HeaderMap,
+    request: &Value,
+) -> Result<Value, RpcError> {
+    let mut request = request.clone();
+    if !state.options.node_key.is_empty() && request["key"].as_str().unwrap_or_default().is_empty()
+    {
+        request["key"] = state.options.node_key.clone().into();
+    }
+
+    let mut forwarded = state.client.post(&state.options.node_url).json(&request);
+    if let Some(authorization) = headers.get(AUTHORIZATION) {
+        forwarded = forwarded.header(AUTHORIZATION, authorization);
+    }
+
+    Ok(forwarded.send().await?.json::<Value>().await?)
+}
+
+/// Function that stores a signed block in the outbox, forwards it with `process` and records the result.
+async fn publish(
+    state: &RelayState,
+    headers: &HeaderMap,
+    request: &Value,
+) -> Result<Value, RpcError> {
+    let block = StateBlock::from_json(&request["block"])?;
+    let subtype = request["subtype"].as_str().unwrap_or("send");
+    let hash = state.options.outbox.add(subtype, &block)?;
+
+    let response = forward(state, headers, request).await;
+    let outbox = &state.options.outbox;
+    match &response {
+        Ok(response) if response["hash"].is_string() => {
+            if let Ok(mut published) = state.published.lock() {
+                published.push(block);
+            }
+            outbox.record_attempt(&hash, Ok(()))?;
+        }
+        Ok(response) => outbox.record_attempt(
+            &hash,
+            Err(response["error"]
+                .as_str()
+                .unwrap_or("The node didn't accept the block.")
+                .to_string()),
+        )?,
+        Err(e) => outbox.record_attempt(&hash, Err(e.to_string()))?,
+    }
+
+    response
+}
+
+/// Function that answers `work_generate` with cached or locally generated work (`None` to let the node answer).
+async fn generate_work(state: &RelayState, request: &Value) -> Option<Value> {
+    let root = request["hash"].as_str()?;
+    let difficulty = request["difficulty"]
+        .as_str()
+        .and_then(|d| u64::from_str_radix(d, 16).ok())
+        .unwrap_or(state.options.thresholds.send);
+
+    let work = match state.options.work_cache.get(root, difficulty) {
+        Some(work) => work,
+        None => {
+            let source = state.options.work_source.as_ref()?;
+            let work = source.generate(root, difficulty).await.ok()?;
+            state.options.work_cache.insert(root, &work);
+            work
+        }
+    };
+
+    Some(json!({
+        "work": work,
+        "difficulty": format!("{:016x}", crate::work_difficulty(root, &work)?),
+        "hash": root,
+    }))
+}
+
+/// Function that checks the signature of a block before it's published and compares it with the expected one.
+fn verify_block(state: &RelayState, request: &Value) -> Result<(), RpcError> {
+    let block = StateBlock::from_json(&request["block"])?;
+    let expected = state
+        .expected
+        .lock()
+        .map_err(|_| "The expected block is unavailable.")?
+        .clone();
+
+    match expected {
+        Some(expected) => block.verify_against(&expected),
+        None => {
+            let public_key =
+                account_to_public_key(&block.account).ok_or("The account is invalid.")?;
+            block.verify_signature(&public_key)
+        }
+    }
+}
+
+/// Function that validates the work of a block before it's published, replacing it when it isn't enough.
+async fn ensure_valid_work(state: &RelayState, request: &mut Value) -> Result<(), RpcError> {
+    // the block is either an object (json_block) or a string with the json
+    let as_string = request["block"].is_string();
+    let mut block: Value = match as_string {
+        true => serde_json::from_str(request["block"].as_str().unwrap_or_default())?,
+        false => request["block"].clone(),
+    };
+
+    let root = block_root(&block).ok_or("The block has an invalid root.")?;
+    let threshold = state
+        .options
+        .thresholds
+        .for_subtype(request["subtype"].as_str().unwrap_or("send"));
+    let work = block["work"].as_str().unwrap_or_default().to_string();
+    if validate_work(&root, &work, threshold) {
+        return Ok(());
+    }
+
+    let work = match state.options.work_cache.get(&root, threshold) {
+        Some(work) => work,
+        None => match &state.options.work_source {
+            Some(source) => source.generate(&root, threshold).await?,
+            // without a source the node decides (some nodes generate work on process)
+            None => return Ok(()),
+        },
+    };
+    block["work"] = work.into();
+    request["block"] = match as_string {
+        true => block.to_string().into(),
+        false => block,
+    };
+
+    Ok(())
+}
@@ -0,0 +1,353 @@
+//! This file contains the scheduled payments of each account: sends the group repeats with a cadence (e.g. a monthly
+//! invoice), which the wallet brings up when they're due so the group can sign them.
+
+use crate::{
+    civil_from_days, days_from_civil, days_in_month, is_valid_account, nano_to_raw, SharedList, DAY,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Define the time after which a payment that is due but wasn't paid is shown as missed.
+pub const MISSED_AFTER: u64 = DAY;
+
+/// Enum that represents how often a scheduled payment repeats.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Cadence {
+    /// The payment is made a single time.
+    Once,
+    Daily,
+    Weekly,
+
+    /// The payment repeats on the day of the month it started (or the last day of shorter months).
+    #[default]
+    Monthly,
+}
+
+/// Struct that represents a payment the group makes on a schedule.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledPayment {
+    pub id: String,
+
+    /// Account that makes the payment.
+    pub account: String,
+
+    pub receivers_account: String,
+
+    /// Amount of Nano sent, written in decimal.
+    pub amount: String,
+
+    /// What the payment is for.
+    #[serde(default)]
+    pub label: String,
+
+    pub cadence: Cadence,
+
+    /// Time of the first payment (seconds since the unix epoch).
+    pub start: u64,
+
+    /// Times of the payments that were made.
+    #[serde(default)]
+    pub paid: Vec<u64>,
+
+    /// Times of the payments the group decided not to make.
+    #[serde(default)]
+    pub skipped: Vec<u64>,
+}
+
+/// Enum that represents the state of a payment in the calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStatus {
+    Paid,
+    Skipped,
+
+    /// The payment is due and can still be made on time.
+    Due,
+
+    /// The payment was due more than `MISSED_AFTER` ago and wasn't made.
+    Missed,
+
+    Upcoming,
+}
+
+/// Struct that represents a payment in the calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEntry {
+    pub payment: ScheduledPayment,
+
+    /// Time the payment is due (seconds since the unix epoch).
+    pub due: u64,
+
+    pub status: PaymentStatus,
+}
+
+/// Struct that represents the scheduled payments of every account, stored in a file shared by every process of the
+/// wallet.
+#[derive(Debug, Clone, Default)]
+pub struct PaymentSchedule {
+    payments: SharedList<ScheduledPayment>,
+}
+
+impl Cadence {
+    /// Constant with every available cadence.
+    pub const ALL: [Cadence; 4] = [
+        Cadence::Once,
+        Cadence::Daily,
+        Cadence::Weekly,
+        Cadence::Monthly,
+    ];
+
+    /// Function that returns the name of the cadence shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cadence::Once => "Once",
+            Cadence::Daily => "Daily",
+            Cadence::Weekly => "Weekly",
+            Cadence::Monthly => "Monthly",
+        }
+    }
+
+    /// Function that returns the cadence with the given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|cadence| cadence.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl PaymentStatus {
+    /// Function that returns the name of the status shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaymentStatus::Paid => "Paid",
+            PaymentStatus::Skipped => "Skipped",
+            PaymentStatus::Due => "Due",
+            PaymentStatus::Missed => "Missed",
+            PaymentStatus::Upcoming => "Upcoming",
+        }
+    }
+}
+
+impl ScheduledPayment {
+    /// Function that creates a payment that wasn't made yet, identified by the time it was created.
+    pub fn new(
+        account: &str,
+        receivers_account: &str,
+        amount: &str,
+        label: &str,
+        cadence: Cadence,
+        start: u64,
+    ) -> Self {
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        Self {
+            id: format!("{created:x}"),
+            account: account.to_string(),
+            receivers_account: receivers_account.to_string(),
+            amount: amount.trim().to_string(),
+            label: label.trim().to_string(),
+            cadence,
+            start,
+            paid: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Function that checks the payment, returning the problems found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if !is_valid_account(&self.receivers_account) {
+            errors.push(format!(
+                "\"{}\" isn't a valid Nano account.",
+                self.receivers_account
+            ));
+        }
+        if nano_to_raw(&self.amount).is_none_or(|raw| raw == 0) {
+            errors.push(format!("{} is not a valid amount.", self.amount));
+        }
+        errors
+    }
+
+    /// Function that returns the time of the `n`th payment (`None` if there's no such payment).
+    pub fn occurrence(&self, n: u64) -> Option<u64> {
+        match self.cadence {
+            Cadence::Once => (n == 0).then_some(self.start),
+            Cadence::Daily => self.start.checked_add(n.checked_mul(DAY)?),
+            Cadence::Weekly => self.start.checked_add(n.checked_mul(7 * DAY)?),
+            Cadence::Monthly => {
+                let (year, month, day) = civil_from_days((self.start / DAY) as i64);
+                let months = i64::from(month) - 1 + i64::try_from(n).ok()?;
+                let (year, month) = (year + months / 12, (months % 12) as u32 + 1);
+                let day = day.min(days_in_month(year, month));
+                let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+                days.checked_mul(DAY)?.checked_add(self.start % DAY)
+            }
+        }
+    }
+
+    /// Function that returns the times of the payments between `from` (included) and `to` (excluded).
+    pub fn occurrences(&self, from: u64, to: u64) -> Vec<u64> {
+        // the fixed cadences skip straight to the first payment in the interval
+        let first = match self.cadence {
+            Cadence::Daily => from.saturating_sub(self.start) / DAY,
+            Cadence::Weekly => from.saturating_sub(self.start) / (7 * DAY),
+            Cadence::Once | Cadence::Monthly => 0,
+        };
+        (first..)
+            .map_while(|n| self.occurrence(n))
+            .take_while(|&due| due < to)
+            .filter(|&due| due >= from)
+            .collect()
+    }
+
+    /// Function that checks if the payment due at the given time was made or skipped.
+    pub fn is_settled(&self, due: u64) -> bool {
+        self.paid.contains(&due) || self.skipped.contains(&due)
+    }
+
+    /// Function that returns the oldest payment that is due and wasn't made or skipped.
+    pub fn due(&self, now: u64) -> Option<u64> {
+        (0..)
+            .map_while(|n| self.occurrence(n))
+            .take_while(|&due| due <= now)
+            .find(|&due| !self.is_settled(due))
+    }
+
+    /// Function that returns the state of the payment due at the given time.
+    pub fn status(&self, due: u64, now: u64) -> PaymentStatus {
+        if self.paid.contains(&due) {
+            PaymentStatus::Paid
+        } else if self.skipped.contains(&due) {
+            PaymentStatus::Skipped
+        } else if due > now {
+            PaymentStatus::Upcoming
+        } else if now - due > MISSED_AFTER {
+            PaymentStatus::Missed
+        } else {
+            PaymentStatus::Due
+        }
+    }
+}
+
+impl PaymentSchedule {
+    /// Function that opens the schedule stored in a file (it's created when the first payment is added).
+    /// A schedule that can't be read is an error, since treating it as empty would erase it on the next change.
+    pub fn open(path: &str) -> Result<Self, String> {
+        Ok(Self {
+            payments: SharedList::open("schedule", path)?,
+        })
+    }
+
+    /// Function that returns the schedule stored in a file without reading it, so every change fails until it can be
+    /// read.
+    pub fn at(path: &str) -> Self {
+        Self {
+            payments: SharedList::at("schedule", path),
+        }
+    }
+
+    /// Function that returns the scheduled payments of an account.
+    pub fn payments_for(&self, account: &str) -> Vec<ScheduledPayment> {
+        self.payments
+            .entries()
+            .into_iter()
+            .filter(|payment| payment.account == account)
+            .collect()
+    }
+
+    /// Function that changes the payments and stores the schedule.
+    fn update<T>(
+        &self,
+        change: impl FnOnce(&mut Vec<ScheduledPayment>) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.payments.update(change)
+    }
+
+    /// Function that adds a payment to the schedule.
+    pub fn add(&self, payment: ScheduledPayment) -> Result<(), String> {
+        let errors = payment.validate();
+        if !errors.is_empty() {
+            return Err(errors.join(" "));
+        }
+        self.update(|payments| {
+            payments.push(payment);
+            Ok(())
+        })
+    }
+
+    /// Function that removes a payment from the schedule.
+    pub fn remove(&self, id: &str) -> Result<(), String> {
+        self.update(|payments| {
+            let count = payments.len();
+            payments.retain(|payment| payment.id != id);
+            match payments.len() < count {
+                true => Ok(()),
+                false => Err(format!("There's no scheduled payment {id}.")),
+            }
+        })
+    }
+
+    /// Function that changes the payment with the given id.
+    fn change(&self, id: &str, change: impl FnOnce(&mut ScheduledPayment)) -> Result<(), String> {
+        self.update(|payments| {
+            let payment = payments
+                .iter_mut()
+                .find(|payment| payment.id == id)
+                .ok_or(format!("There's no scheduled payment {id}."))?;
+            change(payment);
+            Ok(())
+        })
+    }
+
+    /// Function that records that the payment due at the given time was made.
+    pub fn mark_paid(&self, id: &str, due: u64) -> Result<(), String> {
+        self.change(id, |payment| {
+            if !payment.paid.contains(&due) {
+                payment.paid.push(due);
+            }
+        })
+    }
+
+    /// Function that records that the group won't make the payment due at the given time.
+    pub fn skip(&self, id: &str, due: u64) -> Result<(), String> {
+        self.change(id, |payment| {
+            if !payment.skipped.contains(&due) {
+                payment.skipped.push(due);
+            }
+        })
+    }
+
+    /// Function that returns the payments of an account that are due, each with the time of its oldest unpaid
+    /// payment, oldest first.
+    pub fn due_payments(&self, account: &str, now: u64) -> Vec<(ScheduledPayment, u64)> {
+        let mut due: Vec<_> = self
+            .payments_for(account)
+            .into_iter()
+            .filter_map(|payment| payment.due(now).map(|due| (payment, due)))
+            .collect();
+        due.sort_by_key(|(_, due)| *due);
+        due
+    }
+
+    /// Function that returns the payments of an account between `from` (included) and `to` (excluded), in order.
+    pub fn calendar(&self, account: &str, from: u64, to: u64, now: u64) -> Vec<CalendarEntry> {
+        let mut calendar: Vec<_> = self
+            .payments_for(account)
+            .into_iter()
+            .flat_map(|payment| {
+                payment
+                    .occurrences(from, to)
+                    .into_iter()
+                    .map(move |due| CalendarEntry {
+                        status: payment.status(due, now),
+                        payment: payment.clone(),
+                        due,
+                    })
+            })
+            .collect();
+        calendar.sort_by_key(|entry| entry.due);
+        calendar
+    }
+}
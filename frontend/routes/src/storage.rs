@@ -0,0 +1,187 @@
+//! This file contains the storage of the files kept next to the configuration: atomic writes that keep a backup of the
+//! previous file and the lists that the desktop application and the daemon change at the same time.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    error::Error,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Function that returns the path of the backup of a configuration file.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Function that writes a file atomically: `write` creates a temporary file that then replaces the previous one, which
+/// is kept as a backup if asked. A failure at any point leaves the previous file untouched.
+pub fn write_atomic(
+    path: &Path,
+    backup: bool,
+    write: impl FnOnce(&Path) -> Result<(), Box<dyn Error>>,
+) -> Result<(), String> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    write(&temporary).map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+    if let Ok(file) = fs::File::open(&temporary) {
+        let _ = file.sync_all();
+    }
+    if backup && path.exists() {
+        fs::copy(path, backup_path(path))
+            .map_err(|e| format!("Couldn't back up {}: {e}", path.display()))?;
+    }
+    fs::rename(&temporary, path).map_err(|e| {
+        let _ = fs::remove_file(&temporary);
+        format!("Couldn't replace {}: {e}", path.display())
+    })
+}
+
+/// Struct that represents a list stored in a JSON file that more than one process of the wallet changes.
+/// Every read and change goes to the file while holding a lock on it, so no process works on a stale copy or erases
+/// the entries written by another one. Without a file the list is only kept in memory.
+#[derive(Debug, Clone)]
+pub struct SharedList<T> {
+    /// Name of the list shown in the errors (e.g. "journal").
+    name: &'static str,
+    path: Option<PathBuf>,
+
+    /// Last entries read, which also makes the threads of a process take turns.
+    entries: Arc<Mutex<Vec<T>>>,
+}
+
+impl<T> Default for SharedList<T> {
+    fn default() -> Self {
+        Self {
+            name: "list",
+            path: None,
+            entries: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<T> SharedList<T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    /// Function that returns the list stored in a file without reading it (it's created on the first change).
+    pub fn at(name: &'static str, path: &str) -> Self {
+        Self {
+            name,
+            path: Some(PathBuf::from(path)),
+            entries: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Function that opens the list stored in a file, failing if the file can't be read.
+    pub fn open(name: &'static str, path: &str) -> Result<Self, String> {
+        let list = Self::at(name, path);
+        list.read()?;
+        Ok(list)
+    }
+
+    /// Function that reads every entry of the list.
+    pub fn read(&self) -> Result<Vec<T>, String> {
+        let mut entries = self.lock_entries()?;
+        if let Some(path) = &self.path {
+            // nothing was written yet, and the directory may not exist either
+            if !path.exists() {
+                return Ok(Vec::new());
+            }
+            let _lock = self.lock_file(path, false)?;
+            *entries = self.read_file(path)?;
+        }
+        Ok(entries.clone())
+    }
+
+    /// Function that returns every entry of the list, or the last ones read if the file can't be read now.
+    pub fn entries(&self) -> Vec<T> {
+        self.read().unwrap_or_else(|_| {
+            self.entries
+                .lock()
+                .map(|entries| entries.clone())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Function that changes the entries read from the file and writes them back, all while holding the lock.
+    /// Nothing is written if `change` fails.
+    pub fn update<R>(
+        &self,
+        change: impl FnOnce(&mut Vec<T>) -> Result<R, String>,
+    ) -> Result<R, String> {
+        let mut entries = self.lock_entries()?;
+        let Some(path) = &self.path else {
+            let mut changed = entries.clone();
+            let result = change(&mut changed)?;
+            *entries = changed;
+            return Ok(result);
+        };
+
+        let _lock = self.lock_file(path, true)?;
+        let mut changed = self.read_file(path)?;
+        let result = change(&mut changed)?;
+
+        let contents = serde_json::to_string_pretty(&changed)
+            .map_err(|e| format!("The {} couldn't be saved: {e}", self.name))?;
+        write_atomic(path, false, |temporary| {
+            fs::write(temporary, contents).map_err(Into::into)
+        })
+        .map_err(|e| format!("The {} couldn't be saved: {e}", self.name))?;
+
+        *entries = changed;
+        Ok(result)
+    }
+
+    /// Function that makes the threads of this process take turns on the list.
+    fn lock_entries(&self) -> Result<MutexGuard<'_, Vec<T>>, String> {
+        self.entries
+            .lock()
+            .map_err(|_| format!("The {} is unavailable.", self.name))
+    }
+
+    /// Function that locks the list for the other processes until the returned file is dropped.
+    /// The lock is taken on a file next to the list, since writing the list replaces its file.
+    fn lock_file(&self, path: &Path, exclusive: bool) -> Result<fs::File, String> {
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+
+        let error = |e: std::io::Error| format!("The {} couldn't be locked: {e}", self.name);
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(PathBuf::from(lock_path))
+            .map_err(error)?;
+        match exclusive {
+            true => file.lock(),
+            false => file.lock_shared(),
+        }
+        .map_err(error)?;
+        Ok(file)
+    }
+
+    /// Function that reads the entries stored in the file (none if it wasn't created yet).
+    fn read_file(&self, path: &Path) -> Result<Vec<T>, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+                format!(
+                    "The {} {} is corrupt and won't be changed until it's fixed ({e}).",
+                    self.name,
+                    path.display()
+                )
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(format!(
+                "The {} {} couldn't be read ({e}).",
+                self.name,
+                path.display()
+            )),
+        }
+    }
+}
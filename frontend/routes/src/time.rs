@@ -0,0 +1,92 @@
+//! This file contains the handling of time: the current time, the civil dates of the calendar and the timestamps shown
+//! to the user, all in UTC.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Define the length of a day in seconds.
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// Define the length of a week in seconds.
+pub const WEEK: u64 = 7 * DAY;
+
+/// Function that returns the current time in seconds since the unix epoch.
+pub fn current_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Function that converts a number of days since the unix epoch to a civil date (year, month, day).
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
+
+/// Function that converts a civil date to the number of days since the unix epoch.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = (i64::from(month) + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Function that returns the number of days of a month.
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Function that formats a time (seconds since the unix epoch) as "YYYY-MM-DD HH:MM UTC".
+pub fn format_timestamp(time: u64) -> String {
+    let (year, month, day) = civil_from_days((time / DAY) as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        (time % DAY) / 3600,
+        (time % 3600) / 60
+    )
+}
+
+/// Function that reads a time written as "YYYY-MM-DD" or "YYYY-MM-DD HH:MM" (UTC) as seconds since the unix epoch.
+pub fn parse_timestamp(text: &str) -> Option<u64> {
+    let text = text.trim().trim_end_matches(" UTC");
+    let (date, time) = text.split_once([' ', 'T']).unwrap_or((text, "00:00"));
+
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<u32>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let (hour, minute) = time.split_once(':')?;
+    let (hour, minute) = (hour.parse::<u64>().ok()?, minute.parse::<u64>().ok()?);
+    if !(1970..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year.into(), month)
+        || hour > 23
+        || minute > 59
+    {
+        return None;
+    }
+
+    let days = days_from_civil(year.into(), month, day) as u64;
+    Some(days * DAY + hour * 3600 + minute * 60)
+}
@@ -0,0 +1,137 @@
+//! This file contains the payment requests the wallet shares and reads as `nano:` URIs
+//! (`nano:<account>?amount=<raw>&label=<label>&message=<message>`).
+
+use crate::{raw_to_nano, NetworkSettings};
+
+/// Constant value for the scheme of the payment requests.
+pub const NANO_SCHEME: &str = "nano";
+
+/// Struct that represents a request to pay an account, with an optional amount in raw.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub account: String,
+    pub amount: Option<u128>,
+
+    /// Name of whoever asks for the payment.
+    pub label: Option<String>,
+
+    /// What the payment is for.
+    pub message: Option<String>,
+}
+
+impl PaymentRequest {
+    /// Function that creates a request to pay any amount to an account.
+    pub fn new(account: &str) -> Self {
+        Self {
+            account: account.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Function that reads a payment request from a `nano:` URI or from a bare account of the given network.
+    /// The amount must be an exact number of raw and unknown parameters are ignored.
+    pub fn parse(text: &str, network: &NetworkSettings) -> Result<Self, String> {
+        let text = text.trim();
+        let uri = match text.split_once(':') {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case(NANO_SCHEME) => {
+                rest.trim_start_matches("//")
+            }
+            _ => text,
+        };
+        let (account, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let account = account.trim_end_matches('/');
+        if !network.is_account(account) {
+            return Err(format!(
+                "\"{account}\" isn't an account of the {} network.",
+                network.network.name()
+            ));
+        }
+
+        let mut request = Self::new(account);
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let value = percent_decode(value)
+                .ok_or(format!("The value of \"{name}\" isn't correctly encoded."))?;
+            let field = match name {
+                "amount" => {
+                    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+                        return Err(format!("\"{value}\" isn't a valid amount in raw."));
+                    }
+                    let amount = value
+                        .parse::<u128>()
+                        .map_err(|_| format!("\"{value}\" is more raw than exists."))?;
+                    request.amount.replace(amount).map(|_| ())
+                }
+                "label" => request.label.replace(value).map(|_| ()),
+                "message" => request.message.replace(value).map(|_| ()),
+                _ => None,
+            };
+            if field.is_some() {
+                return Err(format!("The payment request has more than one \"{name}\"."));
+            }
+        }
+        Ok(request)
+    }
+
+    /// Function that writes the payment request as a `nano:` URI.
+    pub fn to_uri(&self) -> String {
+        let parameters: Vec<String> = [
+            ("amount", self.amount.map(|amount| amount.to_string())),
+            ("label", self.label.clone()),
+            ("message", self.message.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!("{name}={}", percent_encode(&value?))))
+        .collect();
+
+        match parameters.is_empty() {
+            true => format!("{NANO_SCHEME}:{}", self.account),
+            false => format!("{NANO_SCHEME}:{}?{}", self.account, parameters.join("&")),
+        }
+    }
+
+    /// Function that returns the requested amount in Nano, written exactly.
+    pub fn amount_in_nano(&self) -> Option<String> {
+        self.amount.map(raw_to_nano)
+    }
+
+    /// Function that returns the label and the message of the request to show them to the user.
+    pub fn description(&self) -> Option<String> {
+        match (&self.label, &self.message) {
+            (Some(label), Some(message)) => Some(format!("{label}: {message}")),
+            (Some(text), None) | (None, Some(text)) => Some(text.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Function that encodes every byte of a text that isn't an unreserved character of a URI.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Function that decodes the `%XX` sequences of a text (`None` if they're invalid or aren't UTF-8).
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let hex = [input.next()?, input.next()?];
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return None;
+                }
+                bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,179 @@
+//! This file contains the client for the Nano node WebSocket API and the polling fallback used when it is not available.
+
+use crate::get_account_frontier;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{error::Error, time::Duration};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// Define the error type returned by the WebSocket client.
+pub type WebSocketError = Box<dyn Error + Send + Sync>;
+
+/// Define the struct for the message of the WebSocket `confirmation` topic.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ConfirmationMessage {
+    pub account: String,
+    pub amount: String,
+    pub hash: String,
+    #[serde(default)]
+    pub confirmation_type: String,
+    #[serde(default)]
+    pub block: Option<serde_json::Value>,
+}
+
+/// Define the struct for each message received from the WebSocket API.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WebSocketMessage {
+    #[serde(default)]
+    pub topic: Option<String>,
+    #[serde(default)]
+    pub ack: Option<String>,
+    #[serde(default)]
+    pub time: Option<String>,
+    #[serde(default)]
+    pub message: Option<serde_json::Value>,
+}
+
+/// Struct that represents an open subscription to the `confirmation` topic.
+pub struct ConfirmationSubscription {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+/// Enum that represents an update to an account that is being watched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountEvent {
+    /// A block involving the account was confirmed (received through the WebSocket).
+    Confirmation(ConfirmationMessage),
+
+    /// The account's frontier changed (detected by polling the RPC).
+    FrontierChanged(String),
+}
+
+/// Struct that represents the options used to watch an account.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// WebSocket link of the node (empty to only poll).
+    pub websocket_url: String,
+
+    /// RPC link of the node used when polling.
+    pub rpc_url: String,
+
+    /// API key of the RPC.
+    pub key: String,
+
+    /// Account being watched.
+    pub account: String,
+
+    /// Time between each poll of the RPC.
+    pub poll_interval: Duration,
+
+    /// Time spent polling before trying to connect to the WebSocket again.
+    pub reconnect_interval: Duration,
+}
+
+impl ConfirmationSubscription {
+    /// Function that connects to the WebSocket API and subscribes to the confirmations of the given accounts.
+    pub async fn connect(url: &str, accounts: &[String]) -> Result<Self, WebSocketError> {
+        let (mut stream, _) = connect_async(url).await?;
+
+        let subscription = json!({
+            "action": "subscribe",
+            "topic": "confirmation",
+            "ack": true,
+            "options": {
+                "accounts": accounts,
+            },
+        });
+        stream
+            .send(Message::Text(subscription.to_string().into()))
+            .await?;
+
+        // the node acknowledges the subscription before sending any confirmation
+        loop {
+            match Self::read_message(&mut stream).await? {
+                Some(message) if message.ack.as_deref() == Some("subscribe") => break,
+                Some(_) => continue,
+                None => return Err("The WebSocket closed before acknowledging.".into()),
+            }
+        }
+
+        Ok(Self { stream })
+    }
+
+    /// Function that waits for the next confirmation (`None` when the connection is closed).
+    pub async fn next(&mut self) -> Option<Result<ConfirmationMessage, WebSocketError>> {
+        loop {
+            let message = match Self::read_message(&mut self.stream).await {
+                Ok(Some(message)) => message,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if message.topic.as_deref() != Some("confirmation") {
+                continue;
+            }
+
+            if let Some(message) = message.message {
+                return Some(serde_json::from_value(message).map_err(|e| e.into()));
+            }
+        }
+    }
+
+    /// Function that reads the next text message of the stream.
+    async fn read_message(
+        stream: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+    ) -> Result<Option<WebSocketMessage>, WebSocketError> {
+        while let Some(message) = stream.next().await {
+            match message? {
+                Message::Text(text) => return Ok(Some(serde_json::from_str(&text)?)),
+                Message::Close(_) => return Ok(None),
+                _ => continue,
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Function that watches an account and calls `on_event` for each update.
+/// It uses the WebSocket API when possible and polls the RPC while it isn't available.
+pub async fn watch_account<F>(options: WatchOptions, mut on_event: F)
+where
+    F: FnMut(AccountEvent),
+{
+    let mut frontier = get_account_frontier(&options.rpc_url, &options.key, &options.account)
+        .await
+        .unwrap_or(None);
+
+    loop {
+        if !options.websocket_url.is_empty() {
+            let accounts = [options.account.clone()];
+            if let Ok(mut subscription) =
+                ConfirmationSubscription::connect(&options.websocket_url, &accounts).await
+            {
+                while let Some(Ok(confirmation)) = subscription.next().await {
+                    frontier = None;
+                    on_event(AccountEvent::Confirmation(confirmation));
+                }
+            }
+        }
+
+        // fall back to polling until it's time to try the WebSocket again
+        let mut elapsed = Duration::ZERO;
+        while options.websocket_url.is_empty() || elapsed < options.reconnect_interval {
+            tokio::time::sleep(options.poll_interval).await;
+            elapsed += options.poll_interval;
+
+            if let Ok(Some(new_frontier)) =
+                get_account_frontier(&options.rpc_url, &options.key, &options.account).await
+            {
+                if frontier.as_ref() != Some(&new_frontier) {
+                    frontier = Some(new_frontier.clone());
+                    on_event(AccountEvent::FrontierChanged(new_frontier));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! This file contains the proof-of-work subsystem: thresholds, validation and the sources that generate work.
+
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    future::Future,
+    hash::{BuildHasher, RandomState},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// Define the error type returned by the work sources.
+pub type WorkError = Box<dyn Error + Send + Sync>;
+
+/// Define the future returned by the work sources.
+pub type WorkFuture<'a> = Pin<Box<dyn Future<Output = Result<String, WorkError>> + Send + 'a>>;
+
+/// Define the minimum difficulty of send and change blocks (epoch 2).
+pub const SEND_THRESHOLD: u64 = 0xfffffff800000000;
+
+/// Define the minimum difficulty of receive and open blocks (epoch 2).
+pub const RECEIVE_THRESHOLD: u64 = 0xfffffe0000000000;
+
+/// Struct that represents the minimum difficulties of a network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkThresholds {
+    /// Minimum difficulty of send and change blocks.
+    pub send: u64,
+
+    /// Minimum difficulty of receive and open blocks.
+    pub receive: u64,
+}
+
+/// Define how many nonces each thread tries before checking if the work was cancelled.
+const BATCH_SIZE: u64 = 1 << 16;
+
+/// Trait that represents a source of proof-of-work.
+pub trait WorkSource: Send + Sync {
+    /// Function that returns the name of the source.
+    fn name(&self) -> &'static str;
+
+    /// Function that generates work (as hex) for the given root with at least the given difficulty.
+    fn generate<'a>(&'a self, root: &'a str, difficulty: u64) -> WorkFuture<'a>;
+
+    /// Function that cancels every generation that is in progress.
+    fn cancel(&self);
+}
+
+/// Struct that generates work locally using every CPU thread.
+pub struct CpuWorkGenerator {
+    threads: usize,
+    in_progress: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+/// Struct that asks a node to generate work through the `work_generate` RPC.
+pub struct RemoteWorkSource {
+    url: String,
+    key: String,
+    in_progress: Mutex<Vec<String>>,
+}
+
+/// Struct that tries each source in order until one of them generates work.
+pub struct FallbackWorkSource {
+    pub sources: Vec<Box<dyn WorkSource>>,
+}
+
+/// Struct that stores work computed ahead of time, indexed by root.
+#[derive(Debug, Clone, Default)]
+pub struct WorkCache {
+    work: Arc<Mutex<HashMap<String, String>>>,
+}
+
+/// Enum that represents the work sources that can be selected in the settings.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkSourceKind {
+    /// Ask the node (the previous behaviour).
+    Remote,
+
+    /// Generate the work on this computer.
+    Local,
+
+    /// Ask the node and generate the work on this computer when it refuses.
+    #[default]
+    RemoteThenLocal,
+}
+
+/// Struct that represents the work settings chosen by the user.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WorkSettings {
+    #[serde(default)]
+    pub source: WorkSourceKind,
+
+    /// Number of threads used by the local generator (0 uses every thread available).
+    #[serde(default)]
+    pub threads: usize,
+
+    /// Whether the work for the next block is computed right after each confirmed transaction.
+    #[serde(default = "default_precompute")]
+    pub precompute: bool,
+}
+
+fn default_precompute() -> bool {
+    true
+}
+
+impl Default for WorkSettings {
+    fn default() -> Self {
+        Self {
+            source: WorkSourceKind::default(),
+            threads: 0,
+            precompute: default_precompute(),
+        }
+    }
+}
+
+impl WorkSettings {
+    /// Function that builds the work source described by the settings.
+    pub fn work_source(&self, url: &str, key: &str) -> Box<dyn WorkSource> {
+        let remote = RemoteWorkSource::new(url, key);
+        let local = CpuWorkGenerator::new(self.threads);
+
+        match self.source {
+            WorkSourceKind::Remote => Box::new(remote),
+            WorkSourceKind::Local => Box::new(local),
+            WorkSourceKind::RemoteThenLocal => Box::new(FallbackWorkSource {
+                sources: vec![Box::new(remote), Box::new(local)],
+            }),
+        }
+    }
+}
+
+impl WorkThresholds {
+    /// Define the thresholds of the live network.
+    pub const LIVE: Self = Self {
+        send: SEND_THRESHOLD,
+        receive: RECEIVE_THRESHOLD,
+    };
+
+    /// Function that returns the minimum difficulty for a block subtype ("send", "receive", "open", "change").
+    pub fn for_subtype(&self, subtype: &str) -> u64 {
+        match subtype.to_lowercase().as_str() {
+            "receive" | "open" => self.receive,
+            _ => self.send,
+        }
+    }
+}
+
+impl Default for WorkThresholds {
+    fn default() -> Self {
+        Self::LIVE
+    }
+}
+
+/// Function that returns the minimum difficulty of the live network for a block subtype.
+pub fn threshold_for_subtype(subtype: &str) -> u64 {
+    WorkThresholds::LIVE.for_subtype(subtype)
+}
+
+/// Function that parses a 32 byte hex root.
+fn parse_root(root: &str) -> Option<[u8; 32]> {
+    hex::decode(root).ok()?.try_into().ok()
+}
+
+/// Function that computes the difficulty of a nonce for a root (blake2b of the nonce and the root).
+pub fn work_value(root: &[u8; 32], nonce: u64) -> u64 {
+    let mut hasher = Blake2bVar::new(8).expect("8 is a valid blake2b output size");
+    hasher.update(&nonce.to_le_bytes());
+    hasher.update(root);
+    let mut value = [0u8; 8];
+    hasher
+        .finalize_variable(&mut value)
+        .expect("the buffer has the requested size");
+    u64::from_le_bytes(value)
+}
+
+/// Function that computes the difficulty of a hex work for a hex root (`None` if they are malformed).
+pub fn work_difficulty(root: &str, work: &str) -> Option<u64> {
+    let root = parse_root(root)?;
+    let nonce = u64::from_str_radix(work, 16).ok()?;
+    Some(work_value(&root, nonce))
+}
+
+/// Function that checks if a work is valid for a root with the given threshold.
+pub fn validate_work(root: &str, work: &str, threshold: u64) -> bool {
+    matches!(work_difficulty(root, work), Some(difficulty) if difficulty >= threshold)
+}
+
+/// Function that returns the multiplier of a difficulty relative to a threshold (as reported by the node).
+pub fn difficulty_multiplier(difficulty: u64, threshold: u64) -> f64 {
+    (threshold.wrapping_neg() as f64) / (difficulty.wrapping_neg().max(1) as f64)
+}
+
+impl CpuWorkGenerator {
+    /// Function that creates a generator with the given number of threads (0 uses every thread available).
+    pub fn new(threads: usize) -> Self {
+        let threads = match threads {
+            0 => thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            threads => threads,
+        };
+
+        Self {
+            threads,
+            in_progress: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Function that searches for a nonce in every thread until one is found or it's cancelled.
+    fn search(
+        root: [u8; 32],
+        difficulty: u64,
+        threads: usize,
+        cancelled: &AtomicBool,
+    ) -> Option<u64> {
+        let found = AtomicBool::new(false);
+        let seed = RandomState::new().hash_one(root);
+        let step = u64::MAX / threads as u64;
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads as u64)
+                .map(|i| {
+                    let found = &found;
+                    scope.spawn(move || {
+                        let mut nonce = seed.wrapping_add(i.wrapping_mul(step));
+                        while !found.load(Ordering::Relaxed) && !cancelled.load(Ordering::Relaxed) {
+                            for _ in 0..BATCH_SIZE {
+                                if work_value(&root, nonce) >= difficulty {
+                                    found.store(true, Ordering::Relaxed);
+                                    return Some(nonce);
+                                }
+                                nonce = nonce.wrapping_add(1);
+                            }
+                        }
+                        None
+                    })
+                })
+                .collect();
+
+            handles.into_iter().find_map(|h| h.join().ok().flatten())
+        })
+    }
+}
+
+/// Struct that cancels the search when the generation is dropped (e.g. the request was abandoned).
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl WorkSource for CpuWorkGenerator {
+    fn name(&self) -> &'static str {
+        "Local"
+    }
+
+    fn generate<'a>(&'a self, root: &'a str, difficulty: u64) -> WorkFuture<'a> {
+        Box::pin(async move {
+            let root = parse_root(root).ok_or("The root is not a valid hash.")?;
+            let cancelled = Arc::new(AtomicBool::new(false));
+            if let Ok(mut in_progress) = self.in_progress.lock() {
+                in_progress.retain(|c| !c.load(Ordering::Relaxed));
+                in_progress.push(cancelled.clone());
+            }
+            let _guard = CancelOnDrop(cancelled.clone());
+
+            let threads = self.threads;
+            let search_cancelled = cancelled.clone();
+            let nonce = tokio::task::spawn_blocking(move || {
+                Self::search(root, difficulty, threads, &search_cancelled)
+            })
+            .await?;
+
+            match nonce {
+                Some(nonce) => Ok(format!("{nonce:016x}")),
+                None => Err("The work generation was cancelled.".into()),
+            }
+        })
+    }
+
+    fn cancel(&self) {
+        if let Ok(in_progress) = self.in_progress.lock() {
+            for cancelled in in_progress.iter() {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl RemoteWorkSource {
+    /// Function that creates a source that uses the given Nano RPC.
+    pub fn new(url: &str, key: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            key: key.to_string(),
+            in_progress: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl WorkSource for RemoteWorkSource {
+    fn name(&self) -> &'static str {
+        "Remote"
+    }
+
+    fn generate<'a>(&'a self, root: &'a str, difficulty: u64) -> WorkFuture<'a> {
+        Box::pin(async move {
+            if let Ok(mut in_progress) = self.in_progress.lock() {
+                in_progress.push(root.to_string());
+            }
+            let response =
+                crate::generate_work(&self.url, &self.key, root, &format!("{difficulty:016x}"))
+                    .await;
+            if let Ok(mut in_progress) = self.in_progress.lock() {
+                in_progress.retain(|r| r != root);
+            }
+            let response = response?;
+
+            // never trust the node's work blindly
+            match validate_work(root, &response.work, difficulty) {
+                true => Ok(response.work),
+                false => Err("The node returned invalid work.".into()),
+            }
+        })
+    }
+
+    fn cancel(&self) {
+        let roots = match self.in_progress.lock() {
+            Ok(in_progress) => in_progress.clone(),
+            Err(_) => return,
+        };
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            for root in roots {
+                let url = self.url.clone();
+                let key = self.key.clone();
+                handle.spawn(async move {
+                    let _ = crate::cancel_work(&url, &key, &root).await;
+                });
+            }
+        }
+    }
+}
+
+impl WorkSource for FallbackWorkSource {
+    fn name(&self) -> &'static str {
+        "Fallback"
+    }
+
+    fn generate<'a>(&'a self, root: &'a str, difficulty: u64) -> WorkFuture<'a> {
+        Box::pin(async move {
+            let mut last_error: WorkError = "There are no work sources.".into();
+            for source in &self.sources {
+                match source.generate(root, difficulty).await {
+                    Ok(work) => return Ok(work),
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(last_error)
+        })
+    }
+
+    fn cancel(&self) {
+        for source in &self.sources {
+            source.cancel();
+        }
+    }
+}
+
+impl WorkCache {
+    /// Function that returns the cached work for a root if it is valid for the difficulty.
+    pub fn get(&self, root: &str, difficulty: u64) -> Option<String> {
+        let work = self.work.lock().ok()?.get(&root.to_uppercase()).cloned()?;
+        match validate_work(root, &work, difficulty) {
+            true => Some(work),
+            false => None,
+        }
+    }
+
+    /// Function that stores the work for a root.
+    pub fn insert(&self, root: &str, work: &str) {
+        if let Ok(mut cache) = self.work.lock() {
+            cache.insert(root.to_uppercase(), work.to_string());
+        }
+    }
+
+    /// Function that computes and stores the work of a root so the next block doesn't wait for it.
+    /// The send threshold is used because it also satisfies receive blocks.
+    pub async fn precompute(
+        &self,
+        source: &dyn WorkSource,
+        root: &str,
+        thresholds: &WorkThresholds,
+    ) -> Result<String, WorkError> {
+        if let Some(work) = self.get(root, thresholds.send) {
+            return Ok(work);
+        }
+
+        let work = source.generate(root, thresholds.send).await?;
+        self.insert(root, &work);
+        Ok(work)
+    }
+}
@@ -0,0 +1,92 @@
+//! Tests for the conversions between public keys and Nano accounts.
+
+use routes::{
+    account_body_to_public_key, account_prefix, account_to_public_key, is_valid_account,
+    public_key_to_account, public_key_to_account_with_prefix,
+};
+
+/// Constant value for the public key of the genesis account.
+const GENESIS_KEY: &str = "E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA";
+
+/// Constant value for the genesis account.
+const GENESIS: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+/// Function that decodes a public key written in hexadecimal.
+fn key(hex: &str) -> [u8; 32] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn keys_and_accounts_match_the_network() {
+    let vectors = [
+        (GENESIS_KEY, GENESIS),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "nano_1111111111111111111111111111111111111111111111111111hifc8npp",
+        ),
+        (
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            "nano_3zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzc3yoon41",
+        ),
+        (
+            "5D1AA8A45F8736519D707FCB375976A7F9AF795091021D7E9C7548D6F45DD8D5",
+            "nano_1qato4k7z3spc8gq1zyd8xeqfbzsoxwo36a45ozbrxcatut7up8ohyardu1z",
+        ),
+    ];
+
+    for (public_key, account) in vectors {
+        assert_eq!(public_key_to_account(&key(public_key)), account);
+        assert_eq!(
+            account_to_public_key(account),
+            Some(key(public_key)),
+            "{account}"
+        );
+    }
+}
+
+#[test]
+fn accounts_with_a_wrong_checksum_are_rejected() {
+    // the last 8 characters are the checksum, the ones before them the key
+    let wrong_checksum = GENESIS.replace("hr3", "hr4");
+    let wrong_key = GENESIS.replace("xtoncuohr3", "xuoncuohr3");
+    for account in [&wrong_checksum, &wrong_key] {
+        assert_eq!(account_to_public_key(account), None, "{account}");
+        assert!(!is_valid_account(account), "{account}");
+    }
+
+    for invalid in [
+        "",
+        "nano_",
+        "3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3",
+        // too short, too long and a key over 256 bits
+        &GENESIS[..GENESIS.len() - 1],
+        format!("{GENESIS}1").as_str(),
+        GENESIS.replacen("_3", "_4", 1).as_str(),
+        // characters that aren't in the alphabet
+        GENESIS.replace("hr3", "hr0").as_str(),
+        GENESIS.replace("ncuohr3", "ncuOhr3").as_str(),
+        // the prefix must be made of lowercase letters
+        GENESIS.replacen("nano_", "NANO_", 1).as_str(),
+        GENESIS.replacen("nano_", "_", 1).as_str(),
+    ] {
+        assert!(!is_valid_account(invalid), "{invalid}");
+    }
+}
+
+#[test]
+fn every_prefix_encodes_the_same_key() {
+    let public_key = key(GENESIS_KEY);
+    let legacy = public_key_to_account_with_prefix(&public_key, "xrb_");
+    assert_eq!(legacy, GENESIS.replacen("nano_", "xrb_", 1));
+    assert_eq!(account_to_public_key(&legacy), Some(public_key));
+
+    // test and dev networks use their own prefix
+    let test = public_key_to_account_with_prefix(&public_key, "test_");
+    assert_eq!(account_to_public_key(&test), Some(public_key));
+    assert_eq!(account_prefix(&test), Some("test"));
+    assert_eq!(account_prefix(GENESIS), Some("nano"));
+    assert_eq!(
+        account_body_to_public_key(&GENESIS["nano_".len()..]),
+        Some(public_key)
+    );
+}
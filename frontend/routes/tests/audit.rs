@@ -0,0 +1,169 @@
+//! Tests for the audit log of the group sessions.
+
+mod common;
+
+use common::temp_path;
+use routes::{AuditEvent, AuditLog, AuditRecord};
+use std::{fs, path::PathBuf};
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+/// Function that returns a log with three records.
+fn log_with_records(name: &str) -> (AuditLog, PathBuf) {
+    let path = temp_path(&format!("audit-{name}"), "audit.log");
+    let log = AuditLog::open(path.to_str().unwrap());
+    for i in 0..3 {
+        log.append(
+            ACCOUNT,
+            AuditEvent::SessionSucceeded {
+                block_hash: i.to_string().repeat(64),
+                published: true,
+            },
+        )
+        .unwrap();
+    }
+    (log, path)
+}
+
+/// Function that rewrites the lines of a log file.
+fn edit_lines(path: &PathBuf, edit: impl FnOnce(&mut Vec<String>)) {
+    let mut lines: Vec<String> = fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect();
+    edit(&mut lines);
+    fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn records_are_chained_to_the_one_before() {
+    let (log, _) = log_with_records("chain");
+    let records = log.records().unwrap().records;
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].previous_hash, "0".repeat(64));
+    for (i, pair) in records.windows(2).enumerate() {
+        assert_eq!(pair[1].sequence, i as u64 + 1);
+        assert_eq!(pair[1].previous_hash, pair[0].hash, "{i}");
+    }
+    assert_eq!(records[2].account, ACCOUNT);
+    assert_eq!(log.verify().unwrap(), 3);
+
+    // an empty log is valid
+    assert_eq!(
+        AuditLog::open(temp_path("audit-empty", "audit.log").to_str().unwrap())
+            .verify()
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn edited_or_removed_records_are_detected() {
+    let (log, path) = log_with_records("edited");
+    edit_lines(&path, |lines| {
+        lines[1] = lines[1].replace("\"published\":true", "\"published\":false");
+    });
+    assert_eq!(
+        log.verify().unwrap_err().to_string(),
+        "The audit log was tampered with at record 1."
+    );
+
+    let (log, path) = log_with_records("removed");
+    edit_lines(&path, |lines| {
+        lines.remove(1);
+    });
+    assert_eq!(
+        log.verify().unwrap_err().to_string(),
+        "The audit log was tampered with at record 1."
+    );
+
+    // removing the last record can't be told apart, but the records before it stay valid
+    let (log, path) = log_with_records("truncated");
+    edit_lines(&path, |lines| {
+        lines.pop();
+    });
+    assert_eq!(log.verify().unwrap(), 2);
+}
+
+#[test]
+fn damaged_records_dont_stop_the_log() {
+    let (log, path) = log_with_records("damaged");
+    edit_lines(&path, |lines| lines[1] = "{\"sequence\": ".to_string());
+
+    // the damage is reported, the other records are still read and new ones are chained to the last one
+    assert_eq!(
+        log.verify().unwrap_err().to_string(),
+        "The audit log has unreadable records on lines 2."
+    );
+    let contents = log.records().unwrap();
+    assert_eq!(contents.records.len(), 2);
+    assert_eq!(contents.damaged_lines, [2]);
+    let record = log
+        .append(
+            ACCOUNT,
+            AuditEvent::KeygenFailed {
+                error: "test".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(record.sequence, 3);
+    assert_eq!(record.previous_hash, contents.records[1].hash);
+
+    // a damaged last record is skipped as well
+    edit_lines(&path, |lines| lines.push("{\"sequence\": ".to_string()));
+    let record = log
+        .append(
+            ACCOUNT,
+            AuditEvent::KeygenFailed {
+                error: "test".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(record.sequence, 4);
+}
+
+#[test]
+fn interrupted_record_is_dropped_by_the_next_append() {
+    let (log, path) = log_with_records("interrupted");
+    let contents = fs::read_to_string(&path).unwrap();
+    let last_line = contents.trim_end().rfind('\n').unwrap() + 1;
+    // a crash while writing leaves half of the last record without its newline
+    fs::write(&path, &contents[..last_line + 40]).unwrap();
+    assert_eq!(
+        log.verify().unwrap_err().to_string(),
+        "The audit log has unreadable records on lines 3."
+    );
+
+    let record = log
+        .append(
+            ACCOUNT,
+            AuditEvent::KeygenFailed {
+                error: "test".to_string(),
+            },
+        )
+        .unwrap();
+    let records = log.records().unwrap().records;
+    assert_eq!(record.sequence, 2);
+    assert_eq!(record.previous_hash, records[1].hash);
+    assert_eq!(records.last(), Some(&record));
+    assert_eq!(log.verify().unwrap(), 3);
+}
+
+#[test]
+fn exported_log_has_every_record() {
+    let (log, path) = log_with_records("export");
+    let export = path.with_file_name("export.json");
+    let read = |export: &PathBuf| -> Vec<AuditRecord> {
+        serde_json::from_str(&fs::read_to_string(export).unwrap()).unwrap()
+    };
+    assert!(log.export(export.to_str().unwrap()).unwrap().is_empty());
+    assert_eq!(read(&export), log.records().unwrap().records);
+    assert_eq!(read(&export).len(), 3);
+
+    // damaged records are left out of the export and reported
+    edit_lines(&path, |lines| lines[0] = "damaged".to_string());
+    assert_eq!(log.export(export.to_str().unwrap()).unwrap(), [1]);
+    assert_eq!(read(&export).len(), 2);
+}
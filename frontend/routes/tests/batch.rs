@@ -0,0 +1,96 @@
+//! Tests for the batch payouts.
+
+use routes::{nano_to_raw, PayoutBatch, PayoutStatus, MAX_PAYOUTS};
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const RECEIVER: &str = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+
+#[test]
+fn payouts_are_read_from_csv_files() {
+    let csv = format!(
+        "\u{feff}address,amount\r\n\
+         {ACCOUNT},1.5\r\n\
+         \r\n\
+         # the rent\r\n\
+         \"{RECEIVER}\" , \"0.000001\"\r\n"
+    );
+    let batch = PayoutBatch::parse(&csv).unwrap();
+    let rows: Vec<_> = batch
+        .payouts
+        .iter()
+        .map(|payout| {
+            (
+                payout.line,
+                payout.receivers_account.as_str(),
+                payout.amount.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(rows, [(2, ACCOUNT, "1.5"), (5, RECEIVER, "0.000001")]);
+
+    // without a header the first row is a payout
+    let batch = PayoutBatch::parse(&format!("{RECEIVER},2")).unwrap();
+    assert_eq!(batch.payouts[0].line, 1);
+
+    // only the first line can be a header, so invalid rows after it are kept and reported
+    let batch = PayoutBatch::parse(&format!(
+        "# payouts\naddress,amount\nrent,due\n{RECEIVER},2"
+    ))
+    .unwrap();
+    let lines: Vec<_> = batch.payouts.iter().map(|payout| payout.line).collect();
+    assert_eq!(lines, [2, 3, 4]);
+    let errors = batch.validate(nano_to_raw("3").unwrap());
+    assert_eq!(errors.len(), 4);
+    assert!(errors[0].starts_with("Line 2:"));
+    assert!(errors[3].starts_with("Line 3:"));
+
+    for invalid in [
+        "address,amount\n".to_string(),
+        format!("{ACCOUNT},1,rent"),
+        format!("\"{ACCOUNT},1"),
+        format!("{ACCOUNT},1\n").repeat(MAX_PAYOUTS + 1),
+    ] {
+        assert!(PayoutBatch::parse(&invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn batches_are_checked_against_the_balance() {
+    let batch = PayoutBatch::parse(&format!("{ACCOUNT},1.5\n{RECEIVER},0.5")).unwrap();
+    let balance = nano_to_raw("3").unwrap();
+    assert_eq!(batch.total(), nano_to_raw("2"));
+    assert_eq!(batch.balance_after(balance), nano_to_raw("1"));
+    assert!(batch.validate(balance).is_empty());
+
+    let short = nano_to_raw("1.9").unwrap();
+    assert_eq!(batch.balance_after(short), None);
+    assert_eq!(
+        batch.validate(short),
+        ["The payouts add up to 2 Nano but the balance is 1.9 Nano."]
+    );
+
+    // every row with a problem is reported
+    let invalid = PayoutBatch::parse(&format!(
+        "{ACCOUNT},1\nnano_1invalid,0\n{RECEIVER},1.5.0\n{RECEIVER},0"
+    ))
+    .unwrap();
+    assert_eq!(invalid.total(), None);
+    let errors = invalid.validate(balance);
+    assert_eq!(errors.len(), 4);
+    assert!(errors[0].starts_with("Line 2:"));
+    assert!(errors[3].starts_with("Line 4:"));
+
+    // the rows of a batch are compared by account and amount in raw
+    let same =
+        PayoutBatch::parse(&format!("address,amount\n{ACCOUNT},1.50\n{RECEIVER},.5")).unwrap();
+    assert!(batch.pays_the_same(&same.payouts));
+    assert!(!batch.pays_the_same(&same.payouts[..1]));
+    let reordered = PayoutBatch::parse(&format!("{RECEIVER},0.5\n{ACCOUNT},1.5")).unwrap();
+    assert!(!batch.pays_the_same(&reordered.payouts));
+
+    assert_eq!(PayoutStatus::NotSent.name(), "Not sent");
+    assert_eq!(
+        PayoutStatus::Failed("The node is unreachable.".to_string()).error(),
+        Some("The node is unreachable.")
+    );
+}
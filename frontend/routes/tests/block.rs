@@ -0,0 +1,108 @@
+//! Tests for the state blocks and the amounts written in Nano.
+
+use routes::{account_to_public_key, nano_to_raw, raw_to_nano, StateBlock, RAW_PER_NANO};
+use serde_json::json;
+
+/// Function that returns a send block published on the live network.
+fn live_block() -> StateBlock {
+    StateBlock::from_json(&json!({
+        "type": "state",
+        "account": "nano_1qato4k7z3spc8gq1zyd8xeqfbzsoxwo36a45ozbrxcatut7up8ohyardu1z",
+        "previous": "6CDDA48608C7843A0AC1122BDD46D9E20E21190986B19EAC23E7F33F2E6A6766",
+        "representative": "nano_3pczxuorp48td8645bs3m6c3xotxd3idskrenmi65rbrga5zmkemzhwkaznh",
+        "balance": "40200000001000000000000000000000000",
+        "link": "87434F8041869A01C8F6F263B87972D7BA443A72E0A97D7A3FD0CCC2358FD6F9",
+        "signature": "A5DB164F6B81648F914E49CAB533900C389FAAD64FBB24F6902F9261312B29F730D07E9BCCD21D918301419B4E05B181637CF8419ED4DCBF8EF2539EB2467F07",
+        "work": "000bc55b014e807d"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn blocks_are_hashed_and_verified_like_the_network() {
+    let block = live_block();
+    let account = account_to_public_key(&block.account).unwrap();
+    assert_eq!(
+        block.hash_hex().unwrap(),
+        "E2FB233EF4554077A7BF1AA85851D5BF0B36965D2B0FB504B2BC778AB89917D3"
+    );
+    assert!(block.verify_signature(&account).is_ok());
+
+    // the link can be written as the account it sends to
+    let link_as_account = StateBlock {
+        link: "nano_33t5by1653nt196hfwm5q3wq7oxtaix97r7bhox5zn8eratrzoqsny49ftsd".to_string(),
+        ..block.clone()
+    };
+    assert_eq!(
+        link_as_account.hash_hex().unwrap(),
+        block.hash_hex().unwrap()
+    );
+
+    // any change to the block or the key breaks the signature
+    let tampered = StateBlock {
+        balance: block.balance - 1,
+        ..block.clone()
+    };
+    assert!(tampered.verify_signature(&account).is_err());
+    let other_key = account_to_public_key(&block.representative).unwrap();
+    assert!(block.verify_signature(&other_key).is_err());
+    let unsigned = StateBlock {
+        signature: None,
+        ..block.clone()
+    };
+    assert!(unsigned.verify_signature(&account).is_err());
+
+    let invalid = StateBlock {
+        previous: "not a hash".to_string(),
+        ..block
+    };
+    assert!(invalid.hash().is_err());
+}
+
+#[test]
+fn amounts_are_converted_between_nano_and_raw() {
+    assert_eq!(nano_to_raw("1"), Some(RAW_PER_NANO));
+    assert_eq!(nano_to_raw(" 1.5 "), Some(RAW_PER_NANO * 3 / 2));
+    assert_eq!(nano_to_raw(".5"), Some(RAW_PER_NANO / 2));
+    assert_eq!(nano_to_raw("2."), Some(2 * RAW_PER_NANO));
+    assert_eq!(nano_to_raw("0.000000000000000000000000000001"), Some(1));
+    assert_eq!(
+        nano_to_raw("340282366.920938463463374607431768211455"),
+        Some(u128::MAX)
+    );
+
+    for invalid in [
+        "",
+        ".",
+        "-1",
+        "+1",
+        "1e3",
+        "1.2.3",
+        "1,5",
+        // more decimals than raw and more raw than exists
+        "0.0000000000000000000000000000001",
+        "340282366.920938463463374607431768211456",
+        "340282367",
+    ] {
+        assert_eq!(nano_to_raw(invalid), None, "{invalid}");
+    }
+
+    assert_eq!(raw_to_nano(0), "0");
+    assert_eq!(raw_to_nano(1), "0.000000000000000000000000000001");
+    assert_eq!(raw_to_nano(RAW_PER_NANO * 3 / 2), "1.5");
+    assert_eq!(
+        raw_to_nano(u128::MAX),
+        "340282366.920938463463374607431768211455"
+    );
+    for raw in [
+        0,
+        1,
+        10,
+        RAW_PER_NANO - 1,
+        RAW_PER_NANO,
+        123_456 * RAW_PER_NANO / 1000,
+        u128::MAX,
+    ] {
+        assert_eq!(nano_to_raw(&raw_to_nano(raw)), Some(raw), "{raw}");
+    }
+}
@@ -0,0 +1,12 @@
+//! This file contains the helpers shared by the integration tests.
+
+use std::{fs, path::PathBuf};
+
+/// Function that returns the path of a file that doesn't exist yet, in a directory only used by the given test.
+pub fn temp_path(prefix: &str, file: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("routes-{prefix}-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join(file);
+    let _ = fs::remove_file(&path);
+    path
+}
@@ -0,0 +1,59 @@
+//! Tests for the signing journal.
+
+mod common;
+
+use common::temp_path;
+use routes::{AccountState, SigningJournal, StateBlock, FRONTIER_LOCKED_ERROR};
+use std::fs;
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const RECEIVER: &str = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+
+/// Function that builds a block sending `amount` raw from the same frontier.
+fn send(amount: u128) -> StateBlock {
+    let state = AccountState {
+        account: ACCOUNT.to_string(),
+        frontier: Some("AB".repeat(32)),
+        balance: 1_000,
+        representative: ACCOUNT.to_string(),
+    };
+    StateBlock::send(&state, RECEIVER, amount).unwrap()
+}
+
+#[test]
+fn frontier_is_locked_across_processes() {
+    let path = temp_path("journal-shared", "journal.json");
+    let path = path.to_str().unwrap();
+    // each handle stands for a process (the desktop application and the daemon)
+    let application = SigningJournal::open(path).unwrap();
+    let daemon = SigningJournal::open(path).unwrap();
+
+    let hash = application.lock(&send(10), 10).unwrap();
+    assert_eq!(daemon.lock(&send(10), 10).unwrap(), hash);
+    assert_eq!(
+        daemon.lock(&send(20), 20).unwrap_err().to_string(),
+        FRONTIER_LOCKED_ERROR
+    );
+    assert_eq!(daemon.sent_since(ACCOUNT, 0).unwrap(), 10);
+
+    // a failed session unlocks the frontier for every process, a signed block keeps it locked
+    daemon.release(&hash).unwrap();
+    let other = application.lock(&send(20), 20).unwrap();
+    application.mark_signed(&other).unwrap();
+    daemon.release(&other).unwrap();
+    assert!(daemon.lock(&send(10), 10).is_err());
+    assert_eq!(SigningJournal::open(path).unwrap().entries().len(), 1);
+}
+
+#[test]
+fn unreadable_journal_is_an_error() {
+    let path = temp_path("journal-corrupt", "journal.json");
+    fs::write(&path, "[{\"hash\": ").unwrap();
+    let path = path.to_str().unwrap();
+
+    assert!(SigningJournal::open(path).is_err());
+    let journal = SigningJournal::at(path);
+    assert!(journal.lock(&send(10), 10).is_err());
+    assert!(journal.sent_since(ACCOUNT, 0).is_err());
+    assert_eq!(fs::read_to_string(path).unwrap(), "[{\"hash\": ");
+}
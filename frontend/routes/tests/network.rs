@@ -0,0 +1,63 @@
+//! Tests for the network profiles.
+
+use routes::{
+    account_to_public_key, validate_work, CpuWorkGenerator, Network, NetworkSettings, WorkSource,
+    WorkThresholds,
+};
+
+const GENESIS: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+#[test]
+fn accounts_use_the_prefix_of_the_network() {
+    let public_key = account_to_public_key(GENESIS).unwrap();
+
+    let live = NetworkSettings::default();
+    assert_eq!(live.account(&public_key), GENESIS);
+    assert!(live.is_account(GENESIS));
+    assert!(live.is_account(&GENESIS.replacen("nano_", "xrb_", 1)));
+
+    let dev = NetworkSettings {
+        network: Network::Dev,
+        prefix: "test".to_string(),
+    };
+    let account = dev.account(&public_key);
+    assert_eq!(account, GENESIS.replacen("nano_", "test_", 1));
+    assert_eq!(account_to_public_key(&account), Some(public_key));
+    assert!(dev.is_account(&account));
+    assert!(!dev.is_account(GENESIS));
+    assert!(!live.is_account(&account));
+
+    assert_eq!(
+        account_to_public_key(&GENESIS.replacen("nano_", "Test_", 1)),
+        None
+    );
+}
+
+#[test]
+fn networks_are_found_by_the_name_the_node_reports() {
+    for network in Network::ALL {
+        assert_eq!(Network::from_name(network.name()), Some(network));
+    }
+    assert_eq!(Network::from_name("LIVE"), Some(Network::Live));
+    assert_eq!(Network::from_name("main"), None);
+
+    assert_eq!(Network::Live.banner(), None);
+    assert!(Network::Beta.banner().unwrap().starts_with("BETA NETWORK"));
+    assert_eq!(Network::Test.work_thresholds(), WorkThresholds::LIVE);
+    assert_eq!(
+        Network::Dev.work_thresholds().for_subtype("open"),
+        0xf000000000000000
+    );
+}
+
+#[tokio::test]
+async fn work_is_generated_for_the_dev_thresholds() {
+    let root = "991CF190094C00F0B68E2E5F75F6BEE95A2E0BD93CEAA4A6734DB9F19B728948";
+    let thresholds = Network::Dev.work_thresholds();
+
+    let work = CpuWorkGenerator::new(1)
+        .generate(root, thresholds.send)
+        .await
+        .unwrap();
+    assert!(validate_work(root, &work, thresholds.send));
+}
@@ -0,0 +1,56 @@
+//! Tests for the outbox.
+
+mod common;
+
+use common::temp_path;
+use routes::{AccountState, Outbox, OutboxStatus, StateBlock};
+use std::fs;
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const RECEIVER: &str = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+
+/// Function that builds a block sending `amount` raw.
+fn send(amount: u128) -> StateBlock {
+    let state = AccountState {
+        account: ACCOUNT.to_string(),
+        frontier: Some("CD".repeat(32)),
+        balance: 1_000,
+        representative: ACCOUNT.to_string(),
+    };
+    StateBlock::send(&state, RECEIVER, amount).unwrap()
+}
+
+#[test]
+fn blocks_added_by_any_process_are_kept() {
+    let path = temp_path("outbox-shared", "outbox.json");
+    let path = path.to_str().unwrap();
+    // each handle stands for a process (the desktop application and the daemon)
+    let application = Outbox::open(path).unwrap();
+    let daemon = Outbox::open(path).unwrap();
+
+    let first = application.add("send", &send(10)).unwrap();
+    let second = daemon.add("send", &send(20)).unwrap();
+    application
+        .record_attempt(&first, Err("Fork".to_string()))
+        .unwrap();
+
+    let entries = Outbox::open(path).unwrap().entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries
+        .iter()
+        .any(|entry| entry.hash == first && entry.status == OutboxStatus::Fork));
+    assert!(entries
+        .iter()
+        .any(|entry| entry.hash == second && entry.status == OutboxStatus::PendingPublish));
+}
+
+#[test]
+fn unreadable_outbox_is_an_error() {
+    let path = temp_path("outbox-corrupt", "outbox.json");
+    fs::write(&path, "not json").unwrap();
+    let path = path.to_str().unwrap();
+
+    assert!(Outbox::open(path).is_err());
+    assert!(Outbox::at(path).add("send", &send(10)).is_err());
+    assert_eq!(fs::read_to_string(path).unwrap(), "not json");
+}
@@ -0,0 +1,121 @@
+//! Tests for the payment requests and their QR codes.
+
+use image::{GrayImage, ImageFormat, Luma};
+use qrcode::{Color, QrCode};
+use routes::{qr_code_svg, read_qr_code, Network, NetworkSettings, PaymentRequest};
+use std::io::Cursor;
+
+const GENESIS: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+/// Function that draws a QR code as a PNG image, like a screenshot of the one shown by another wallet.
+fn qr_code_png(text: &str) -> Vec<u8> {
+    let code = QrCode::new(text.as_bytes()).unwrap();
+    let (width, scale, border) = (code.width() as u32, 8, 4);
+    let colors = code.to_colors();
+    let size = (width + 2 * border) * scale;
+    let image = GrayImage::from_fn(size, size, |x, y| {
+        let (x, y) = (
+            (x / scale) as i64 - border as i64,
+            (y / scale) as i64 - border as i64,
+        );
+        let inside = (0..width as i64).contains(&x) && (0..width as i64).contains(&y);
+        match inside && colors[(y * width as i64 + x) as usize] == Color::Dark {
+            true => Luma([0]),
+            false => Luma([255]),
+        }
+    });
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    png
+}
+
+#[test]
+fn payment_requests_are_written_and_read_as_uris() {
+    let live = NetworkSettings::default();
+    let request = PaymentRequest {
+        amount: Some(1_000_000_000_000_000_000_000_000_000_000),
+        ..PaymentRequest::new(GENESIS)
+    };
+    let uri = request.to_uri();
+    assert_eq!(
+        uri,
+        format!("nano:{GENESIS}?amount=1000000000000000000000000000000")
+    );
+    assert_eq!(PaymentRequest::parse(&uri, &live), Ok(request));
+
+    let account = PaymentRequest::parse(GENESIS, &live).unwrap();
+    assert_eq!(account, PaymentRequest::new(GENESIS));
+    assert_eq!(account.to_uri(), format!("nano:{GENESIS}"));
+
+    let invoice = PaymentRequest {
+        amount: Some(u128::MAX),
+        label: Some("Café & Co".to_string()),
+        message: Some("Invoice #12 = 100%".to_string()),
+        ..PaymentRequest::new(GENESIS)
+    };
+    let uri = invoice.to_uri();
+    assert!(uri.ends_with("&label=Caf%C3%A9%20%26%20Co&message=Invoice%20%2312%20%3D%20100%25"));
+    assert_eq!(PaymentRequest::parse(&uri, &live), Ok(invoice.clone()));
+    assert_eq!(
+        invoice.amount_in_nano(),
+        Some("340282366.920938463463374607431768211455".to_string())
+    );
+    assert_eq!(
+        invoice.description(),
+        Some("Café & Co: Invoice #12 = 100%".to_string())
+    );
+}
+
+#[test]
+fn invalid_payment_requests_are_rejected() {
+    let live = NetworkSettings::default();
+    // other wallets write the scheme in capitals or with slashes and add their own parameters
+    let request =
+        PaymentRequest::parse(&format!("NANO://{GENESIS}?amount=1&source=app"), &live).unwrap();
+    assert_eq!(request.amount, Some(1));
+
+    for uri in [
+        "nano:nano_1invalid".to_string(),
+        format!("bitcoin:{GENESIS}"),
+        format!("nano:{GENESIS}?amount=1.5"),
+        format!("nano:{GENESIS}?amount=+1"),
+        format!("nano:{GENESIS}?amount="),
+        format!("nano:{GENESIS}?amount=340282366920938463463374607431768211456"),
+        format!("nano:{GENESIS}?amount=1&amount=2"),
+        format!("nano:{GENESIS}?label=%ZZ"),
+        format!("nano:{GENESIS}?message=%FF"),
+    ] {
+        assert!(PaymentRequest::parse(&uri, &live).is_err(), "{uri}");
+    }
+
+    // the accounts of a network with its own prefix are only read on that network
+    let test = NetworkSettings {
+        network: Network::Test,
+        prefix: "test".to_string(),
+    };
+    let account = GENESIS.replacen("nano_", "test_", 1);
+    let uri = format!("nano:{account}?amount=1");
+    assert_eq!(PaymentRequest::parse(&uri, &test).unwrap().account, account);
+    assert!(PaymentRequest::parse(&uri, &live).is_err());
+    assert!(PaymentRequest::parse(GENESIS, &test).is_err());
+}
+
+#[test]
+fn qr_codes_are_read_from_images() {
+    let uri = format!("nano:{GENESIS}?amount=1");
+    assert_eq!(read_qr_code(&qr_code_png(&uri)), Ok(uri.clone()));
+    assert!(qr_code_svg(&uri).unwrap().starts_with("<?xml"));
+
+    assert!(read_qr_code(b"not an image").is_err());
+    let mut blank = Vec::new();
+    GrayImage::from_pixel(64, 64, Luma([255]))
+        .write_to(&mut Cursor::new(&mut blank), ImageFormat::Png)
+        .unwrap();
+    assert_eq!(
+        read_qr_code(&blank),
+        Err("There's no QR code in the image.".to_string())
+    );
+}
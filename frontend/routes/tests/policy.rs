@@ -0,0 +1,191 @@
+//! Tests for the spending policies.
+
+use routes::{
+    nano_to_raw, AccountState, SpendingHistory, SpendingPolicy, StateBlock, TimeWindow, DAY,
+};
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const RECEIVER: &str = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+
+/// Function that returns the time (seconds since the unix epoch) of the given hour of a day.
+fn at_hour(hour: u64) -> u64 {
+    100 * DAY + hour * 3600
+}
+
+/// Function that returns the state of an open account with 10 Nano.
+fn state() -> AccountState {
+    AccountState {
+        account: ACCOUNT.to_string(),
+        frontier: Some("AB".repeat(32)),
+        balance: nano_to_raw("10").unwrap(),
+        representative: ACCOUNT.to_string(),
+    }
+}
+
+/// Function that builds a block sending `amount` Nano to an account.
+fn send(receiver: &str, amount: &str) -> StateBlock {
+    StateBlock::send(&state(), receiver, nano_to_raw(amount).unwrap()).unwrap()
+}
+
+#[test]
+fn limits_count_the_blocks_sent_before() {
+    let policy = SpendingPolicy {
+        max_per_transaction: Some("2".to_string()),
+        daily_limit: Some("3".to_string()),
+        weekly_limit: Some("5".to_string()),
+        ..SpendingPolicy::default()
+    };
+    let history = |last_day: &str, last_week: &str| SpendingHistory {
+        last_day: nano_to_raw(last_day).unwrap(),
+        last_week: nano_to_raw(last_week).unwrap(),
+    };
+    let check = |amount: &str, history: SpendingHistory| {
+        policy.check(&state(), &send(RECEIVER, amount), history, at_hour(12))
+    };
+
+    assert!(check("2", history("1", "3")).is_ok());
+    assert_eq!(
+        check("2.5", SpendingHistory::default()).unwrap_err(),
+        "The policy allows sending at most 2 XNO per transaction (2.5 XNO with this transaction)."
+    );
+    assert!(check("2", history("1.5", "1.5"))
+        .unwrap_err()
+        .contains("per day"));
+    assert!(check("1", history("0", "4.5"))
+        .unwrap_err()
+        .contains("per week"));
+
+    // receiving isn't limited and an empty policy allows everything
+    let receive = StateBlock::receive(&state(), &"CD".repeat(32), nano_to_raw("100").unwrap());
+    assert!(policy
+        .check(&state(), &receive.unwrap(), history("3", "5"), at_hour(12))
+        .is_ok());
+    assert!(SpendingPolicy::default()
+        .check(&state(), &send(RECEIVER, "10"), history("100", "100"), 0)
+        .is_ok());
+
+    let invalid = SpendingPolicy {
+        daily_limit: Some("a lot".to_string()),
+        ..SpendingPolicy::default()
+    };
+    assert!(invalid
+        .check(
+            &state(),
+            &send(RECEIVER, "1"),
+            SpendingHistory::default(),
+            0
+        )
+        .is_err());
+}
+
+#[test]
+fn time_windows_allow_their_hours_only() {
+    let check = |start_hour: u8, end_hour: u8, hour: u64| {
+        let policy = SpendingPolicy {
+            time_window: Some(TimeWindow {
+                start_hour,
+                end_hour,
+            }),
+            ..SpendingPolicy::default()
+        };
+        policy
+            .check(
+                &state(),
+                &send(RECEIVER, "1"),
+                SpendingHistory::default(),
+                at_hour(hour),
+            )
+            .is_ok()
+    };
+
+    // the end hour is left out
+    assert!(check(9, 17, 9));
+    assert!(check(9, 17, 16));
+    assert!(!check(9, 17, 17));
+    assert!(!check(9, 17, 3));
+
+    // a window through midnight
+    assert!(check(22, 6, 23));
+    assert!(check(22, 6, 0));
+    assert!(check(22, 6, 5));
+    assert!(!check(22, 6, 6));
+    assert!(!check(22, 6, 12));
+
+    // the same start and end is the whole day
+    for hour in 0..24 {
+        assert!(check(8, 8, hour), "{hour}");
+    }
+
+    // there's no hour 24
+    assert!(!check(0, 24, 12));
+    assert!(TimeWindow {
+        start_hour: 0,
+        end_hour: 24
+    }
+    .validate()
+    .is_err());
+    assert!(TimeWindow {
+        start_hour: 23,
+        end_hour: 0
+    }
+    .validate()
+    .is_ok());
+}
+
+#[test]
+fn only_allowed_recipients_receive_nano() {
+    let policy = SpendingPolicy {
+        // the prefix of the allowed accounts doesn't matter
+        allowed_recipients: vec![RECEIVER.replacen("nano_", "xrb_", 1)],
+        ..SpendingPolicy::default()
+    };
+    let check = |receiver: &str| {
+        policy.check(
+            &state(),
+            &send(receiver, "1"),
+            SpendingHistory::default(),
+            0,
+        )
+    };
+
+    assert!(check(RECEIVER).is_ok());
+    assert_eq!(
+        check(ACCOUNT).unwrap_err(),
+        format!("The policy doesn't allow sending to {ACCOUNT}.")
+    );
+}
+
+#[test]
+fn representative_changes_can_be_forbidden() {
+    let policy = SpendingPolicy {
+        forbid_representative_change: true,
+        ..SpendingPolicy::default()
+    };
+    let change = StateBlock::change(&state(), RECEIVER).unwrap();
+    assert_eq!(
+        policy
+            .check(&state(), &change, SpendingHistory::default(), 0)
+            .unwrap_err(),
+        "The policy forbids changing the representative."
+    );
+
+    // a send keeping the representative is fine, and so is the first representative of a new account
+    assert!(policy
+        .check(
+            &state(),
+            &send(RECEIVER, "1"),
+            SpendingHistory::default(),
+            0
+        )
+        .is_ok());
+    let unopened = AccountState {
+        frontier: None,
+        balance: 0,
+        representative: RECEIVER.to_string(),
+        ..state()
+    };
+    let open = StateBlock::receive(&unopened, &"CD".repeat(32), 1).unwrap();
+    assert!(policy
+        .check(&unopened, &open, SpendingHistory::default(), 0)
+        .is_ok());
+}
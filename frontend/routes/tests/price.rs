@@ -0,0 +1,102 @@
+//! Tests for the price providers and how their prices are combined.
+
+use routes::{
+    median, FirstAvailablePrice, MedianPrice, Offline, PriceFuture, PriceMode, PriceProvider,
+    PriceSettings, PriceSource,
+};
+
+/// Struct that answers with a fixed price, or fails if it has none.
+struct FixedPrice(Option<f64>);
+
+impl PriceProvider for FixedPrice {
+    fn name(&self) -> &'static str {
+        "Fixed"
+    }
+
+    fn get_price<'a>(&'a self, _currency: &'a str) -> PriceFuture<'a> {
+        Box::pin(async move { self.0.ok_or_else(|| "No price.".into()) })
+    }
+}
+
+/// Function that builds the providers answering with the given prices.
+fn providers(prices: &[Option<f64>]) -> Vec<Box<dyn PriceProvider>> {
+    prices
+        .iter()
+        .map(|price| Box::new(FixedPrice(*price)) as Box<dyn PriceProvider>)
+        .collect()
+}
+
+#[test]
+fn median_of_odd_and_even_lists() {
+    assert_eq!(median(vec![3., 1., 2.]), Some(2.));
+    assert_eq!(median(vec![4., 1., 3., 2.]), Some(2.5));
+    assert_eq!(median(vec![7.]), Some(7.));
+    assert_eq!(median(Vec::new()), None);
+
+    // prices that aren't numbers are left out
+    assert_eq!(median(vec![f64::NAN, 1., f64::INFINITY, 3.]), Some(2.));
+    assert_eq!(median(vec![f64::NAN]), None);
+}
+
+#[tokio::test]
+async fn median_price_ignores_failing_providers() {
+    let price = |prices: &[Option<f64>]| MedianPrice {
+        providers: providers(prices),
+    };
+
+    assert_eq!(
+        price(&[Some(1.), None, Some(5.), Some(2.)])
+            .get_price("eur")
+            .await
+            .unwrap(),
+        2.
+    );
+    assert_eq!(
+        price(&[Some(1.), Some(2.), None])
+            .get_price("eur")
+            .await
+            .unwrap(),
+        1.5
+    );
+    assert_eq!(
+        price(&[None, None])
+            .get_price("eur")
+            .await
+            .unwrap_err()
+            .to_string(),
+        "None of the price providers answered."
+    );
+}
+
+#[tokio::test]
+async fn first_available_price_skips_failing_providers() {
+    let price = |prices: &[Option<f64>]| FirstAvailablePrice {
+        providers: providers(prices),
+    };
+
+    assert_eq!(
+        price(&[None, Some(3.), Some(4.)])
+            .get_price("eur")
+            .await
+            .unwrap(),
+        3.
+    );
+    assert!(price(&[None]).get_price("eur").await.is_err());
+    assert!(price(&[]).get_price("eur").await.is_err());
+    assert!(Offline.get_price("eur").await.is_err());
+}
+
+#[tokio::test]
+async fn settings_build_the_chosen_provider() {
+    let settings = PriceSettings {
+        sources: vec![PriceSource::Offline],
+        mode: PriceMode::Median,
+        currency: "usd".to_string(),
+    };
+    assert_eq!(settings.provider().name(), "Median");
+    assert!(routes::get_nano_price(&settings).await.is_none());
+
+    let default = PriceSettings::default();
+    assert_eq!(default.provider().name(), "First Available");
+    assert_eq!(default.currency, "eur");
+}
@@ -0,0 +1,252 @@
+//! Tests for the typed Nano RPC calls using responses recorded from the node's RPC (in `tests/fixtures`).
+
+mod common;
+
+use axum::{extract::State, routing::post, Json, Router};
+use common::temp_path;
+use routes::{
+    check_node, generate_work, get_account_frontier, get_account_representative,
+    get_accounts_balances, get_accounts_frontiers, get_block_count, get_blocks_info,
+    get_confirmation_quorum, get_receivable, get_representatives_online, get_telemetry,
+    process_block, validate_account_number, Outbox, RelayOptions, RpcRelay, StateBlock, WorkCache,
+    WorkThresholds,
+};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+const GENESIS: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const BLOCK: &str = "87434F8041869A01C8F6F263B87972D7BA443A72E0A97D7A3FD0CCC2358FD6F9";
+
+/// Function that reads a recorded response.
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Function that starts a stub of the RPC that answers each action with its fixture (or always with `error`).
+/// The requests it receives are kept so the tests can check them.
+async fn start_stub(always: Option<&'static str>) -> (String, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let router = Router::new()
+        .route(
+            "/",
+            post(
+                move |State(requests): State<Arc<Mutex<Vec<Value>>>>,
+                      Json(request): Json<Value>| async move {
+                    let action = request["action"].as_str().unwrap_or_default().to_string();
+                    requests.lock().unwrap().push(request);
+                    Json(fixture(always.unwrap_or(&action)))
+                },
+            ),
+        )
+        .with_state(requests.clone());
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    (format!("http://{address}"), requests)
+}
+
+#[tokio::test]
+async fn accounts_balances_and_frontiers_are_parsed() {
+    let (url, requests) = start_stub(None).await;
+    let accounts = vec![GENESIS.to_string()];
+
+    let balances = get_accounts_balances(&url, "key", &accounts).await.unwrap();
+    assert_eq!(
+        balances.balances[GENESIS].balance,
+        "325586539664609129644855132177"
+    );
+    assert_eq!(
+        balances.balances[GENESIS].receivable,
+        "2309372032769300000000000000000000"
+    );
+    assert_eq!(balances.errors.len(), 1);
+
+    let frontiers = get_accounts_frontiers(&url, "key", &accounts)
+        .await
+        .unwrap();
+    assert_eq!(
+        frontiers.frontiers[GENESIS],
+        "791AF413173EEE674A6FCF633B5DFC0F3C33F397F0DA08E987D9E0741D40D81A"
+    );
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0]["accounts"][0], GENESIS);
+    assert_eq!(requests[0]["key"], "key");
+    assert_eq!(requests[1]["action"], "accounts_frontiers");
+}
+
+#[tokio::test]
+async fn blocks_info_contains_the_block() {
+    let (url, requests) = start_stub(None).await;
+
+    let blocks = get_blocks_info(&url, "", &[BLOCK.to_string()])
+        .await
+        .unwrap();
+    let info = &blocks.blocks[BLOCK];
+    assert_eq!(info.subtype.as_deref(), Some("send"));
+    assert_eq!(info.height.as_deref(), Some("58"));
+    assert_eq!(info.confirmed.as_deref(), Some("true"));
+    let contents = info.contents.as_ref().unwrap();
+    assert_eq!(contents.content_type.as_deref(), Some("state"));
+    assert_eq!(contents.account, info.block_account);
+    assert_eq!(blocks.blocks_not_found.len(), 1);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0]["json_block"], "true");
+    assert!(requests[0].get("key").is_none());
+}
+
+#[tokio::test]
+async fn receivable_and_representatives_are_sorted() {
+    let (url, _) = start_stub(None).await;
+
+    let receivable = get_receivable(&url, "", GENESIS, 10)
+        .await
+        .unwrap()
+        .sorted();
+    assert_eq!(receivable.len(), 2);
+    assert!(receivable[0].0 < receivable[1].0);
+    assert_eq!(receivable[0].1.amount, "6000000000000000000000000000000");
+
+    let representatives = get_representatives_online(&url, "").await.unwrap();
+    assert_eq!(representatives.by_weight()[0].0, GENESIS);
+}
+
+#[tokio::test]
+async fn process_and_work_generate_return_their_results() {
+    let (url, requests) = start_stub(None).await;
+    let block =
+        StateBlock::from_json(&fixture("blocks_info")["blocks"][BLOCK]["contents"]).unwrap();
+
+    let hash = process_block(&url, "", "send", &block).await.unwrap();
+    assert_eq!(
+        hash,
+        "E2FB233EF4554077A7BF1AA85851D5BF0B36965D2B0FB504B2BC778AB89917D3"
+    );
+
+    let work = generate_work(&url, "", BLOCK, "fffffff800000000")
+        .await
+        .unwrap();
+    assert_eq!(work.work, "2b3d689bbcb21dca");
+    assert_eq!(work.difficulty.as_deref(), Some("fffffff93c41ec94"));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0]["subtype"], "send");
+    assert_eq!(requests[0]["block"]["account"], block.account);
+}
+
+#[tokio::test]
+async fn node_information_is_parsed() {
+    let (url, _) = start_stub(None).await;
+
+    let telemetry = get_telemetry(&url, "").await.unwrap();
+    assert_eq!(telemetry.block_count, "5777903");
+    assert_eq!(telemetry.major_version, "21");
+
+    let count = get_block_count(&url, "").await.unwrap();
+    assert_eq!(
+        (count.count.as_str(), count.cemented.as_str()),
+        ("1000", "25")
+    );
+
+    let quorum = get_confirmation_quorum(&url, "").await.unwrap();
+    assert_eq!(quorum.online_weight_quorum_percent, "50");
+
+    let representative = get_account_representative(&url, "", GENESIS).await.unwrap();
+    assert_eq!(
+        representative,
+        "nano_3hd4ezdgsp15iemx7h81in7xz5tpxi43b6b41zn3qmwiuypankocw3awes5k"
+    );
+
+    assert!(validate_account_number(&url, "", GENESIS).await.unwrap());
+}
+
+#[tokio::test]
+async fn node_errors_are_returned() {
+    let (url, _) = start_stub(Some("error")).await;
+
+    let error = get_account_representative(&url, "", GENESIS)
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Account not found");
+    assert!(get_telemetry(&url, "").await.is_err());
+}
+
+#[tokio::test]
+async fn node_check_verifies_the_network() {
+    let (url, requests) = start_stub(None).await;
+
+    let health = check_node(&url, "key", "live").await.unwrap();
+    assert_eq!(health.vendor, "Nano V27.1");
+    assert_eq!(health.network, "live");
+    assert_eq!(health.block_count, "1000");
+    assert!(health.warnings.is_empty());
+    let actions: Vec<Value> = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| request["action"].clone())
+        .collect();
+    assert_eq!(actions, ["version", "block_count", "accounts_frontiers"]);
+
+    let error = check_node(&url, "key", "beta").await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The node belongs to the live network instead of the beta network."
+    );
+
+    let (url, _) = start_stub(Some("error")).await;
+    assert!(check_node(&url, "key", "live").await.is_err());
+}
+
+#[tokio::test]
+async fn only_a_missing_account_is_unopened() {
+    let (url, requests) = start_stub(None).await;
+    assert_eq!(
+        get_account_frontier(&url, "key", GENESIS).await.unwrap(),
+        Some("791AF413173EEE674A6FCF633B5DFC0F3C33F397F0DA08E987D9E0741D40D81A".to_string())
+    );
+    assert_eq!(requests.lock().unwrap()[0]["action"], "account_info");
+
+    let (url, _) = start_stub(Some("error")).await;
+    assert_eq!(get_account_frontier(&url, "", GENESIS).await.unwrap(), None);
+
+    // any other error of the node isn't mistaken for an unopened account
+    let (url, _) = start_stub(Some("invalid_key")).await;
+    let error = get_account_frontier(&url, "", GENESIS).await.unwrap_err();
+    assert_eq!(error.to_string(), "Invalid API key");
+}
+
+#[tokio::test]
+async fn relay_only_forwards_on_its_secret_path() {
+    let (url, requests) = start_stub(None).await;
+    let outbox = temp_path("relay", "outbox.json");
+    let relay = RpcRelay::start(RelayOptions {
+        node_url: url,
+        node_key: "node key".to_string(),
+        work_source: None,
+        work_cache: WorkCache::default(),
+        thresholds: WorkThresholds::LIVE,
+        outbox: Outbox::at(outbox.to_str().unwrap()),
+    })
+    .await
+    .unwrap();
+
+    // the session's requests reach the node with its API key
+    let count = get_block_count(relay.url(), "").await.unwrap();
+    assert_eq!(count.count, "1000");
+    assert_eq!(requests.lock().unwrap()[0]["key"], "node key");
+
+    // any other path of the relay's port is refused before reaching the node
+    let (root, path) = relay.url().rsplit_once('/').unwrap();
+    assert_eq!(path.len(), 64);
+    assert!(get_block_count(root, "").await.is_err());
+    assert!(get_block_count(&format!("{root}/other"), "").await.is_err());
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
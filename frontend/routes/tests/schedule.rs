@@ -0,0 +1,151 @@
+//! Tests for the scheduled payments.
+
+mod common;
+
+use common::temp_path;
+use routes::{
+    format_timestamp, parse_timestamp, Cadence, PaymentSchedule, PaymentStatus, ScheduledPayment,
+    DAY,
+};
+
+const ACCOUNT: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+const RECEIVER: &str = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+
+/// Function that reads a time written as "YYYY-MM-DD HH:MM".
+fn time(text: &str) -> u64 {
+    parse_timestamp(text).unwrap()
+}
+
+#[test]
+fn times_are_written_and_read_as_dates() {
+    assert_eq!(parse_timestamp("1970-01-01"), Some(0));
+    assert_eq!(
+        format_timestamp(time("2024-02-29 13:05")),
+        "2024-02-29 13:05 UTC"
+    );
+    assert_eq!(
+        parse_timestamp("2024-02-29 13:05 UTC"),
+        parse_timestamp("2024-02-29T13:05")
+    );
+
+    for invalid in [
+        "2023-02-29",
+        "2024-13-01",
+        "2024-01-01 24:00",
+        "yesterday",
+        "1969-12-31",
+    ] {
+        assert_eq!(parse_timestamp(invalid), None, "{invalid}");
+    }
+}
+
+#[test]
+fn monthly_payments_stay_on_their_day_or_the_end_of_the_month() {
+    let payment = ScheduledPayment::new(
+        ACCOUNT,
+        RECEIVER,
+        "1.5",
+        "Rent",
+        Cadence::Monthly,
+        time("2024-01-31 09:00"),
+    );
+    let payments: Vec<String> = payment
+        .occurrences(time("2024-01-01"), time("2025-01-01"))
+        .into_iter()
+        .map(format_timestamp)
+        .collect();
+    assert_eq!(payments.len(), 12);
+    assert_eq!(
+        &payments[..3],
+        [
+            "2024-01-31 09:00 UTC",
+            "2024-02-29 09:00 UTC",
+            "2024-03-31 09:00 UTC"
+        ]
+    );
+    assert_eq!(payments[11], "2024-12-31 09:00 UTC");
+
+    let weekly = ScheduledPayment {
+        cadence: Cadence::Weekly,
+        ..payment.clone()
+    };
+    assert_eq!(
+        weekly.occurrences(time("2024-03-01"), time("2024-03-15")),
+        vec![time("2024-03-06 09:00"), time("2024-03-13 09:00")]
+    );
+
+    let once = ScheduledPayment {
+        cadence: Cadence::Once,
+        ..payment
+    };
+    assert_eq!(
+        once.occurrences(0, u64::MAX),
+        vec![time("2024-01-31 09:00")]
+    );
+}
+
+#[test]
+fn due_payments_are_paid_skipped_or_missed() {
+    let path = temp_path("schedule", "schedule.json");
+    let schedule = PaymentSchedule::open(path.to_str().unwrap()).unwrap();
+
+    let start = time("2024-05-01 12:00");
+    let payment = ScheduledPayment::new(ACCOUNT, RECEIVER, "2", "", Cadence::Daily, start);
+    let id = payment.id.clone();
+    schedule.add(payment).unwrap();
+    assert!(schedule
+        .add(ScheduledPayment::new(
+            ACCOUNT,
+            "nano_1invalid",
+            "0",
+            "",
+            Cadence::Daily,
+            start
+        ))
+        .is_err());
+
+    // three days later the first payment is the one brought up and the older ones are missed
+    let now = start + 2 * DAY + 60;
+    assert_eq!(schedule.due_payments(ACCOUNT, now)[0].1, start);
+    assert!(schedule.due_payments(RECEIVER, now).is_empty());
+    schedule.mark_paid(&id, start).unwrap();
+    schedule.skip(&id, start + DAY).unwrap();
+    assert_eq!(schedule.due_payments(ACCOUNT, now)[0].1, start + 2 * DAY);
+
+    let statuses: Vec<PaymentStatus> = schedule
+        .calendar(ACCOUNT, start - DAY, start + 4 * DAY, now + DAY)
+        .into_iter()
+        .map(|entry| entry.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            PaymentStatus::Paid,
+            PaymentStatus::Skipped,
+            PaymentStatus::Missed,
+            PaymentStatus::Due,
+        ]
+    );
+
+    // the schedule is kept in its file
+    let reopened = PaymentSchedule::open(path.to_str().unwrap()).unwrap();
+    assert_eq!(reopened.payments_for(ACCOUNT)[0].paid, vec![start]);
+    reopened.remove(&id).unwrap();
+    assert!(reopened.remove(&id).is_err());
+    assert!(PaymentSchedule::open(path.to_str().unwrap())
+        .unwrap()
+        .payments_for(ACCOUNT)
+        .is_empty());
+}
+
+#[test]
+fn unreadable_schedule_is_an_error() {
+    let path = temp_path("schedule-corrupt", "schedule.json");
+    std::fs::write(&path, "[{").unwrap();
+    let path = path.to_str().unwrap();
+
+    assert!(PaymentSchedule::open(path).is_err());
+    let payment = ScheduledPayment::new(ACCOUNT, RECEIVER, "2", "", Cadence::Daily, 0);
+    assert!(PaymentSchedule::at(path).add(payment).is_err());
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "[{");
+}
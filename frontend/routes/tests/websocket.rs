@@ -0,0 +1,100 @@
+//! Tests for the WebSocket client using a local stub of the node's WebSocket API.
+
+use futures::{SinkExt, StreamExt};
+use routes::{watch_account, AccountEvent, ConfirmationSubscription, WatchOptions};
+use serde_json::json;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+const ACCOUNT: &str = "nano_3b54jp7j8ewcdmwpg1fjrr9ke11tgcwdjsd1dif44arruk65bfy3qtrycnmh";
+
+/// Function that starts a stub that acknowledges the subscription, sends one confirmation and closes.
+async fn start_stub() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut stream = accept_async(socket).await.unwrap();
+
+        let subscription = match stream.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            message => panic!("unexpected message {message:?}"),
+        };
+        assert_eq!(subscription["action"], "subscribe");
+        assert_eq!(subscription["topic"], "confirmation");
+        assert_eq!(subscription["options"]["accounts"][0], ACCOUNT);
+
+        let messages = [
+            json!({ "ack": "subscribe", "time": "1" }),
+            json!({ "topic": "telemetry", "time": "2", "message": {} }),
+            json!({
+                "topic": "confirmation",
+                "time": "3",
+                "message": {
+                    "account": ACCOUNT,
+                    "amount": "1000000000000000000000000000000",
+                    "hash": "09D4FAAC675D6C7D1EADA5152565D6D238FA7CAD66BA3F2587415F71F68D841B",
+                    "confirmation_type": "active_quorum",
+                },
+            }),
+        ];
+        for message in messages {
+            stream
+                .send(Message::Text(message.to_string().into()))
+                .await
+                .unwrap();
+        }
+        stream.close(None).await.unwrap();
+    });
+
+    format!("ws://{address}")
+}
+
+#[tokio::test]
+async fn subscription_receives_confirmations() {
+    let url = start_stub().await;
+    let mut subscription = ConfirmationSubscription::connect(&url, &[ACCOUNT.to_string()])
+        .await
+        .unwrap();
+
+    let confirmation = subscription.next().await.unwrap().unwrap();
+    assert_eq!(confirmation.account, ACCOUNT);
+    assert_eq!(
+        confirmation.hash,
+        "09D4FAAC675D6C7D1EADA5152565D6D238FA7CAD66BA3F2587415F71F68D841B"
+    );
+    assert_eq!(confirmation.confirmation_type, "active_quorum");
+
+    assert!(subscription.next().await.is_none());
+}
+
+#[tokio::test]
+async fn watch_account_forwards_confirmations() {
+    let url = start_stub().await;
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let options = WatchOptions {
+        websocket_url: url,
+        rpc_url: "http://127.0.0.1:1".to_string(),
+        key: String::new(),
+        account: ACCOUNT.to_string(),
+        poll_interval: Duration::from_millis(50),
+        reconnect_interval: Duration::from_secs(60),
+    };
+    let watcher = tokio::spawn(watch_account(options, move |event| {
+        let _ = sender.send(event);
+    }));
+
+    let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    match event {
+        AccountEvent::Confirmation(confirmation) => assert_eq!(confirmation.account, ACCOUNT),
+        event => panic!("unexpected event {event:?}"),
+    }
+
+    watcher.abort();
+}
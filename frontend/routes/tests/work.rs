@@ -0,0 +1,97 @@
+//! Tests for the proof-of-work: difficulties, thresholds and the local generator.
+
+use routes::{
+    difficulty_multiplier, threshold_for_subtype, validate_work, work_difficulty, work_value,
+    CpuWorkGenerator, WorkCache, WorkSource, WorkThresholds, RECEIVE_THRESHOLD, SEND_THRESHOLD,
+};
+use std::time::Duration;
+
+/// Constant value for the root of the `work_validate` example of the node's documentation.
+const DOCS_ROOT: &str = "718CC2121C3E641059BC1C2CFC45666C99E8AE922F7A807B7D07B62C995D79E2";
+
+/// Constant value for the work of the `work_validate` example of the node's documentation.
+const DOCS_WORK: &str = "2bf29ef00786a6bc";
+
+/// Constant value for the previous block of a send published on the live network before epoch 2.
+const LIVE_ROOT: &str = "6CDDA48608C7843A0AC1122BDD46D9E20E21190986B19EAC23E7F33F2E6A6766";
+
+/// Constant value for the work of that send.
+const LIVE_WORK: &str = "000bc55b014e807d";
+
+#[test]
+fn difficulties_match_the_node() {
+    let root: [u8; 32] = hex::decode(DOCS_ROOT).unwrap().try_into().unwrap();
+    assert_eq!(work_value(&root, 0x2bf29ef00786a6bc), 0xffffffd21c3933f4);
+    assert_eq!(
+        work_difficulty(DOCS_ROOT, DOCS_WORK),
+        Some(0xffffffd21c3933f4)
+    );
+    assert_eq!(
+        work_difficulty(LIVE_ROOT, LIVE_WORK),
+        Some(0xffffffdfb55d8139)
+    );
+
+    // the root and the work must be well formed
+    assert_eq!(work_difficulty("AB", DOCS_WORK), None);
+    assert_eq!(work_difficulty(DOCS_ROOT, "not hex"), None);
+
+    let multiplier = difficulty_multiplier(0xffffffd21c3933f4, RECEIVE_THRESHOLD);
+    assert!((multiplier - 11.157).abs() < 0.001, "{multiplier}");
+}
+
+#[test]
+fn work_is_validated_against_the_threshold_of_the_block() {
+    // both works are from before epoch 2, so they only satisfy receive blocks now
+    for (root, work) in [(DOCS_ROOT, DOCS_WORK), (LIVE_ROOT, LIVE_WORK)] {
+        assert!(validate_work(root, work, RECEIVE_THRESHOLD), "{work}");
+        assert!(!validate_work(root, work, SEND_THRESHOLD), "{work}");
+    }
+
+    // the threshold itself is enough
+    assert!(validate_work(DOCS_ROOT, DOCS_WORK, 0xffffffd21c3933f4));
+    assert!(!validate_work(DOCS_ROOT, DOCS_WORK, 0xffffffd21c3933f5));
+    assert!(!validate_work(DOCS_ROOT, "nonce", 0));
+
+    let live = WorkThresholds::LIVE;
+    assert_eq!(WorkThresholds::default(), live);
+    assert_eq!(live.for_subtype("send"), SEND_THRESHOLD);
+    assert_eq!(live.for_subtype("CHANGE"), SEND_THRESHOLD);
+    assert_eq!(live.for_subtype("receive"), RECEIVE_THRESHOLD);
+    assert_eq!(threshold_for_subtype("open"), RECEIVE_THRESHOLD);
+}
+
+#[test]
+fn cached_work_is_only_used_when_it_is_enough() {
+    let cache = WorkCache::default();
+    cache.insert(&DOCS_ROOT.to_lowercase(), DOCS_WORK);
+
+    assert_eq!(
+        cache.get(DOCS_ROOT, RECEIVE_THRESHOLD),
+        Some(DOCS_WORK.to_string())
+    );
+    assert_eq!(cache.get(DOCS_ROOT, SEND_THRESHOLD), None);
+    assert_eq!(cache.get(LIVE_ROOT, RECEIVE_THRESHOLD), None);
+}
+
+#[tokio::test]
+async fn local_generation_can_be_cancelled() {
+    let generator = CpuWorkGenerator::new(1);
+    let root = "AB".repeat(32);
+
+    // no nonce reaches the highest difficulty in the time the test runs
+    let (result, _) = tokio::join!(
+        tokio::time::timeout(Duration::from_secs(30), generator.generate(&root, u64::MAX)),
+        async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            generator.cancel();
+        }
+    );
+    assert_eq!(
+        result.unwrap().unwrap_err().to_string(),
+        "The work generation was cancelled."
+    );
+
+    // the generator still works after a cancellation
+    let work = generator.generate(&root, 0xff00000000000000).await.unwrap();
+    assert!(validate_work(&root, &work, 0xff00000000000000));
+}
@@ -0,0 +1,492 @@
+//! This file contains the chains of blocks the group signs in one session (receiving every receivable block or paying
+//! a batch).
+//! The coordinator builds the whole chain before anything is signed and shares it with the other participants, who
+//! check it against their own node and refuse to sign unless they build the same blocks.
+//! frost-sig signs one block per run, so the coordinator then announces when the socket of each block is open and the
+//! participants join the round of that block only.
+
+use crate::{account_state, open_representative, Wallet, MAX_RECEIVABLE, SOCKET_DELAY};
+use axum::{extract::State, routing::get, Json, Router};
+use frost_sig::nano::sign::Subtype;
+use routes::{get_receivable, AccountState, Payout, PayoutBatch, StateBlock};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{net::TcpListener, task::JoinHandle, time::Instant};
+
+/// Constant value for how far above the signing port the coordinator shares the chain (the port right above it is
+/// the daemon's).
+const CHAIN_PORT_OFFSET: u32 = 2;
+
+/// Constant value for the time a participant waits for the coordinator to share the chain or open a round.
+const CHAIN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Constant value for the time between the requests a participant makes to follow the chain.
+const CHAIN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Constant value for the error returned when the chain built by a participant isn't the proposed one.
+pub const CHAIN_MISMATCH_ERROR: &str =
+    "The chain proposed by the coordinator doesn't match the one built by this wallet.";
+
+/// Struct that represents a receivable block received by a chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReceivableSource {
+    pub hash: String,
+
+    /// Amount received in raw.
+    pub amount: String,
+}
+
+/// Enum that represents what the blocks of a chain do.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainKind {
+    /// Receives the blocks in order, opening the account with `representative` if it wasn't opened yet.
+    Receive {
+        sources: Vec<ReceivableSource>,
+        representative: String,
+    },
+
+    /// Sends the payouts of a batch in order.
+    Send { payouts: Vec<Payout> },
+}
+
+/// Struct that represents the chain the coordinator proposes to the group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChainProposal {
+    pub account: String,
+
+    /// Frontier the chain is built on (`None` if the account wasn't opened yet).
+    pub frontier: Option<String>,
+
+    pub chain: ChainKind,
+
+    /// Hashes of the blocks of the chain, in the order they're signed.
+    pub hashes: Vec<String>,
+}
+
+/// Struct that represents a block of a chain and the state of the account it's built on (a block signed on its own is
+/// a chain of one).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainBlock {
+    pub hash: String,
+    pub block: StateBlock,
+    pub previous_state: AccountState,
+}
+
+/// Struct that represents what the coordinator shares while the chain is signed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct ChainAnnouncement {
+    proposal: ChainProposal,
+
+    /// Block of the chain whose socket is open (`None` before the first one).
+    round: Option<usize>,
+
+    /// Why the coordinator stopped signing the chain.
+    error: Option<String>,
+}
+
+/// Struct that represents the server the coordinator shares the chain with.
+pub struct ChainServer {
+    announcement: Arc<Mutex<ChainAnnouncement>>,
+    task: JoinHandle<()>,
+}
+
+/// Struct that represents the client a participant follows the chain of the coordinator with.
+pub struct ChainClient {
+    url: String,
+    client: reqwest::Client,
+}
+
+/// Enum that represents how this participant follows the rounds of a chain.
+pub enum ChainRounds {
+    /// Opens the socket of each round and announces it to the other participants.
+    Coordinator(ChainServer),
+
+    /// Waits for the coordinator to announce each round before joining it.
+    Participant(ChainClient),
+}
+
+impl ChainBlock {
+    /// Function that creates a block of a chain built on the given state of the account.
+    pub fn new(block: StateBlock, previous_state: AccountState) -> Result<Self, String> {
+        Ok(Self {
+            hash: block.hash_hex().map_err(|e| e.to_string())?,
+            block,
+            previous_state,
+        })
+    }
+
+    /// Function that returns the state of the account after the block.
+    fn next_state(&self) -> AccountState {
+        AccountState {
+            account: self.previous_state.account.clone(),
+            frontier: Some(self.hash.clone()),
+            balance: self.block.balance,
+            representative: self.block.representative.clone(),
+        }
+    }
+
+    /// Function that returns the subtype frost-sig signs the block with.
+    pub fn subtype(&self) -> Subtype {
+        match self.block.subtype(&self.previous_state) {
+            "open" => Subtype::OPEN,
+            "receive" => Subtype::RECEIVE,
+            "change" => Subtype::CHANGE,
+            _ => Subtype::SEND,
+        }
+    }
+
+    /// Function that returns the amount of raw the block moves.
+    pub fn amount(&self) -> u128 {
+        self.block.balance.abs_diff(self.previous_state.balance)
+    }
+}
+
+/// Function that builds the blocks of a chain, each one on the block before it, starting from the account's state.
+pub fn build_chain(mut state: AccountState, chain: &ChainKind) -> Result<Vec<ChainBlock>, String> {
+    let mut blocks = Vec::new();
+    let mut push = |state: AccountState, block: StateBlock| -> Result<AccountState, String> {
+        let chain_block = ChainBlock::new(block, state)?;
+        let next_state = chain_block.next_state();
+        blocks.push(chain_block);
+        Ok(next_state)
+    };
+
+    match chain {
+        ChainKind::Receive {
+            sources,
+            representative,
+        } => {
+            // the first block received by an unopened account opens it
+            if state.frontier.is_none() {
+                state.representative = representative.clone();
+            }
+            for source in sources {
+                let amount = source
+                    .amount
+                    .parse::<u128>()
+                    .map_err(|_| "The node returned an invalid amount.".to_string())?;
+                let block =
+                    StateBlock::receive(&state, &source.hash, amount).map_err(|e| e.to_string())?;
+                state = push(state, block)?;
+            }
+        }
+        ChainKind::Send { payouts } => {
+            for payout in payouts {
+                let amount = payout
+                    .raw()
+                    .ok_or_else(|| format!("\"{}\" is not a valid amount.", payout.amount))?;
+                let block = StateBlock::send(&state, &payout.receivers_account, amount)
+                    .map_err(|e| e.to_string())?;
+                state = push(state, block)?;
+            }
+        }
+    }
+
+    Ok(blocks)
+}
+
+impl ChainProposal {
+    /// Function that builds a chain on the account's state and creates its proposal, returning both.
+    pub fn new(state: &AccountState, chain: ChainKind) -> Result<(Self, Vec<ChainBlock>), String> {
+        let blocks = build_chain(state.clone(), &chain)?;
+        let proposal = Self {
+            account: state.account.clone(),
+            frontier: state.frontier.clone(),
+            chain,
+            hashes: blocks.iter().map(|block| block.hash.clone()).collect(),
+        };
+        Ok((proposal, blocks))
+    }
+
+    /// Function that builds the proposed chain on the account's state known by this participant, returning its blocks
+    /// only if they're the blocks the coordinator proposed.
+    pub fn verify(&self, state: &AccountState) -> Result<Vec<ChainBlock>, String> {
+        let (proposal, blocks) = Self::new(state, self.chain.clone())?;
+        match proposal == *self {
+            true => Ok(blocks),
+            false => Err(CHAIN_MISMATCH_ERROR.to_string()),
+        }
+    }
+}
+
+/// Function that returns the receivable blocks of the open account (up to `MAX_RECEIVABLE`) sorted by hash.
+async fn receivable_sources(wallet: &Wallet) -> Result<Vec<ReceivableSource>, String> {
+    let config = wallet.node();
+    let receivable = get_receivable(
+        &config.url,
+        &config.key,
+        &wallet.nano_account,
+        MAX_RECEIVABLE,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(receivable
+        .sorted()
+        .into_iter()
+        .map(|(hash, block)| ReceivableSource {
+            hash,
+            amount: block.amount,
+        })
+        .collect())
+}
+
+/// Function that builds the chain that receives every receivable block of the open account, as the coordinator.
+pub async fn propose_receive_chain(
+    wallet: &Wallet,
+) -> Result<(ChainProposal, Vec<ChainBlock>), String> {
+    let state = account_state(wallet).await?;
+    let sources = receivable_sources(wallet).await?;
+    if sources.is_empty() {
+        return Err("There are no receivable blocks.".to_string());
+    }
+
+    let representative = match state.frontier {
+        Some(_) => String::new(),
+        None => open_representative(wallet, "").await,
+    };
+    ChainProposal::new(
+        &state,
+        ChainKind::Receive {
+            sources,
+            representative,
+        },
+    )
+}
+
+/// Function that checks the chain of receive blocks proposed by the coordinator, returning its blocks.
+/// Every block received must be receivable according to this participant's node.
+pub async fn verify_receive_chain(
+    wallet: &Wallet,
+    proposal: &ChainProposal,
+) -> Result<Vec<ChainBlock>, String> {
+    let ChainKind::Receive {
+        sources,
+        representative,
+    } = &proposal.chain
+    else {
+        return Err("The coordinator proposed another kind of chain.".to_string());
+    };
+    if proposal.account != wallet.nano_account {
+        return Err("The coordinator proposed a chain for another account.".to_string());
+    }
+
+    let state = account_state(wallet).await?;
+    if state.frontier.is_none() && !wallet.settings.network.is_account(representative) {
+        return Err("The representative proposed for the account isn't valid.".to_string());
+    }
+    let receivable = receivable_sources(wallet).await?;
+    if let Some(source) = sources.iter().find(|source| !receivable.contains(source)) {
+        return Err(format!(
+            "The block {} isn't receivable according to your node.",
+            source.hash
+        ));
+    }
+
+    proposal.verify(&state)
+}
+
+/// Function that builds the chain that pays every row of the batch, as the coordinator.
+pub async fn propose_send_chain(
+    wallet: &Wallet,
+    batch: &PayoutBatch,
+) -> Result<(ChainProposal, Vec<ChainBlock>), String> {
+    let state = account_state(wallet).await?;
+    let errors = batch.validate(state.balance);
+    if !errors.is_empty() {
+        return Err(errors.join(" "));
+    }
+
+    ChainProposal::new(
+        &state,
+        ChainKind::Send {
+            payouts: batch.payouts.clone(),
+        },
+    )
+}
+
+/// Function that checks the chain of send blocks proposed by the coordinator, returning its blocks.
+/// The chain must pay the rows of this participant's own copy of the batch.
+pub async fn verify_send_chain(
+    wallet: &Wallet,
+    proposal: &ChainProposal,
+    batch: &PayoutBatch,
+) -> Result<Vec<ChainBlock>, String> {
+    let ChainKind::Send { payouts } = &proposal.chain else {
+        return Err("The coordinator proposed another kind of chain.".to_string());
+    };
+    if proposal.account != wallet.nano_account {
+        return Err("The coordinator proposed a chain for another account.".to_string());
+    }
+    if !batch.pays_the_same(payouts) {
+        return Err("The payouts proposed by the coordinator don't match your file.".to_string());
+    }
+
+    let state = account_state(wallet).await?;
+    let errors = batch.validate(state.balance);
+    if !errors.is_empty() {
+        return Err(errors.join(" "));
+    }
+    proposal.verify(&state)
+}
+
+impl ChainServer {
+    /// Function that starts sharing the proposed chain on all interfaces at the given port.
+    pub async fn start(port: u32, proposal: ChainProposal) -> Result<Self, String> {
+        let port = u16::try_from(port).map_err(|_| format!("{port} isn't a valid port."))?;
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .await
+            .map_err(|e| format!("The chain couldn't be shared on port {port}: {e}"))?;
+
+        let announcement = Arc::new(Mutex::new(ChainAnnouncement {
+            proposal,
+            round: None,
+            error: None,
+        }));
+        let router = Router::new()
+            .route("/", get(announce))
+            .with_state(announcement.clone());
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Ok(Self { announcement, task })
+    }
+
+    /// Function that changes what the coordinator shares.
+    fn update(&self, change: impl FnOnce(&mut ChainAnnouncement)) {
+        if let Ok(mut announcement) = self.announcement.lock() {
+            change(&mut announcement);
+        }
+    }
+}
+
+impl Drop for ChainServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Function that answers the participants following the chain.
+async fn announce(
+    State(announcement): State<Arc<Mutex<ChainAnnouncement>>>,
+) -> Json<Option<ChainAnnouncement>> {
+    Json(
+        announcement
+            .lock()
+            .ok()
+            .map(|announcement| announcement.clone()),
+    )
+}
+
+impl ChainClient {
+    /// Function that creates the client of the chain shared by the coordinator at the given address and port.
+    pub fn new(coordinator: &str, port: u32) -> Self {
+        Self {
+            url: format!("http://{coordinator}:{port}/"),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Function that asks the coordinator what it shares.
+    async fn announcement(&self) -> Result<ChainAnnouncement, String> {
+        self.client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .json::<Option<ChainAnnouncement>>()
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "The coordinator couldn't share the chain.".to_string())
+    }
+
+    /// Function that follows the chain of the coordinator until `done` accepts what it shares.
+    /// Before the coordinator is `reached` it may not be sharing the chain yet, after it an error ends the chain.
+    async fn wait_for(
+        &self,
+        mut reached: bool,
+        done: impl Fn(&ChainAnnouncement) -> bool,
+    ) -> Result<ChainAnnouncement, String> {
+        let deadline = Instant::now() + CHAIN_TIMEOUT;
+        loop {
+            match self.announcement().await {
+                Ok(announcement) => {
+                    reached = true;
+                    if let Some(error) = announcement.error {
+                        return Err(format!(
+                            "The coordinator stopped signing the chain: {error}"
+                        ));
+                    }
+                    if done(&announcement) {
+                        return Ok(announcement);
+                    }
+                }
+                Err(_) if reached => {
+                    return Err("The coordinator stopped sharing the chain.".to_string());
+                }
+                Err(_) => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err("The coordinator didn't share the chain in time.".to_string());
+            }
+            tokio::time::sleep(CHAIN_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Function that waits for the coordinator to share the chain it proposes.
+    pub async fn proposal(&self) -> Result<ChainProposal, String> {
+        Ok(self.wait_for(false, |_| true).await?.proposal)
+    }
+
+    /// Function that waits for the coordinator to open the socket of a block of the chain.
+    async fn round(&self, round: usize) -> Result<(), String> {
+        let announcement = self
+            .wait_for(true, |announcement| {
+                announcement.round.is_some_and(|open| open >= round)
+            })
+            .await?;
+        match announcement.round == Some(round) {
+            true => Ok(()),
+            false => Err("The group signed the block without this participant.".to_string()),
+        }
+    }
+}
+
+impl ChainRounds {
+    /// Function that shares the proposed chain with the other participants, as the coordinator.
+    pub async fn share(wallet: &Wallet, proposal: ChainProposal) -> Result<Self, String> {
+        let server = ChainServer::start(chain_port(wallet), proposal).await?;
+        Ok(ChainRounds::Coordinator(server))
+    }
+
+    /// Function that returns once the socket of a block of the chain is open.
+    /// The coordinator opens it before waiting, so it's open when the round is announced.
+    pub async fn ready(&self, round: usize) -> Result<(), String> {
+        match self {
+            ChainRounds::Coordinator(server) => {
+                tokio::time::sleep(SOCKET_DELAY).await;
+                server.update(|announcement| announcement.round = Some(round));
+                Ok(())
+            }
+            ChainRounds::Participant(client) => client.round(round).await,
+        }
+    }
+
+    /// Function that tells the other participants why the chain stopped (only the coordinator shares it).
+    pub fn fail(&self, error: &str) {
+        if let ChainRounds::Coordinator(server) = self {
+            server.update(|announcement| announcement.error = Some(error.to_string()));
+        }
+    }
+}
+
+/// Function that returns the port the coordinator of the account shares its chains on.
+pub fn chain_port(wallet: &Wallet) -> u32 {
+    wallet.port() + CHAIN_PORT_OFFSET
+}
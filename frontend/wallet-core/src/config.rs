@@ -0,0 +1,92 @@
+//! This file contains the handling of the configuration files: the version of their format, the migrations between
+//! versions and the validation of their values.
+
+use crate::Wallet;
+use std::{fs, path::Path};
+
+// the atomic writes are shared with the files the wallet keeps in the `routes` crate
+pub use routes::{backup_path, write_atomic};
+
+/// Constant value for the version of the configuration format written by this wallet.
+/// 1: the API key is stored in `config.json` (files written before the format had a version).
+/// 2: the API key is stored in the secret store.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Type that represents a migration that brings a loaded wallet to the next version of the configuration.
+type Migration = fn(&mut Wallet) -> Result<(), String>;
+
+/// Constant value with the migrations, each with the version it migrates to.
+const MIGRATIONS: [(u32, Migration); 1] = [(2, move_api_key_to_secret_store)];
+
+/// Function that runs the migrations a wallet loaded with an older version of the configuration needs, in order.
+/// Returns whether something was migrated.
+pub(crate) fn migrate(wallet: &mut Wallet, version: u32) -> Result<bool, String> {
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "The configuration was written by a newer version of the wallet (version {version}), so it won't be changed."
+        ));
+    }
+
+    let mut migrated = false;
+    for (target, migration) in MIGRATIONS {
+        if target > version {
+            migration(wallet).map_err(|e| {
+                format!("The configuration couldn't be upgraded to version {target}: {e}")
+            })?;
+            migrated = true;
+        }
+    }
+    if migrated {
+        wallet.settings.version = CONFIG_VERSION;
+        wallet.save_settings()?;
+    }
+    Ok(migrated)
+}
+
+/// Function that moves the API key from `config.json` to the secret store (version 2).
+/// The backup is removed since it's the only other copy of the key in plain text.
+fn move_api_key_to_secret_store(wallet: &mut Wallet) -> Result<(), String> {
+    if wallet.config_file.key.is_empty() {
+        return Ok(());
+    }
+
+    wallet.save_config()?;
+    let _ = fs::remove_file(backup_path(Path::new(&wallet.config_file_path)));
+    Ok(())
+}
+
+/// Function that checks a link, returning why it's invalid (`None` if it's valid).
+pub fn validate_url(name: &str, url: &str, schemes: &[&str]) -> Option<String> {
+    let valid = schemes.iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .and_then(|rest| rest.strip_prefix("://"))
+            .is_some_and(|host| !host.is_empty() && !host.contains(char::is_whitespace))
+    });
+    match valid {
+        true => None,
+        false => Some(format!(
+            "The {name} \"{url}\" isn't a valid {} link.",
+            schemes.join("/")
+        )),
+    }
+}
+
+/// Function that writes a file only the user can read.
+#[cfg(unix)]
+pub(crate) fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Function that writes a file only the user can read.
+#[cfg(not(unix))]
+pub(crate) fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
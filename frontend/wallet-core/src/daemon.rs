@@ -0,0 +1,564 @@
+//! This file contains the daemon that keeps accounts open in the background and the client of its API.
+//! The API follows JSON-RPC 2.0 over HTTP, only listens on localhost and requires the token stored next to the
+//! configuration, so internal tools (and the desktop application) can read the accounts and take part in sessions.
+
+use crate::{write_private, Proposal, Role, Session, SessionStatus, TransactionKind, Wallet};
+use axum::{
+    extract::State,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use frost_sig::{
+    client::ConfigFile,
+    nano::rpc::{AccountBalance, AccountHistory, RPCState},
+};
+use routes::raw_to_nano;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+/// Constant value of the port the daemon listens on by default.
+pub const DAEMON_PORT: u16 = 6706;
+
+/// Constant value for the name of the file with the daemon's token (stored in the configuration directory).
+pub const DAEMON_TOKEN_FILE: &str = "daemon.token";
+
+/// Constant value for the number of blocks listed by `history` when no count is given.
+const DEFAULT_HISTORY_COUNT: u32 = 50;
+
+/// Constant values for the JSON-RPC error codes answered by the daemon.
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// Struct that represents the balance of an account, written in Nano.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BalanceResponse {
+    pub account: String,
+    pub balance: String,
+    pub receivable: String,
+}
+
+/// Struct that represents a block in the history of an account, with the amount written in Nano.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub account: String,
+    pub hash: String,
+    pub amount: String,
+}
+
+/// Struct that represents the last blocks of an account.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HistoryResponse {
+    pub account: String,
+    pub history: Vec<HistoryEntry>,
+}
+
+/// Struct that represents a session run by the daemon.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub session_id: u64,
+    pub account: String,
+    pub proposal: Proposal,
+    pub status: SessionStatus,
+
+    /// Hash of the signed block (once the group signed it).
+    pub hash: Option<String>,
+}
+
+/// Struct that represents a running daemon (it stops when dropped).
+pub struct Daemon {
+    url: String,
+    task: JoinHandle<()>,
+}
+
+/// Struct that represents the state shared by the daemon's handlers.
+struct DaemonState {
+    token: String,
+    accounts: HashMap<String, DaemonAccount>,
+    sessions: Mutex<Vec<SessionInfo>>,
+}
+
+/// Struct that represents an account kept open by the daemon.
+/// The configuration is copied so reading the account doesn't wait for a running session.
+struct DaemonAccount {
+    config: ConfigFile,
+    wallet: Arc<tokio::sync::Mutex<Wallet>>,
+}
+
+/// Struct that represents the parameters of `balance` and `history`.
+#[derive(Deserialize)]
+struct AccountParams {
+    account: String,
+    count: Option<u32>,
+}
+
+/// Struct that represents the parameters of `propose_send`.
+#[derive(Deserialize)]
+struct ProposeSendParams {
+    account: String,
+    to: String,
+    amount: String,
+}
+
+/// Struct that represents the parameters of `join_session`.
+#[derive(Deserialize)]
+struct JoinSessionParams {
+    account: String,
+    coordinator: String,
+    #[serde(flatten)]
+    proposal: Proposal,
+}
+
+/// Struct that represents the parameters of `session_status`.
+#[derive(Deserialize)]
+struct SessionStatusParams {
+    session_id: u64,
+}
+
+/// Struct that represents the client of the daemon's API.
+#[derive(Clone, Debug)]
+pub struct DaemonClient {
+    url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+/// Function that gets the balance of an account from the node.
+pub async fn get_balance(config: &ConfigFile, account: &str) -> Result<BalanceResponse, String> {
+    let balance = AccountBalance::get_from_rpc(&RPCState::new(&config.url), account, &config.key)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(BalanceResponse {
+        account: account.to_string(),
+        balance: balance.balance_nano,
+        receivable: balance.receivable_nano,
+    })
+}
+
+/// Function that gets the last `count` blocks of an account from the node.
+pub async fn get_history(
+    config: &ConfigFile,
+    account: &str,
+    count: u32,
+) -> Result<HistoryResponse, String> {
+    let history =
+        AccountHistory::get_from_rpc(&RPCState::new(&config.url), account, count, &config.key)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(HistoryResponse {
+        account: account.to_string(),
+        history: history
+            .history
+            .into_iter()
+            .map(|block| HistoryEntry {
+                amount: block
+                    .amount
+                    .parse()
+                    .map(raw_to_nano)
+                    .unwrap_or(block.amount),
+                kind: block.r#type,
+                account: block.account,
+                hash: block.hash,
+            })
+            .collect(),
+    })
+}
+
+/// Function that gets the balance of the open account, through the daemon when the wallet uses one.
+pub async fn account_balance(wallet: &Wallet) -> Result<BalanceResponse, String> {
+    match DaemonClient::from_wallet(wallet) {
+        Some(client) => client.balance(&wallet.nano_account).await,
+        None => get_balance(&wallet.node(), &wallet.nano_account).await,
+    }
+}
+
+/// Function that gets the last blocks of the open account, through the daemon when the wallet uses one.
+pub async fn account_history(wallet: &Wallet, count: u32) -> Result<HistoryResponse, String> {
+    match DaemonClient::from_wallet(wallet) {
+        Some(client) => client.history(&wallet.nano_account, count).await,
+        None => get_history(&wallet.node(), &wallet.nano_account, count).await,
+    }
+}
+
+/// Function that reads the daemon's token from the configuration directory, creating it the first time.
+pub fn daemon_token(wallet: &Wallet) -> Result<String, String> {
+    let path = Path::new(&wallet.config_file_path).with_file_name(DAEMON_TOKEN_FILE);
+    if let Ok(token) = fs::read_to_string(&path) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let token = hex::encode(rand::random::<[u8; 32]>());
+    write_private(&path, &token).map_err(|e| e.to_string())?;
+    Ok(token)
+}
+
+impl Daemon {
+    /// Function that starts the daemon on the given localhost port, keeping the account of each wallet open.
+    /// A port of 0 picks a free one.
+    pub async fn start(port: u16, token: &str, wallets: Vec<Wallet>) -> Result<Self, String> {
+        if token.is_empty() {
+            return Err("The daemon needs a token.".to_string());
+        }
+
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| e.to_string())?;
+        let url = format!(
+            "http://{}",
+            listener.local_addr().map_err(|e| e.to_string())?
+        );
+
+        let accounts = wallets
+            .into_iter()
+            .map(|wallet| {
+                (
+                    wallet.nano_account.clone(),
+                    DaemonAccount {
+                        config: wallet.node(),
+                        wallet: Arc::new(tokio::sync::Mutex::new(wallet)),
+                    },
+                )
+            })
+            .collect();
+        let state = Arc::new(DaemonState {
+            token: token.to_string(),
+            accounts,
+            sessions: Mutex::new(Vec::new()),
+        });
+        let router = Router::new().route("/", post(handle)).with_state(state);
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Ok(Self { url, task })
+    }
+
+    /// Function that returns the link of the daemon's API.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Function that serves the API until the daemon stops.
+    pub async fn run(mut self) {
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl DaemonState {
+    /// Function that changes the information of a session.
+    fn update_session(&self, session_id: u64, update: impl FnOnce(&mut SessionInfo)) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            if let Some(session) = sessions.iter_mut().find(|s| s.session_id == session_id) {
+                update(session);
+            }
+        }
+    }
+}
+
+/// Function that checks the token sent in the `Authorization: Bearer` header without leaking it through timing.
+fn authorized(token: &str, headers: &HeaderMap) -> bool {
+    let given = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Function that handles each request sent to the daemon.
+async fn handle(
+    State(state): State<Arc<DaemonState>>,
+    headers: HeaderMap,
+    Json(request): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let id = request["id"].clone();
+    if !authorized(&state.token, &headers) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": INVALID_REQUEST, "message": "Invalid token." },
+            })),
+        );
+    }
+
+    let response = match call(&state, &request).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    };
+    (StatusCode::OK, Json(response))
+}
+
+/// Function that runs the method of a request and returns its result.
+async fn call(state: &Arc<DaemonState>, request: &Value) -> Result<Value, (i64, String)> {
+    let params = request["params"].clone();
+    let result = match request["method"].as_str().unwrap_or_default() {
+        "balance" => {
+            let params: AccountParams = parse_params(params)?;
+            let account = open_account(state, &params.account)?;
+            to_value(get_balance(&account.config, &params.account).await)
+        }
+        "history" => {
+            let params: AccountParams = parse_params(params)?;
+            let account = open_account(state, &params.account)?;
+            let count = params.count.unwrap_or(DEFAULT_HISTORY_COUNT);
+            to_value(get_history(&account.config, &params.account, count).await)
+        }
+        "propose_send" => {
+            let params: ProposeSendParams = parse_params(params)?;
+            let proposal = Proposal::send(&params.to, &params.amount);
+            to_value(start_session(
+                state,
+                &params.account,
+                Role::Coordinator,
+                proposal,
+            ))
+        }
+        "join_session" => {
+            let params: JoinSessionParams = parse_params(params)?;
+            to_value(start_session(
+                state,
+                &params.account,
+                Role::Participant(params.coordinator),
+                params.proposal,
+            ))
+        }
+        "session_status" => {
+            let params: SessionStatusParams = parse_params(params)?;
+            let sessions = state
+                .sessions
+                .lock()
+                .map_err(|_| (SERVER_ERROR, "The sessions are unavailable.".to_string()))?;
+            to_value(
+                sessions
+                    .iter()
+                    .find(|s| s.session_id == params.session_id)
+                    .cloned()
+                    .ok_or("The session doesn't exist.".to_string()),
+            )
+        }
+        method => {
+            return Err((
+                METHOD_NOT_FOUND,
+                format!("The method {method:?} doesn't exist."),
+            ));
+        }
+    };
+
+    result.map_err(|e| (SERVER_ERROR, e))
+}
+
+/// Function that reads the parameters of a method.
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+    serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))
+}
+
+/// Function that turns the result of a method into JSON.
+fn to_value<T: Serialize>(result: Result<T, String>) -> Result<Value, String> {
+    result.and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string()))
+}
+
+/// Function that returns an account kept open by the daemon.
+fn open_account<'a>(
+    state: &'a DaemonState,
+    account: &str,
+) -> Result<&'a DaemonAccount, (i64, String)> {
+    state.accounts.get(account).ok_or((
+        SERVER_ERROR,
+        "The account isn't open in the daemon.".to_string(),
+    ))
+}
+
+/// Function that starts signing a proposal with the group in the background and returns the new session.
+/// Only one session runs at a time because every session uses the same socket port.
+fn start_session(
+    state: &Arc<DaemonState>,
+    account: &str,
+    role: Role,
+    proposal: Proposal,
+) -> Result<SessionInfo, String> {
+    let wallet = open_account(state, account)
+        .map_err(|(_, e)| e)?
+        .wallet
+        .clone();
+
+    let info = {
+        let mut sessions = state
+            .sessions
+            .lock()
+            .map_err(|_| "The sessions are unavailable.".to_string())?;
+        if sessions.iter().any(|s| !s.status.is_finished()) {
+            return Err("Another session is running.".to_string());
+        }
+        let info = SessionInfo {
+            session_id: sessions.len() as u64 + 1,
+            account: account.to_string(),
+            proposal: proposal.clone(),
+            status: SessionStatus::Preparing,
+            hash: None,
+        };
+        sessions.push(info.clone());
+        info
+    };
+
+    // follow the session so its status can be asked at any time
+    let (session, mut status) = Session::new();
+    let session_id = info.session_id;
+    let follower = state.clone();
+    tokio::spawn(async move {
+        while let Some(status) = status.recv().await {
+            follower.update_session(session_id, |info| {
+                if let SessionStatus::Signed { hash, .. } = &status {
+                    info.hash = Some(hash.clone());
+                }
+                info.status = status;
+            });
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut wallet = wallet.lock().await;
+        let _ = session.sign(&mut wallet, &role, &proposal).await;
+    });
+
+    Ok(info)
+}
+
+impl DaemonClient {
+    /// Function that creates the client of the daemon at the given link.
+    pub fn new(url: &str, token: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            token: token.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Function that creates the client of the daemon set in the wallet's settings (`None` if it doesn't use one).
+    pub fn from_wallet(wallet: &Wallet) -> Option<Self> {
+        let daemon = &wallet.settings.daemon;
+        match daemon.url.is_empty() {
+            true => None,
+            false => Some(Self::new(&daemon.url, &daemon.token)),
+        }
+    }
+
+    /// Function that calls a method of the daemon and reads its result.
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .bearer_auth(&self.token)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .json::<Value>()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        match response.get("error") {
+            Some(error) => Err(error["message"]
+                .as_str()
+                .unwrap_or("The daemon returned an error.")
+                .to_string()),
+            None => serde_json::from_value(response["result"].clone()).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Function that gets the balance of an account open in the daemon.
+    pub async fn balance(&self, account: &str) -> Result<BalanceResponse, String> {
+        self.call("balance", json!({ "account": account })).await
+    }
+
+    /// Function that gets the last `count` blocks of an account open in the daemon.
+    pub async fn history(&self, account: &str, count: u32) -> Result<HistoryResponse, String> {
+        self.call("history", json!({ "account": account, "count": count }))
+            .await
+    }
+
+    /// Function that asks the daemon to open a session proposing to send `amount` Nano to an account.
+    pub async fn propose_send(
+        &self,
+        account: &str,
+        to: &str,
+        amount: &str,
+    ) -> Result<SessionInfo, String> {
+        self.call(
+            "propose_send",
+            json!({ "account": account, "to": to, "amount": amount }),
+        )
+        .await
+    }
+
+    /// Function that asks the daemon to join the session opened by the coordinator.
+    pub async fn join_session(
+        &self,
+        account: &str,
+        coordinator: &str,
+        proposal: &Proposal,
+    ) -> Result<SessionInfo, String> {
+        let mut params = serde_json::to_value(proposal).map_err(|e| e.to_string())?;
+        params["account"] = account.into();
+        params["coordinator"] = coordinator.into();
+        self.call("join_session", params).await
+    }
+
+    /// Function that asks the daemon how a session is going.
+    pub async fn session_status(&self, session_id: u64) -> Result<SessionInfo, String> {
+        self.call("session_status", json!({ "session_id": session_id }))
+            .await
+    }
+
+    /// Function that asks the daemon to sign a proposal: sends are proposed as the coordinator and any other proposal
+    /// joins the coordinator's session. Other proposals can't be coordinated by the daemon.
+    pub async fn sign(
+        &self,
+        account: &str,
+        role: &Role,
+        proposal: &Proposal,
+    ) -> Result<SessionInfo, String> {
+        match (role, proposal.kind) {
+            (Role::Coordinator, TransactionKind::Send) => {
+                self.propose_send(account, &proposal.receivers_account, &proposal.amount)
+                    .await
+            }
+            (Role::Participant(coordinator), _) => {
+                self.join_session(account, coordinator, proposal).await
+            }
+            (Role::Coordinator, _) => Err("The daemon only coordinates sends.".to_string()),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! This file contains the registration of the desktop application as the handler of `nano:` links.
+//! On macOS the scheme is declared by the bundle (`Info.plist`), on Linux by a desktop entry and on Windows in the
+//! registry of the user. Every clicked link starts the application, so only the first instance keeps running and the
+//! later ones hand their link to it.
+
+use routes::NANO_SCHEME;
+use std::{
+    fs::{self, OpenOptions, TryLockError},
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// Constant value for the name of the desktop entry that opens `nano:` links with the wallet (Linux).
+pub const DESKTOP_ENTRY: &str = "aokiji-nano.desktop";
+
+/// Constant value for the name of the file the running desktop application keeps locked (in the configuration
+/// directory).
+pub const INSTANCE_LOCK_FILE: &str = "aokiji.lock";
+
+/// Constant value for the name of the file with the localhost port the running desktop application receives links on.
+pub const INSTANCE_PORT_FILE: &str = "aokiji.port";
+
+/// Constant value for the number of times a new instance looks for the port of one that is still starting.
+const INSTANCE_ATTEMPTS: u32 = 20;
+
+/// Constant value for the longest link an instance accepts from another one.
+const MAX_FORWARDED_LINK: u64 = 4096;
+
+/// Enum that represents the instance of the desktop application this process became.
+#[derive(Debug)]
+pub enum Instance {
+    /// No other instance was running: the links opened afterwards arrive on the stream (empty when an instance was
+    /// started without one).
+    Primary(UnboundedReceiver<String>),
+
+    /// Another instance was running and the link was handed to it, so this one must exit.
+    Forwarded,
+}
+
+/// Function that returns the program the operating system must start to open a link: the AppImage when the wallet
+/// runs from one (its executable is inside a temporary mount) and the current executable otherwise.
+pub fn launcher() -> Result<PathBuf, String> {
+    match std::env::var_os("APPIMAGE") {
+        Some(appimage) => Ok(PathBuf::from(appimage)),
+        None => std::env::current_exe().map_err(|e| e.to_string()),
+    }
+}
+
+/// Function that returns the desktop entry that opens `nano:` links with the given program.
+pub fn desktop_entry(launcher: &Path) -> String {
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Aokiji\n\
+         Comment=Pay Nano payment requests with a group account\n\
+         Exec=\"{}\" %u\n\
+         Icon=aokiji\n\
+         Terminal=false\n\
+         NoDisplay=true\n\
+         Categories=Finance;\n\
+         MimeType=x-scheme-handler/{NANO_SCHEME};\n",
+        launcher.display()
+    )
+}
+
+/// Function that returns the first `nano:` link among the arguments the application was started with.
+pub fn uri_argument(arguments: impl IntoIterator<Item = String>) -> Option<String> {
+    arguments.into_iter().find(|argument| {
+        argument
+            .split_once(':')
+            .is_some_and(|(scheme, _)| scheme.eq_ignore_ascii_case(NANO_SCHEME))
+    })
+}
+
+/// Function that makes sure only one instance of the desktop application runs with the given configuration directory.
+/// The first instance locks a file and listens on localhost for the links of the instances started after it, which
+/// forward their link (or an empty line) to it.
+pub fn single_instance(directory: &Path, uri: Option<&str>) -> Result<Instance, String> {
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(directory.join(INSTANCE_LOCK_FILE))
+        .map_err(|e| format!("The instance lock couldn't be opened: {e}"))?;
+    let port_path = directory.join(INSTANCE_PORT_FILE);
+
+    match lock.try_lock() {
+        Ok(()) => {
+            let listener = TcpListener::bind(("127.0.0.1", 0)).map_err(|e| e.to_string())?;
+            let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+            fs::write(&port_path, port.to_string())
+                .map_err(|e| format!("The port of the instance couldn't be saved: {e}"))?;
+
+            let (sender, receiver) = unbounded_channel();
+            std::thread::spawn(move || {
+                // the lock is held for as long as the instance receives links
+                let _lock = lock;
+                for stream in listener.incoming().flatten() {
+                    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+                    let mut link = String::new();
+                    if BufReader::new(stream.take(MAX_FORWARDED_LINK))
+                        .read_line(&mut link)
+                        .is_err()
+                    {
+                        continue;
+                    }
+                    if sender.send(link.trim().to_string()).is_err() {
+                        break;
+                    }
+                }
+            });
+            Ok(Instance::Primary(receiver))
+        }
+        Err(TryLockError::WouldBlock) => {
+            forward_link(&port_path, uri.unwrap_or_default())?;
+            Ok(Instance::Forwarded)
+        }
+        Err(TryLockError::Error(e)) => Err(format!("The instance lock couldn't be taken: {e}")),
+    }
+}
+
+/// Function that hands a link to the running instance, waiting for it if it's still starting.
+fn forward_link(port_path: &Path, uri: &str) -> Result<(), String> {
+    for _ in 0..INSTANCE_ATTEMPTS {
+        let port = fs::read_to_string(port_path)
+            .ok()
+            .and_then(|port| port.trim().parse::<u16>().ok());
+        if let Some(mut stream) = port.and_then(|port| TcpStream::connect(("127.0.0.1", port)).ok())
+        {
+            return writeln!(stream, "{uri}")
+                .map_err(|e| format!("The link couldn't be handed to the running wallet: {e}"));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Err("Aokiji is already running but couldn't be reached.".to_string())
+}
+
+/// Function that registers the wallet as the handler of `nano:` links, unless another application handles them.
+#[cfg(target_os = "linux")]
+pub fn register_uri_handler() -> Result<(), String> {
+    use std::process::Command;
+
+    let mime_type = format!("x-scheme-handler/{NANO_SCHEME}");
+    let current = Command::new("xdg-mime")
+        .args(["query", "default", &mime_type])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    if !current.is_empty() && current != DESKTOP_ENTRY {
+        return Ok(());
+    }
+
+    let applications = dirs::data_dir()
+        .ok_or("The data directory of the user couldn't be found.")?
+        .join("applications");
+    std::fs::create_dir_all(&applications).map_err(|e| e.to_string())?;
+    std::fs::write(
+        applications.join(DESKTOP_ENTRY),
+        desktop_entry(&launcher()?),
+    )
+    .map_err(|e| format!("The desktop entry couldn't be written: {e}"))?;
+
+    // the database is only a cache, the entry works without it
+    let _ = Command::new("update-desktop-database")
+        .arg(&applications)
+        .status();
+    match Command::new("xdg-mime")
+        .args(["default", DESKTOP_ENTRY, &mime_type])
+        .status()
+    {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err("xdg-mime couldn't set the handler of nano: links.".to_string()),
+    }
+}
+
+/// Function that registers the wallet as the handler of `nano:` links, unless another application handles them.
+#[cfg(target_os = "windows")]
+pub fn register_uri_handler() -> Result<(), String> {
+    use std::process::Command;
+
+    let key = format!("HKCU\\Software\\Classes\\{NANO_SCHEME}");
+    let command_key = format!("{key}\\shell\\open\\command");
+    let command = format!("\"{}\" \"%1\"", launcher()?.display());
+
+    let current = Command::new("reg")
+        .args(["query", &command_key, "/ve"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    if current.contains("REG_SZ") && !current.contains(&command) {
+        return Ok(());
+    }
+
+    for arguments in [
+        vec![
+            "add",
+            key.as_str(),
+            "/ve",
+            "/d",
+            "URL:Nano Payment Request",
+            "/f",
+        ],
+        vec!["add", key.as_str(), "/v", "URL Protocol", "/d", "", "/f"],
+        vec![
+            "add",
+            command_key.as_str(),
+            "/ve",
+            "/d",
+            command.as_str(),
+            "/f",
+        ],
+    ] {
+        match Command::new("reg").args(arguments).status() {
+            Ok(status) if status.success() => {}
+            _ => return Err("The handler of nano: links couldn't be registered.".to_string()),
+        }
+    }
+    Ok(())
+}
+
+/// Function that registers the wallet as the handler of `nano:` links (the bundle's `Info.plist` already does it).
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn register_uri_handler() -> Result<(), String> {
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! This file contains the wallet core: opening and creating shared accounts and signing their blocks with the group.
+//! It doesn't depend on any interface, so the desktop application, the command line interface and the tests share it.
+
+mod chain;
+pub use chain::*;
+
+mod config;
+pub use config::*;
+
+mod daemon;
+pub use daemon::*;
+
+mod handler;
+pub use handler::*;
+
+mod secrets;
+pub use secrets::*;
+
+mod session;
+pub use session::*;
+
+mod settings;
+pub use settings::*;
+
+mod signing;
+pub use signing::*;
+
+mod wallet;
+pub use wallet::*;
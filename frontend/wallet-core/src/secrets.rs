@@ -0,0 +1,238 @@
+//! This file contains the storage of the wallet's secrets: the secret store of the operating system when it's available
+//! and an encrypted file next to the configuration otherwise (e.g. on headless Linux without a keyring).
+//! The encrypted file only protects the secrets with a passphrase: without one its key is kept in the same directory,
+//! so anyone who can read the file can read the secrets (it only keeps them out of the configuration and backups of it).
+
+use crate::{write_atomic, write_private};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Constant value for the service the secrets are stored under in the secret store of the operating system.
+const SERVICE: &str = "aokiji";
+
+/// Constant value for the name of the node's API key.
+pub const API_KEY_SECRET: &str = "api_key";
+
+/// Constant value for the name of the encrypted file with the secrets.
+pub const SECRETS_FILE: &str = "secrets.json";
+
+/// Constant value for the name of the file with the key of the encrypted file (used when there's no passphrase).
+pub const SECRETS_KEY_FILE: &str = "secrets.key";
+
+/// Constant value for the environment variable with the passphrase the encrypted file's key is derived from.
+pub const PASSPHRASE_VARIABLE: &str = "AOKIJI_PASSPHRASE";
+
+/// Constant value for the environment variable that selects the store ("file" skips the operating system's).
+pub const SECRET_STORE_VARIABLE: &str = "AOKIJI_SECRET_STORE";
+
+/// Struct that represents where the secrets of a wallet are stored.
+#[derive(Clone, Debug)]
+pub struct SecretStore {
+    /// Directory of the wallet's configuration (identifies the wallet in the operating system's store).
+    directory: PathBuf,
+
+    /// Whether the secret store of the operating system is tried before the encrypted file.
+    use_keyring: bool,
+}
+
+/// Struct that represents the encrypted file with the secrets.
+#[derive(Serialize, Deserialize, Default)]
+struct SecretsFile {
+    /// Salt used to derive the key from the passphrase (hex).
+    salt: String,
+
+    /// Encrypted secrets, indexed by name.
+    secrets: BTreeMap<String, EncryptedSecret>,
+}
+
+/// Struct that represents a secret encrypted with ChaCha20-Poly1305 (its name is authenticated with it).
+#[derive(Serialize, Deserialize)]
+struct EncryptedSecret {
+    nonce: String,
+    ciphertext: String,
+}
+
+impl SecretStore {
+    /// Function that returns the store of the wallet whose configuration is in the given directory.
+    pub fn open(directory: PathBuf) -> Self {
+        let use_keyring = !std::env::var(SECRET_STORE_VARIABLE)
+            .is_ok_and(|store| store.eq_ignore_ascii_case("file"));
+        Self {
+            directory,
+            use_keyring,
+        }
+    }
+
+    /// Function that returns a store that only uses the encrypted file.
+    pub fn encrypted_file(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            use_keyring: false,
+        }
+    }
+
+    /// Function that reads a secret (`None` if it was never stored).
+    pub fn get(&self, name: &str) -> Result<Option<String>, String> {
+        if let Some(entry) = self.keyring_entry(name) {
+            if let Ok(secret) = entry.get_password() {
+                return Ok(Some(secret));
+            }
+        }
+
+        let file = self.read_file()?;
+        let Some(secret) = file.secrets.get(name) else {
+            return Ok(None);
+        };
+        let nonce: [u8; 12] = hex::decode(&secret.nonce)
+            .ok()
+            .and_then(|nonce| nonce.try_into().ok())
+            .ok_or("The secrets file is corrupted.")?;
+        let ciphertext =
+            hex::decode(&secret.ciphertext).map_err(|_| "The secrets file is corrupted.")?;
+        let plaintext = self
+            .cipher(&file, false)?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                format!("The secrets file couldn't be decrypted (check {PASSPHRASE_VARIABLE}).")
+            })?;
+        String::from_utf8(plaintext)
+            .map(Some)
+            .map_err(|_| "The secrets file is corrupted.".to_string())
+    }
+
+    /// Function that stores a secret in the operating system's store or, when it isn't available, in the encrypted file.
+    pub fn set(&self, name: &str, secret: &str) -> Result<(), String> {
+        if let Some(entry) = self.keyring_entry(name) {
+            if entry.set_password(secret).is_ok() {
+                // a copy left in the file from a time without the store would shadow nothing but still leak
+                return self.remove_from_file(name);
+            }
+        }
+
+        let mut file = self.read_file()?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(&file, true)?
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: secret.as_bytes(),
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| "The secret couldn't be encrypted.")?;
+        file.secrets.insert(
+            name.to_string(),
+            EncryptedSecret {
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(ciphertext),
+            },
+        );
+        self.write_file(&file)
+    }
+
+    /// Function that removes a secret from every store.
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        if let Some(entry) = self.keyring_entry(name) {
+            let _ = entry.delete_credential();
+        }
+        self.remove_from_file(name)
+    }
+
+    /// Function that returns the entry of a secret in the operating system's store (`None` if it's unavailable).
+    fn keyring_entry(&self, name: &str) -> Option<keyring::Entry> {
+        match self.use_keyring {
+            true => keyring::Entry::new(
+                SERVICE,
+                &format!("{}:{name}", self.directory.to_string_lossy()),
+            )
+            .ok(),
+            false => None,
+        }
+    }
+
+    /// Function that removes a secret from the encrypted file.
+    fn remove_from_file(&self, name: &str) -> Result<(), String> {
+        let mut file = self.read_file()?;
+        match file.secrets.remove(name) {
+            Some(_) => self.write_file(&file),
+            None => Ok(()),
+        }
+    }
+
+    /// Function that reads the encrypted file, creating its salt the first time.
+    fn read_file(&self) -> Result<SecretsFile, String> {
+        let mut file = match fs::read_to_string(self.directory.join(SECRETS_FILE)) {
+            Ok(contents) => serde_json::from_str::<SecretsFile>(&contents)
+                .map_err(|e| format!("The secrets file is corrupted: {e}"))?,
+            Err(_) => SecretsFile::default(),
+        };
+        if file.salt.is_empty() {
+            file.salt = hex::encode(random_bytes::<16>());
+        }
+        Ok(file)
+    }
+
+    /// Function that writes the encrypted file so only the user can read it.
+    fn write_file(&self, file: &SecretsFile) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
+        write_atomic(&self.directory.join(SECRETS_FILE), false, |path| {
+            Ok(write_private(path, &contents)?)
+        })
+    }
+
+    /// Function that returns the cipher of the encrypted file: its key is derived from the passphrase when one is set
+    /// and is otherwise a random key kept in a file only the user can read, next to the encrypted file.
+    /// The key file is only created when a secret is stored (`create_key`), since a new key can't read the stored ones.
+    fn cipher(&self, file: &SecretsFile, create_key: bool) -> Result<ChaCha20Poly1305, String> {
+        let mut key = [0u8; 32];
+        match std::env::var(PASSPHRASE_VARIABLE) {
+            Ok(passphrase) if !passphrase.is_empty() => {
+                let salt = hex::decode(&file.salt).map_err(|_| "The secrets file is corrupted.")?;
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+                    .map_err(|e| e.to_string())?;
+            }
+            _ => {
+                let path = self.directory.join(SECRETS_KEY_FILE);
+                key = match fs::read_to_string(&path) {
+                    Ok(contents) => hex::decode(contents.trim())
+                        .ok()
+                        .and_then(|key| key.try_into().ok())
+                        .ok_or("The key of the secrets file is corrupted.")?,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound && create_key => {
+                        let key = random_bytes::<32>();
+                        write_private(&path, &hex::encode(key)).map_err(|e| e.to_string())?;
+                        key
+                    }
+                    Err(e) => {
+                        return Err(format!(
+                            "The key of the secrets file ({SECRETS_KEY_FILE}) couldn't be read, so the stored secrets \
+                             can't be decrypted (set {PASSPHRASE_VARIABLE} if they were stored with a passphrase): {e}"
+                        ))
+                    }
+                };
+            }
+        }
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+}
+
+/// Function that returns random bytes from the operating system.
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes
+}
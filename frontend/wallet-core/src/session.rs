@@ -0,0 +1,475 @@
+//! This file contains the sessions the group runs together: creating an account and signing its blocks.
+//! The desktop application, the command line interface and the tests drive the wallet through them.
+
+use crate::{
+    chain_port, create_unsigned_block, propose_receive_chain, propose_send_chain, sign_round,
+    start_session_relay, verify_receive_chain, verify_send_chain, wait_for_socket, ChainBlock,
+    ChainClient, ChainRounds, Wallet, KEYGEN_AUDIT_LOG, PORT, SOCKET_DELAY,
+};
+use frost_sig::FrostState;
+use routes::{AuditEvent, PayoutBatch, PayoutStatus, RpcError};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// Enum that represents the part this participant plays in a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Role {
+    /// Opens the socket the other participants join and takes part in the session.
+    Coordinator,
+
+    /// Joins the socket opened by the coordinator at the given address.
+    Participant(String),
+}
+
+/// Enum that represents the types of transaction the group can sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionKind {
+    Send,
+    Receive,
+    Open,
+    Change,
+}
+
+/// Struct that represents the transaction every participant builds on its own before signing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proposal {
+    #[serde(rename = "type")]
+    pub kind: TransactionKind,
+
+    /// Account that receives the Nano (send).
+    #[serde(default, rename = "to")]
+    pub receivers_account: String,
+
+    /// Amount of Nano sent, written in decimal (send).
+    #[serde(default)]
+    pub amount: String,
+
+    /// New representative (change), or the representative of a new account (open).
+    #[serde(default)]
+    pub representative: String,
+}
+
+/// Enum that represents the progress of a session, sent to whoever follows it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    /// The block (or the account) is being prepared.
+    Preparing,
+
+    /// The group is signing the block with the given hash.
+    Signing { hash: String },
+
+    /// The block was signed and handed to the node, moving `amount` raw.
+    Signed { hash: String, amount: u128 },
+
+    /// The session finished.
+    Finished,
+
+    /// The session failed.
+    Failed(String),
+}
+
+/// Struct that represents a session of the wallet with the group, reporting its progress to a stream.
+#[derive(Debug, Clone)]
+pub struct Session {
+    status: UnboundedSender<SessionStatus>,
+}
+
+impl Role {
+    /// Function that returns the name of the role recorded in the audit log.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Coordinator => "coordinator",
+            Role::Participant(_) => "participant",
+        }
+    }
+
+    /// Function that returns the address of the coordinator (`None` if this participant coordinates).
+    pub fn coordinator(&self) -> Option<&str> {
+        match self {
+            Role::Coordinator => None,
+            Role::Participant(address) => Some(address),
+        }
+    }
+}
+
+impl TransactionKind {
+    /// Function that reads a type of transaction from its name (e.g. "SEND").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "SEND" => Some(TransactionKind::Send),
+            "RECEIVE" => Some(TransactionKind::Receive),
+            "OPEN" => Some(TransactionKind::Open),
+            "CHANGE" => Some(TransactionKind::Change),
+            _ => None,
+        }
+    }
+}
+
+impl SessionStatus {
+    /// Function that returns whether the session ended (successfully or not).
+    pub fn is_finished(&self) -> bool {
+        matches!(self, SessionStatus::Finished | SessionStatus::Failed(_))
+    }
+}
+
+impl Proposal {
+    /// Function that creates an empty proposal of the given type.
+    fn new(kind: TransactionKind) -> Self {
+        Self {
+            kind,
+            receivers_account: String::new(),
+            amount: "0".to_string(),
+            representative: String::new(),
+        }
+    }
+
+    /// Function that creates the proposal to send `amount` Nano to an account.
+    pub fn send(receivers_account: &str, amount: &str) -> Self {
+        Self {
+            receivers_account: receivers_account.to_string(),
+            amount: amount.to_string(),
+            ..Self::new(TransactionKind::Send)
+        }
+    }
+
+    /// Function that creates the proposal to receive the oldest receivable block (opening the account if needed).
+    pub fn receive() -> Self {
+        Self::new(TransactionKind::Receive)
+    }
+
+    /// Function that creates the proposal to open the account with the given representative (empty picks one).
+    pub fn open(representative: &str) -> Self {
+        Self {
+            representative: representative.to_string(),
+            ..Self::new(TransactionKind::Open)
+        }
+    }
+
+    /// Function that creates the proposal to change the representative of the account.
+    pub fn change(representative: &str) -> Self {
+        Self {
+            representative: representative.to_string(),
+            ..Self::new(TransactionKind::Change)
+        }
+    }
+}
+
+impl Session {
+    /// Function that creates a session and the stream of its status.
+    pub fn new() -> (Self, UnboundedReceiver<SessionStatus>) {
+        let (status, receiver) = unbounded_channel();
+        (Self { status }, receiver)
+    }
+
+    /// Function that reports the progress of the session (nobody following it is fine).
+    fn report(&self, status: SessionStatus) {
+        let _ = self.status.send(status);
+    }
+
+    /// Function that reports how the session ended and returns its result.
+    fn finish<T>(&self, result: Result<T, String>) -> Result<T, String> {
+        match &result {
+            Ok(_) => self.report(SessionStatus::Finished),
+            Err(e) => self.report(SessionStatus::Failed(e.clone())),
+        }
+        result
+    }
+
+    /// Function that creates an account with the group, saves it to `path` and opens it in the wallet.
+    /// The number of participants and the threshold in `group` are only used by the coordinator.
+    pub async fn create_account(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        group: FrostState,
+        path: &str,
+    ) -> Result<(), String> {
+        self.report(SessionStatus::Preparing);
+
+        // the account doesn't exist yet, so key generations are recorded in their own log
+        let keygen_log = wallet.audit_log_named(KEYGEN_AUDIT_LOG);
+        let audit_error =
+            |e: RpcError| format!("The audit log couldn't record the key generation: {e}");
+        let started = keygen_log.append(
+            path,
+            AuditEvent::KeygenStarted {
+                role: role.name().to_string(),
+                coordinator: role.coordinator().unwrap_or("localhost").to_string(),
+            },
+        );
+        if let Err(e) = started {
+            return self.finish(Err(audit_error(e)));
+        }
+
+        let result = match role {
+            Role::Coordinator => {
+                let server = tokio::spawn(async move {
+                    frost_sig::server::keygen_server::run(
+                        "localhost",
+                        PORT,
+                        group.participants,
+                        group.threshold,
+                    )
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+                });
+                tokio::time::sleep(SOCKET_DELAY).await;
+                let client = frost_sig::client::keygen_client::run("localhost", PORT, path)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                let server = server.await.map_err(|e| e.to_string()).and_then(|r| r);
+                client.and(server)
+            }
+            Role::Participant(address) => {
+                frost_sig::client::keygen_client::run(address, PORT, path)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+        }
+        .and_then(|_| wallet.open_account(path));
+
+        let audit_result = match &result {
+            Ok(_) => wallet.audit_log().append(
+                &wallet.nano_account,
+                AuditEvent::KeygenFinished {
+                    participants: wallet.frost_state.participants,
+                    threshold: wallet.frost_state.threshold,
+                    public_share: wallet.public_share.clone(),
+                },
+            ),
+            Err(error) => keygen_log.append(
+                path,
+                AuditEvent::KeygenFailed {
+                    error: error.clone(),
+                },
+            ),
+        };
+
+        self.finish(match (result, audit_result) {
+            (result, Ok(_)) => result,
+            (Ok(_), Err(e)) => Err(format!("The account was created. {}", audit_error(e))),
+            (Err(error), Err(e)) => Err(format!("{error} {}", audit_error(e))),
+        })
+    }
+
+    /// Function that proposes a transaction to the group as the coordinator, returning the hash of the signed block.
+    pub async fn propose(
+        &self,
+        wallet: &mut Wallet,
+        proposal: &Proposal,
+    ) -> Result<String, String> {
+        self.sign(wallet, &Role::Coordinator, proposal).await
+    }
+
+    /// Function that joins the session opened by the coordinator, returning the hash of the signed block.
+    /// Every participant builds the block from the proposal and refuses to sign anything else.
+    pub async fn join(
+        &self,
+        wallet: &mut Wallet,
+        coordinator: &str,
+        proposal: &Proposal,
+    ) -> Result<String, String> {
+        self.sign(
+            wallet,
+            &Role::Participant(coordinator.to_string()),
+            proposal,
+        )
+        .await
+    }
+
+    /// Function that signs the block of a proposal with the group.
+    pub async fn sign(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        proposal: &Proposal,
+    ) -> Result<String, String> {
+        let result = self.sign_proposal(wallet, role, proposal).await;
+        self.finish(result)
+    }
+
+    /// Function that prepares the block of a proposal and runs its signing round.
+    async fn sign_proposal(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        proposal: &Proposal,
+    ) -> Result<String, String> {
+        self.report(SessionStatus::Preparing);
+        let (_, block, previous_state) = create_unsigned_block(wallet, proposal).await?;
+        let block = ChainBlock::new(block, previous_state)?;
+
+        // the relay checks the block and provides its work before it reaches the node
+        let session_relay = start_session_relay(wallet).await?;
+        self.report(SessionStatus::Signing {
+            hash: block.hash.clone(),
+        });
+
+        let amount = block.amount();
+        let hash = sign_round(
+            wallet,
+            &session_relay.relay,
+            &session_relay.config_file_path,
+            block,
+            role,
+            wait_for_socket(),
+        )
+        .await?;
+        self.report(SessionStatus::Signed {
+            hash: hash.clone(),
+            amount,
+        });
+
+        Ok(hash)
+    }
+
+    /// Function that receives every receivable block of the account (up to `MAX_RECEIVABLE`) with a chain of receive
+    /// blocks, returning their hashes and amounts.
+    /// The coordinator builds the whole chain and shares it before anything is signed, and the other participants only
+    /// sign it if every block is receivable according to their own node and they build the same blocks.
+    pub async fn receive_all(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+    ) -> Result<Vec<(String, u128)>, String> {
+        let result = self.receive_receivable(wallet, role).await;
+        self.finish(result)
+    }
+
+    /// Function that agrees on the chain of receive blocks with the group and signs it.
+    async fn receive_receivable(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+    ) -> Result<Vec<(String, u128)>, String> {
+        self.report(SessionStatus::Preparing);
+        let (rounds, blocks) = match role {
+            Role::Coordinator => {
+                let (proposal, blocks) = propose_receive_chain(wallet).await?;
+                (ChainRounds::share(wallet, proposal).await?, blocks)
+            }
+            Role::Participant(coordinator) => {
+                let client = ChainClient::new(coordinator, chain_port(wallet));
+                let proposal = client.proposal().await?;
+                let blocks = verify_receive_chain(wallet, &proposal).await?;
+                (ChainRounds::Participant(client), blocks)
+            }
+        };
+
+        let (received, error) = self.sign_chain(wallet, role, &rounds, blocks).await;
+        match error {
+            Some(e) => Err(e),
+            None => Ok(received),
+        }
+    }
+
+    /// Function that signs the blocks of an agreed chain in order, one round each, reusing the relay between rounds.
+    /// Returns the hash and amount of every signed block and the error that stopped the chain, since the blocks after a
+    /// failed one would build on a frontier that wasn't published.
+    async fn sign_chain(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        rounds: &ChainRounds,
+        blocks: Vec<ChainBlock>,
+    ) -> (Vec<(String, u128)>, Option<String>) {
+        let session_relay = match start_session_relay(wallet).await {
+            Ok(session_relay) => session_relay,
+            Err(e) => {
+                rounds.fail(&e);
+                return (Vec::new(), Some(e));
+            }
+        };
+
+        let mut signed = Vec::new();
+        for (round, block) in blocks.into_iter().enumerate() {
+            self.report(SessionStatus::Signing {
+                hash: block.hash.clone(),
+            });
+            let amount = block.amount();
+            let result = sign_round(
+                wallet,
+                &session_relay.relay,
+                &session_relay.config_file_path,
+                block,
+                role,
+                rounds.ready(round),
+            )
+            .await;
+
+            match result {
+                Ok(hash) => {
+                    self.report(SessionStatus::Signed {
+                        hash: hash.clone(),
+                        amount,
+                    });
+                    signed.push((hash, amount));
+                }
+                Err(e) => {
+                    rounds.fail(&e);
+                    return (signed, Some(e));
+                }
+            }
+        }
+
+        (signed, None)
+    }
+
+    /// Function that pays every row of a batch with a chain of send blocks, returning the status of each row.
+    /// Like `receive_all`, the coordinator builds the whole chain from its file and shares it before anything is
+    /// signed, and the other participants only sign it if it pays the rows of their own file. The chain stops at the
+    /// first row that fails, since the blocks after it would build on a frontier that wasn't published.
+    pub async fn send_batch(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        batch: &PayoutBatch,
+    ) -> Result<Vec<PayoutStatus>, String> {
+        let result = self.send_payouts(wallet, role, batch).await;
+        match &result {
+            Ok(statuses) => match statuses.iter().find_map(PayoutStatus::error) {
+                Some(e) => self.report(SessionStatus::Failed(e.to_string())),
+                None => self.report(SessionStatus::Finished),
+            },
+            Err(e) => self.report(SessionStatus::Failed(e.clone())),
+        }
+        result
+    }
+
+    /// Function that agrees on the chain of send blocks with the group and signs it.
+    async fn send_payouts(
+        &self,
+        wallet: &mut Wallet,
+        role: &Role,
+        batch: &PayoutBatch,
+    ) -> Result<Vec<PayoutStatus>, String> {
+        self.report(SessionStatus::Preparing);
+        let (rounds, blocks) = match role {
+            Role::Coordinator => {
+                let (proposal, blocks) = propose_send_chain(wallet, batch).await?;
+                (ChainRounds::share(wallet, proposal).await?, blocks)
+            }
+            Role::Participant(coordinator) => {
+                let client = ChainClient::new(coordinator, chain_port(wallet));
+                let proposal = client.proposal().await?;
+                let blocks = verify_send_chain(wallet, &proposal, batch).await?;
+                (ChainRounds::Participant(client), blocks)
+            }
+        };
+
+        let (sent, error) = self.sign_chain(wallet, role, &rounds, blocks).await;
+        let mut statuses: Vec<PayoutStatus> = sent
+            .into_iter()
+            .map(|(hash, _)| PayoutStatus::Sent { hash })
+            .collect();
+        statuses.extend(error.map(PayoutStatus::Failed));
+        statuses.resize(batch.payouts.len(), PayoutStatus::NotSent);
+
+        Ok(statuses)
+    }
+}
@@ -0,0 +1,227 @@
+//! This file contains the wallet settings that are not part of frost-sig's configuration file.
+
+use crate::{validate_url, CONFIG_VERSION};
+use routes::{
+    is_valid_account, nano_to_raw, NetworkSettings, PriceSettings, SpendingPolicy, WorkSettings,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error, fs};
+
+/// Struct that represents the settings of the wallet stored next to the configuration file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Version of the configuration format (files without it are from version 1).
+    #[serde(default = "legacy_version")]
+    pub version: u32,
+
+    /// Network the wallet uses, which sets the accounts' prefix and the work thresholds.
+    #[serde(default)]
+    pub network: NetworkSettings,
+
+    /// Sources used to convert the balance to fiat.
+    #[serde(default)]
+    pub price: PriceSettings,
+
+    /// WebSocket link of the node used for live updates (empty to poll the RPC instead).
+    #[serde(default)]
+    pub websocket_url: String,
+
+    /// Where the proof-of-work of the blocks comes from.
+    #[serde(default)]
+    pub work: WorkSettings,
+
+    /// Other RPC links the outbox publishes the signed blocks to when the main one fails.
+    #[serde(default)]
+    pub broadcast_nodes: Vec<String>,
+
+    /// Spending policies this participant enforces, indexed by account.
+    #[serde(default)]
+    pub policies: HashMap<String, SpendingPolicy>,
+
+    /// Daemon the desktop application reads the account and signs through.
+    #[serde(default)]
+    pub daemon: DaemonSettings,
+
+    /// Settings of each account that replace the ones above, indexed by account.
+    #[serde(default)]
+    pub accounts: HashMap<String, AccountSettings>,
+}
+
+/// Struct that represents the settings of an account that replace the global ones (`None` keeps the global value).
+/// The account's API key is kept in the secret store, next to the global one.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct AccountSettings {
+    /// RPC link of the node used by the account.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// WebSocket link of the node used by the account.
+    #[serde(default)]
+    pub websocket_url: Option<String>,
+
+    /// Other RPC links the outbox publishes the account's blocks to.
+    #[serde(default)]
+    pub broadcast_nodes: Option<Vec<String>>,
+
+    /// Currency the account's balance is converted to.
+    #[serde(default)]
+    pub currency: Option<String>,
+
+    /// Representative used when the account is opened.
+    #[serde(default)]
+    pub representative: Option<String>,
+
+    /// Port the coordinator of the account's signing sessions listens on.
+    #[serde(default)]
+    pub port: Option<u32>,
+}
+
+/// Struct that represents the daemon the wallet connects to as a client.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct DaemonSettings {
+    /// Link of the daemon's API (empty to talk to the node and the group directly).
+    #[serde(default)]
+    pub url: String,
+
+    /// Token of the daemon (stored in `daemon.token` next to the daemon's configuration).
+    #[serde(default)]
+    pub token: String,
+}
+
+fn legacy_version() -> u32 {
+    1
+}
+
+impl Default for Settings {
+    /// Function that returns the settings of a new wallet, in the current version of the format.
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            network: NetworkSettings::default(),
+            price: PriceSettings::default(),
+            websocket_url: String::new(),
+            work: WorkSettings::default(),
+            broadcast_nodes: Vec::new(),
+            policies: HashMap::new(),
+            daemon: DaemonSettings::default(),
+            accounts: HashMap::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Function that reads the settings from a file.
+    pub fn from_file_sync(path: &str) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Function that writes the settings to a file.
+    pub fn to_file_sync(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Function that checks the values of the settings, returning a message for each invalid one.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if !self.websocket_url.is_empty() {
+            errors.extend(validate_url(
+                "WebSocket link",
+                &self.websocket_url,
+                &["ws", "wss"],
+            ));
+        }
+        for node in &self.broadcast_nodes {
+            errors.extend(validate_url("broadcast node", node, &["http", "https"]));
+        }
+        if !self.daemon.url.is_empty() {
+            errors.extend(validate_url(
+                "daemon link",
+                &self.daemon.url,
+                &["http", "https"],
+            ));
+            if self.daemon.token.is_empty() {
+                errors.push("The daemon needs a token.".to_string());
+            }
+        }
+        if !self.network.prefix.bytes().all(|b| b.is_ascii_lowercase()) {
+            errors.push(format!(
+                "The address prefix \"{}\" can only have lowercase letters.",
+                self.network.prefix
+            ));
+        }
+        if self.price.currency.trim().is_empty() {
+            errors.push("The price currency is empty.".to_string());
+        }
+        for (account, policy) in &self.policies {
+            if !is_valid_account(account) {
+                errors.push(format!(
+                    "The policy of {account} isn't for a valid account."
+                ));
+            }
+            let limits = [
+                &policy.max_per_transaction,
+                &policy.daily_limit,
+                &policy.weekly_limit,
+            ];
+            for limit in limits.into_iter().flatten() {
+                if !limit.trim().is_empty() && nano_to_raw(limit.trim()).is_none() {
+                    errors.push(format!(
+                        "The limit {limit} of the policy of {account} isn't a valid amount."
+                    ));
+                }
+            }
+            if let Some(Err(e)) = policy.time_window.map(|window| window.validate()) {
+                errors.push(format!("{e} ({account})"));
+            }
+        }
+        for (account, overrides) in &self.accounts {
+            errors.extend(
+                overrides
+                    .validate()
+                    .into_iter()
+                    .map(|error| format!("{error} ({account})")),
+            );
+        }
+        errors
+    }
+}
+
+impl AccountSettings {
+    /// Function that checks the values of the account's settings, returning a message for each invalid one.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(url) = &self.url {
+            errors.extend(validate_url("RPC link", url, &["http", "https"]));
+        }
+        if let Some(websocket_url) = &self.websocket_url {
+            errors.extend(validate_url(
+                "WebSocket link",
+                websocket_url,
+                &["ws", "wss"],
+            ));
+        }
+        for node in self.broadcast_nodes.iter().flatten() {
+            errors.extend(validate_url("broadcast node", node, &["http", "https"]));
+        }
+        if self
+            .currency
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            errors.push("The price currency is empty.".to_string());
+        }
+        if let Some(representative) = &self.representative {
+            if !is_valid_account(representative) {
+                errors.push(format!(
+                    "The representative {representative} isn't a valid account."
+                ));
+            }
+        }
+        if matches!(self.port, Some(port) if port == 0 || port > u16::MAX as u32) {
+            errors.push("The coordinator port must be between 1 and 65535.".to_string());
+        }
+        errors
+    }
+}
@@ -0,0 +1,500 @@
+//! This file contains the steps of a signing session: building the block, approving it and signing it with the group.
+
+use crate::{ChainBlock, Proposal, Role, TransactionKind, Wallet};
+use frost_sig::{
+    client::SignInput,
+    nano::{
+        rpc::{AccountInfo, RPCState},
+        sign::{Subtype, UnsignedBlock},
+    },
+};
+use routes::{
+    current_time, get_account_frontier, get_receivable, get_representatives_online,
+    is_valid_account, nano_to_raw, AccountState, AuditEvent, OutboxNode, RelayOptions, RpcRelay,
+    SpendingHistory, StateBlock, WorkCache, WorkSettings, WorkSourceKind, WorkThresholds, DAY,
+    WEEK,
+};
+use std::{fs, future::Future, path::Path, sync::Arc, time::Duration};
+
+/// Constant value for the maximum number of receivable blocks handled in one session.
+pub const MAX_RECEIVABLE: u32 = 50;
+
+/// Constant value for the time the participants wait for the coordinator to open the socket.
+pub(crate) const SOCKET_DELAY: Duration = Duration::from_secs(2);
+
+/// Function that reads the state of the account the next block is built on.
+/// The node's answer is cross-checked with what the wallet already knows so a lying node is noticed before signing.
+pub async fn account_state(wallet: &Wallet) -> Result<AccountState, String> {
+    let config = wallet.node();
+    let state = RPCState::new(&config.url);
+    let account = wallet.nano_account.clone();
+
+    let frontier = get_account_frontier(&config.url, &config.key, &account)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(frontier) = frontier else {
+        return Ok(AccountState {
+            account,
+            frontier: None,
+            balance: 0,
+            representative: String::new(),
+        });
+    };
+
+    let account_info = AccountInfo::get_from_rpc(&state, &account, &config.key)
+        .await
+        .map_err(|e| e.to_string())?;
+    let balance = account_info
+        .balance
+        .parse::<u128>()
+        .map_err(|_| "The node returned an invalid balance.".to_string())?;
+    if !account_info.frontier.eq_ignore_ascii_case(&frontier) {
+        return Err("The node returned inconsistent frontiers.".to_string());
+    }
+
+    // the frontier and balance known by the wallet come from its own blocks and from confirmations
+    if let Some(known_frontier) = &wallet.known_frontier {
+        if !known_frontier.eq_ignore_ascii_case(&frontier) {
+            return Err(
+                "The node's frontier doesn't match the one known by the wallet. Wait for the node to synchronize and try again."
+                    .to_string(),
+            );
+        }
+        if matches!(wallet.known_balance, Some(known_balance) if known_balance != balance) {
+            return Err(
+                "The node's balance doesn't match the one computed by the wallet.".to_string(),
+            );
+        }
+    }
+
+    Ok(AccountState {
+        account,
+        frontier: Some(frontier),
+        balance,
+        representative: account_info.representative,
+    })
+}
+
+/// Function that returns the representative of a new account (the chosen one, the one in the account's settings or the
+/// online representative with most weight).
+pub(crate) async fn open_representative(wallet: &Wallet, representative: &str) -> String {
+    if is_valid_account(representative) {
+        return representative.to_string();
+    }
+    if let Some(representative) = wallet.default_representative() {
+        return representative;
+    }
+
+    let config = wallet.node();
+    match get_representatives_online(&config.url, &config.key).await {
+        Ok(representatives) => match representatives.by_weight().first() {
+            Some((representative, _)) => representative.clone(),
+            None => wallet.nano_account.clone(),
+        },
+        Err(_) => wallet.nano_account.clone(),
+    }
+}
+
+/// Function that builds the block of the proposed transaction locally.
+/// Receiving on an account that hasn't been opened yet produces its open block instead.
+pub async fn create_unsigned_block(
+    wallet: &Wallet,
+    proposal: &Proposal,
+) -> Result<(Subtype, StateBlock, AccountState), String> {
+    let config = wallet.node();
+    let state = account_state(wallet).await?;
+
+    let block = match proposal.kind {
+        TransactionKind::Open if state.frontier.is_some() => {
+            return Err("The account is already open.".to_string());
+        }
+        TransactionKind::Receive | TransactionKind::Open => {
+            let receivable =
+                get_receivable(&config.url, &config.key, &state.account, MAX_RECEIVABLE)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .sorted();
+            let (source, receivable_block) = receivable
+                .first()
+                .ok_or_else(|| "There are no receivable blocks.".to_string())?;
+            return create_receive_block(
+                wallet,
+                state,
+                source,
+                &receivable_block.amount,
+                &proposal.representative,
+            )
+            .await;
+        }
+        TransactionKind::Change => (
+            Subtype::CHANGE,
+            StateBlock::change(&state, &proposal.representative),
+        ),
+        TransactionKind::Send => {
+            let amount = nano_to_raw(&proposal.amount)
+                .ok_or_else(|| "The amount is not a valid number.".to_string())?;
+            (
+                Subtype::SEND,
+                StateBlock::send(&state, &proposal.receivers_account, amount),
+            )
+        }
+    };
+
+    match block {
+        (subtype, Ok(block)) => Ok((subtype, block, state)),
+        (_, Err(e)) => Err(e.to_string()),
+    }
+}
+
+/// Function that builds the block that receives the block `source` (its open block if the account wasn't opened yet).
+pub async fn create_receive_block(
+    wallet: &Wallet,
+    mut state: AccountState,
+    source: &str,
+    amount: &str,
+    representative: &str,
+) -> Result<(Subtype, StateBlock, AccountState), String> {
+    let amount = amount
+        .parse::<u128>()
+        .map_err(|_| "The node returned an invalid amount.".to_string())?;
+
+    let subtype = match state.frontier {
+        Some(_) => Subtype::RECEIVE,
+        None => {
+            state.representative = open_representative(wallet, representative).await;
+            Subtype::OPEN
+        }
+    };
+
+    match StateBlock::receive(&state, source, amount) {
+        Ok(block) => Ok((subtype, block, state)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Function that returns the nodes the outbox publishes to (the configured node first).
+pub fn outbox_nodes(wallet: &Wallet) -> Vec<OutboxNode> {
+    let node = wallet.node();
+    let main_node = OutboxNode {
+        url: node.url,
+        key: node.key,
+    };
+
+    std::iter::once(main_node)
+        .chain(wallet.broadcast_nodes().into_iter().map(|url| OutboxNode {
+            url,
+            key: String::new(),
+        }))
+        .collect()
+}
+
+/// Function that waits for the coordinator to open the socket of a single block.
+pub async fn wait_for_socket() -> Result<(), String> {
+    tokio::time::sleep(SOCKET_DELAY).await;
+    Ok(())
+}
+
+/// Function that converts a block built locally to the unsigned block signed by frost-sig.
+pub fn to_unsigned_block(block: &StateBlock) -> Result<UnsignedBlock, String> {
+    serde_json::to_value(block)
+        .and_then(serde_json::from_value::<UnsignedBlock>)
+        .map_err(|_| "Couldn't create the block.".to_string())
+}
+
+/// Function that writes the block that will be signed to the account's file, where frost-sig reads it.
+pub async fn write_sign_input(
+    wallet: &Wallet,
+    subtype: Subtype,
+    block: &StateBlock,
+) -> Result<(), String> {
+    let path = &wallet.account_path;
+    let mut sign_input = SignInput::from_file(path)
+        .await
+        .map_err(|e| e.to_string())?;
+    sign_input.subtype = subtype;
+    sign_input.message = to_unsigned_block(block)?;
+    sign_input.to_file(path).await.map_err(|e| e.to_string())
+}
+
+/// Function that checks the block against this participant's spending policy for the account and, if it follows it,
+/// locks the account's frontier on the block so no conflicting block is signed.
+/// The proposal (or the refusal) is recorded in the audit log.
+pub fn approve_block(
+    wallet: &Wallet,
+    state: &AccountState,
+    block: &StateBlock,
+    role: &Role,
+) -> Result<String, String> {
+    let result = check_and_lock_block(wallet, state, block);
+    let event = match &result {
+        Ok(hash) => AuditEvent::SessionProposed {
+            role: role.name().to_string(),
+            coordinator: role.coordinator().unwrap_or("localhost").to_string(),
+            subtype: block.subtype(state).to_string(),
+            block_hash: hash.clone(),
+            previous: block.previous.clone(),
+            representative: block.representative.clone(),
+            balance: block.balance.to_string(),
+            link: block.link.clone(),
+            participants: wallet.frost_state.participants,
+            threshold: wallet.frost_state.threshold,
+            public_share: wallet.public_share.clone(),
+        },
+        Err(e) => AuditEvent::SessionRejected { reason: e.clone() },
+    };
+
+    // a block is only signed once the session is in the audit log
+    match (result, audit(wallet, event)) {
+        (result, Ok(_)) => result,
+        (Ok(hash), Err(e)) => match wallet.journal.release(&hash) {
+            Ok(_) => Err(e),
+            Err(release_error) => Err(format!("{e} {release_error}")),
+        },
+        (Err(reason), Err(e)) => Err(format!("{reason} {e}")),
+    }
+}
+
+/// Function that checks the block against the spending policy and locks its frontier in the journal.
+fn check_and_lock_block(
+    wallet: &Wallet,
+    state: &AccountState,
+    block: &StateBlock,
+) -> Result<String, String> {
+    let now = current_time();
+
+    if let Some(policy) = wallet.settings.policies.get(&state.account) {
+        let history = SpendingHistory {
+            last_day: wallet
+                .journal
+                .sent_since(&state.account, now.saturating_sub(DAY))?,
+            last_week: wallet
+                .journal
+                .sent_since(&state.account, now.saturating_sub(WEEK))?,
+        };
+        policy
+            .check(state, block, history, now)
+            .map_err(|e| format!("Rejected by your spending policy: {e}"))?;
+    }
+
+    wallet
+        .journal
+        .lock(block, state.balance.saturating_sub(block.balance))
+        .map_err(|e| e.to_string())
+}
+
+/// Function that appends an event to the audit log of the open account.
+pub fn audit(wallet: &Wallet, event: AuditEvent) -> Result<(), String> {
+    wallet
+        .audit_log()
+        .append(&wallet.nano_account, event)
+        .map(|_| ())
+        .map_err(|e| format!("The audit log couldn't record the session: {e}"))
+}
+
+/// Function that records the end of a signing session in the journal and in the audit log.
+/// The last published block becomes the frontier known by the wallet, and the journal keeps the frontier locked
+/// if the block may have been signed (the session succeeded or the block reached the outbox).
+/// Returns an error if the journal or the audit log couldn't be updated (a frontier that wasn't released stays locked).
+pub fn finish_session(
+    wallet: &mut Wallet,
+    relay: &RpcRelay,
+    hash: &str,
+    result: Result<(), String>,
+) -> Result<(), String> {
+    let signed = result.is_ok()
+        || wallet
+            .outbox
+            .entries()
+            .iter()
+            .any(|entry| entry.hash == hash);
+    let journal_result = match signed {
+        true => wallet.journal.mark_signed(hash).map_err(|e| {
+            format!("The block {hash} was signed, but the journal couldn't record it: {e}")
+        }),
+        false => wallet
+            .journal
+            .release(hash)
+            .map_err(|e| format!("The frontier of the block {hash} couldn't be unlocked: {e}")),
+    };
+
+    let published = relay
+        .published()
+        .iter()
+        .any(|block| block.hash_hex().is_ok_and(|h| h == hash));
+    let audit_result = audit(
+        wallet,
+        match result {
+            Ok(_) => AuditEvent::SessionSucceeded {
+                block_hash: hash.to_string(),
+                published,
+            },
+            Err(error) => AuditEvent::SessionFailed {
+                block_hash: hash.to_string(),
+                error,
+            },
+        },
+    );
+
+    if let Some(block) = relay.published().last() {
+        if let Ok(frontier) = block.hash_hex() {
+            wallet.known_frontier = Some(frontier);
+            wallet.known_balance = Some(block.balance);
+        }
+    }
+
+    match (journal_result, audit_result) {
+        (Err(journal_error), Err(audit_error)) => Err(format!("{journal_error} {audit_error}")),
+        (journal_result, audit_result) => journal_result.and(audit_result),
+    }
+}
+
+/// Struct that represents the relay of a signing session and the configuration file that points frost-sig to it.
+/// The file is deleted when the session relay is dropped.
+pub struct SessionRelay {
+    pub relay: RpcRelay,
+    pub config_file_path: String,
+}
+
+impl Drop for SessionRelay {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.config_file_path);
+    }
+}
+
+/// Function that starts the relay used by a signing session and writes a configuration file that points frost-sig to it.
+/// Each session has its own file, since the desktop application, the CLI and the daemon may sign at the same time.
+/// The relay must be kept alive until the session ends.
+pub async fn start_session_relay(wallet: &Wallet) -> Result<SessionRelay, String> {
+    let mut config = wallet.node();
+    let work_settings = &wallet.settings.work;
+    let work_source = match work_settings.source {
+        WorkSourceKind::Remote => None,
+        _ => Some(Arc::from(
+            work_settings.work_source(&config.url, &config.key),
+        )),
+    };
+
+    let relay = RpcRelay::start(RelayOptions {
+        node_url: config.url.clone(),
+        node_key: config.key.clone(),
+        work_source,
+        work_cache: wallet.work_cache.clone(),
+        thresholds: wallet.settings.network.work_thresholds(),
+        outbox: wallet.outbox.clone(),
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let config_file_path = Path::new(&wallet.config_file_path)
+        .with_file_name(format!(
+            "session-{}.json",
+            hex::encode(rand::random::<[u8; 8]>())
+        ))
+        .to_string_lossy()
+        .to_string();
+    // the relay adds the key, so it isn't written to the session's file
+    config.url = relay.url().to_string();
+    config.key = String::new();
+    let session_relay = SessionRelay {
+        relay,
+        config_file_path,
+    };
+    config
+        .to_file_sync(&session_relay.config_file_path)
+        .map_err(|e| e.to_string())?;
+
+    Ok(session_relay)
+}
+
+/// Function that signs one block with the group, returning its hash.
+/// The coordinator opens the socket while the other participants join its address once `ready` returns.
+pub async fn sign_round(
+    wallet: &mut Wallet,
+    relay: &RpcRelay,
+    config_file_path: &str,
+    chain_block: ChainBlock,
+    role: &Role,
+    ready: impl Future<Output = Result<(), String>>,
+) -> Result<String, String> {
+    let ChainBlock {
+        block,
+        previous_state,
+        ..
+    } = &chain_block;
+    // nothing is written for frost-sig before the block is approved
+    let hash = approve_block(wallet, previous_state, block, role)?;
+    if let Err(e) = write_sign_input(wallet, chain_block.subtype(), block).await {
+        return match finish_session(wallet, relay, &hash, Err(e.clone())) {
+            Ok(_) => Err(e),
+            Err(journal_error) => Err(format!("{e} {journal_error}")),
+        };
+    }
+    let coordinator = role.coordinator();
+    let port = wallet.port();
+    relay.expect(chain_block.block);
+
+    let server = match coordinator {
+        Some(_) => None,
+        None => {
+            let frost_state = wallet.frost_state.clone();
+            Some(tokio::spawn(async move {
+                frost_sig::server::sign_server::run(
+                    "localhost",
+                    port,
+                    frost_state.participants,
+                    frost_state.threshold,
+                )
+                .await
+                .map_err(|e| e.to_string())
+            }))
+        }
+    };
+
+    let result = match ready.await {
+        Ok(_) => {
+            let result = frost_sig::client::sign_client::run(
+                coordinator.unwrap_or("localhost"),
+                port,
+                &wallet.account_path,
+                config_file_path,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+            match server {
+                Some(server) => {
+                    let server_result = server.await.map_err(|e| e.to_string()).and_then(|r| r);
+                    result.and(server_result)
+                }
+                None => result,
+            }
+        }
+        // nobody joins the socket of a round this participant won't take part in
+        Err(e) => {
+            if let Some(server) = server {
+                server.abort();
+            }
+            Err(e)
+        }
+    };
+
+    let journal_result = finish_session(wallet, relay, &hash, result.clone());
+    result.and(journal_result).map(|_| hash)
+}
+
+/// Function that computes the work of the account's next block ahead of time.
+pub async fn precompute_next_work(
+    url: String,
+    key: String,
+    account: String,
+    work_settings: WorkSettings,
+    work_cache: WorkCache,
+    thresholds: WorkThresholds,
+) {
+    if let Ok(Some(frontier)) = get_account_frontier(&url, &key, &account).await {
+        let work_source = work_settings.work_source(&url, &key);
+        let _ = work_cache
+            .precompute(work_source.as_ref(), &frontier, &thresholds)
+            .await;
+    }
+}
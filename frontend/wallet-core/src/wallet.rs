@@ -0,0 +1,456 @@
+//! This file contains the wallet: the open account and the files that are kept next to its configuration.
+
+use crate::{
+    migrate, validate_url, write_atomic, AccountSettings, SecretStore, Settings, API_KEY_SECRET,
+    CONFIG_VERSION,
+};
+use dirs::config_dir;
+use frost_sig::{
+    client::{ConfigFile, SignInput},
+    FrostState,
+};
+use routes::{
+    current_time, AuditLog, Outbox, PaymentRequest, PaymentSchedule, PriceSettings,
+    ScheduledPayment, SigningJournal, WorkCache,
+};
+use std::{
+    env::current_dir,
+    error::Error,
+    fs::{self, create_dir_all},
+    path::{Path, PathBuf},
+};
+
+/// Constant value of the port used for socket connections.
+pub const PORT: u32 = 6705;
+
+/// Constant value for the name of the audit log of the key generation sessions.
+pub const KEYGEN_AUDIT_LOG: &str = "keygen.jsonl";
+
+/// Struct that represents the wallet: the open account, its configuration and the state kept between sessions.
+/// Clones share the outbox, the journal and the schedule.
+#[derive(Clone, Debug)]
+pub struct Wallet {
+    /// Path of the account's file.
+    pub account_path: String,
+
+    /// Nano account currently open.
+    pub nano_account: String,
+
+    /// Public share that identifies the user inside the group.
+    pub public_share: String,
+
+    /// FROST parameters of the account.
+    pub frost_state: FrostState,
+
+    /// Configuration file.
+    pub config_file: ConfigFile,
+
+    /// Configuration file path.
+    pub config_file_path: String,
+
+    /// Settings of the wallet.
+    pub settings: Settings,
+
+    /// Settings file path.
+    pub settings_path: String,
+
+    /// Work computed ahead of time for the next blocks.
+    pub work_cache: WorkCache,
+
+    /// Last block of the open account known by the wallet (its own blocks and confirmations).
+    pub known_frontier: Option<String>,
+
+    /// Balance after `known_frontier` when it was computed or confirmed by the wallet.
+    pub known_balance: Option<u128>,
+
+    /// Signed blocks that are waiting to be published or confirmed.
+    pub outbox: Outbox,
+
+    /// Blocks this participant agreed to sign, used to refuse conflicting blocks.
+    pub journal: SigningJournal,
+
+    /// Payments each account makes on a schedule.
+    pub schedule: PaymentSchedule,
+
+    /// Directory with the audit log of each account.
+    pub audit_directory: PathBuf,
+
+    /// Where the API key and the other secrets are stored.
+    pub secrets: SecretStore,
+
+    /// API key of the node of the open account (empty to use the global one).
+    pub account_key: String,
+
+    /// Problems found while loading the configuration. The files aren't changed until it's saved again, and the
+    /// defaults are used meanwhile for the values that couldn't be read.
+    pub config_errors: Vec<String>,
+}
+
+impl Default for Wallet {
+    /// Function that returns the wallet stored in the default configuration directory.
+    fn default() -> Self {
+        Self::from_directory(get_config_directory().unwrap_or_default())
+    }
+}
+
+impl Wallet {
+    /// Function that loads the state of the wallet whose files are stored in the given directory.
+    pub fn from_directory(config_path: PathBuf) -> Self {
+        let config_file_path = config_path
+            .join("config.json")
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+        let settings_path = config_path
+            .join("settings.json")
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+        let outbox_path = config_path
+            .join("outbox.json")
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+        let journal_path = config_path
+            .join("journal.json")
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+        let schedule_path = config_path
+            .join("schedule.json")
+            .into_os_string()
+            .into_string()
+            .unwrap_or("".to_string());
+
+        let mut config_errors = Vec::new();
+        let has_config_file = Path::new(&config_file_path).exists();
+        let config_file = match has_config_file {
+            true => ConfigFile::from_file_sync(&config_file_path).unwrap_or_else(|e| {
+                config_errors.push(format!(
+                    "The configuration file {config_file_path} couldn't be read ({e})."
+                ));
+                ConfigFile::new()
+            }),
+            false => ConfigFile::new(),
+        };
+        let (settings, version) = match Path::new(&settings_path).exists() {
+            true => match Settings::from_file_sync(&settings_path) {
+                Ok(settings) => {
+                    let version = settings.version;
+                    (settings, version)
+                }
+                Err(e) => {
+                    config_errors.push(format!(
+                        "The settings file {settings_path} couldn't be read ({e})."
+                    ));
+                    (Settings::default(), CONFIG_VERSION)
+                }
+            },
+            // a configuration file without settings is from before the format had a version
+            false => (
+                Settings::default(),
+                if has_config_file { 1 } else { CONFIG_VERSION },
+            ),
+        };
+
+        // a journal that can't be read refuses every block until it's fixed, instead of unlocking the frontiers,
+        // and an outbox or a schedule that can't be read isn't replaced, so the blocks and payments in it aren't lost
+        let mut storage_errors = Vec::new();
+        let outbox = Outbox::open(&outbox_path).unwrap_or_else(|e| {
+            storage_errors.push(e);
+            Outbox::at(&outbox_path)
+        });
+        let schedule = PaymentSchedule::open(&schedule_path).unwrap_or_else(|e| {
+            storage_errors.push(e);
+            PaymentSchedule::at(&schedule_path)
+        });
+        let journal = SigningJournal::open(&journal_path).unwrap_or_else(|e| {
+            storage_errors.push(e);
+            SigningJournal::at(&journal_path)
+        });
+
+        let mut wallet = Self {
+            account_path: "".to_string(),
+            nano_account: "".to_string(),
+            frost_state: FrostState::new(0, 0),
+            public_share: "".to_string(),
+            config_file,
+            config_file_path,
+            settings,
+            settings_path,
+            work_cache: WorkCache::default(),
+            known_frontier: None,
+            known_balance: None,
+            outbox,
+            journal,
+            schedule,
+            audit_directory: config_path.join("audit"),
+            secrets: SecretStore::open(config_path),
+            account_key: "".to_string(),
+            config_errors,
+        };
+
+        // nothing is migrated (and so written) while a file couldn't be read, to not replace it with the defaults
+        if wallet.config_errors.is_empty() {
+            if let Err(e) = migrate(&mut wallet, version) {
+                wallet.config_errors.push(e);
+            }
+        }
+        if wallet.config_file.key.is_empty() {
+            match wallet.secrets.get(API_KEY_SECRET) {
+                Ok(key) => wallet.config_file.key = key.unwrap_or_default(),
+                Err(e) => wallet.config_errors.push(e),
+            }
+        }
+        let errors = wallet.validate();
+        wallet.config_errors.extend(errors);
+        wallet.config_errors.extend(storage_errors);
+        wallet
+    }
+
+    /// Function that checks the values of the configuration and the settings, returning a message for each invalid one.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors: Vec<String> =
+            validate_url("RPC link", &self.config_file.url, &["http", "https"])
+                .into_iter()
+                .collect();
+        errors.extend(self.settings.validate());
+        errors
+    }
+
+    /// Function that saves the configuration file, storing the API key in the secret store instead of the file.
+    pub fn save_config(&self) -> Result<(), String> {
+        if let Some(error) = validate_url("RPC link", &self.config_file.url, &["http", "https"]) {
+            return Err(error);
+        }
+
+        match self.config_file.key.is_empty() {
+            true => self.secrets.delete(API_KEY_SECRET)?,
+            false => self.secrets.set(API_KEY_SECRET, &self.config_file.key)?,
+        }
+        let mut config_file = self.config_file.clone();
+        config_file.key = String::new();
+        write_atomic(Path::new(&self.config_file_path), true, |path| {
+            config_file.to_file_sync(&path.to_string_lossy())
+        })
+    }
+
+    /// Function that saves the settings file (refusing invalid settings).
+    pub fn save_settings(&self) -> Result<(), String> {
+        if self.settings.version > CONFIG_VERSION {
+            return Err(format!(
+                "The settings were written by a newer version of the wallet (version {}), so they won't be changed.",
+                self.settings.version
+            ));
+        }
+        let errors = self.settings.validate();
+        if !errors.is_empty() {
+            return Err(errors.join(" "));
+        }
+
+        let settings = Settings {
+            version: CONFIG_VERSION,
+            ..self.settings.clone()
+        };
+        write_atomic(Path::new(&self.settings_path), true, |path| {
+            settings.to_file_sync(&path.to_string_lossy())
+        })
+    }
+
+    /// Function that saves the configuration and the settings, which fixes the problems found while loading them.
+    pub fn save(&mut self) -> Result<(), String> {
+        self.save_config()?;
+        self.save_settings()?;
+        self.settings.version = CONFIG_VERSION;
+        self.config_errors.clear();
+        Ok(())
+    }
+
+    /// Function that opens the audit log of the account currently open.
+    pub fn audit_log(&self) -> AuditLog {
+        self.audit_log_named(&format!("{}.jsonl", self.nano_account))
+    }
+
+    /// Function that opens an audit log stored in the audit directory.
+    pub fn audit_log_named(&self, file_name: &str) -> AuditLog {
+        AuditLog::open(
+            &self
+                .audit_directory
+                .join(file_name)
+                .into_os_string()
+                .into_string()
+                .unwrap_or("".to_string()),
+        )
+    }
+
+    /// Function that opens the account stored in a file created by a key generation session.
+    pub fn open_account(&mut self, path: &str) -> Result<(), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|_| "Couldn't open the file.".to_string())?;
+        let sign_input = serde_json::from_str::<SignInput>(&contents)
+            .map_err(|_| "File has invalid format.".to_string())?;
+
+        self.account_path = path.to_string();
+        self.nano_account = self
+            .settings
+            .network
+            .account(&sign_input.public_aggregated_key.to_bytes());
+        self.frost_state = sign_input.state;
+        self.public_share = hex::encode(sign_input.own_public_share.as_bytes());
+        self.account_key = match self.secrets.get(&account_key_secret(&self.nano_account)) {
+            Ok(key) => key.unwrap_or_default(),
+            Err(e) => {
+                self.config_errors.push(e);
+                String::new()
+            }
+        };
+        Ok(())
+    }
+
+    /// Function that returns the settings of the open account that replace the global ones.
+    pub fn account_settings(&self) -> AccountSettings {
+        self.settings
+            .accounts
+            .get(&self.nano_account)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Function that returns the node used by the open account (its own or the global one).
+    pub fn node(&self) -> ConfigFile {
+        let mut node = self.config_file.clone();
+        if let Some(url) = self.account_settings().url {
+            node.url = url;
+        }
+        if !self.account_key.is_empty() {
+            node.key = self.account_key.clone();
+        }
+        node
+    }
+
+    /// Function that returns the WebSocket link used by the open account.
+    pub fn websocket_url(&self) -> String {
+        self.account_settings()
+            .websocket_url
+            .unwrap_or_else(|| self.settings.websocket_url.clone())
+    }
+
+    /// Function that returns the other RPC links the outbox publishes the open account's blocks to.
+    pub fn broadcast_nodes(&self) -> Vec<String> {
+        self.account_settings()
+            .broadcast_nodes
+            .unwrap_or_else(|| self.settings.broadcast_nodes.clone())
+    }
+
+    /// Function that returns the price settings used for the open account.
+    pub fn price_settings(&self) -> PriceSettings {
+        let mut price = self.settings.price.clone();
+        if let Some(currency) = self.account_settings().currency {
+            price.currency = currency;
+        }
+        price
+    }
+
+    /// Function that returns the representative the open account is opened with (`None` to pick one online).
+    pub fn default_representative(&self) -> Option<String> {
+        self.account_settings().representative
+    }
+
+    /// Function that returns the port the coordinator of the open account's signing sessions listens on.
+    pub fn port(&self) -> u32 {
+        self.account_settings().port.unwrap_or(PORT)
+    }
+
+    /// Function that reads a payment request (a `nano:` URI or an account) whose account must belong to the network
+    /// of the wallet.
+    pub fn payment_request(&self, uri: &str) -> Result<PaymentRequest, String> {
+        PaymentRequest::parse(uri, &self.settings.network)
+    }
+
+    /// Function that adds a payment of the open account to the schedule, once its receiver is checked against the
+    /// network of the wallet.
+    pub fn schedule_payment(&self, payment: ScheduledPayment) -> Result<(), String> {
+        if self.nano_account.is_empty() || payment.account != self.nano_account {
+            return Err("The payment isn't made by the open account.".to_string());
+        }
+        if !self.settings.network.is_account(&payment.receivers_account) {
+            return Err(format!(
+                "{} isn't an account of the {} network.",
+                payment.receivers_account,
+                self.settings.network.network.name()
+            ));
+        }
+        self.schedule.add(payment)
+    }
+
+    /// Function that returns the scheduled payments of the open account that are due, with the time each is due.
+    pub fn due_payments(&self) -> Vec<(ScheduledPayment, u64)> {
+        self.schedule
+            .due_payments(&self.nano_account, current_time())
+    }
+
+    /// Function that returns a due payment of the open account and the time it's due.
+    pub fn due_payment(&self, id: &str) -> Result<(ScheduledPayment, u64), String> {
+        self.due_payments()
+            .into_iter()
+            .find(|(payment, _)| payment.id == id)
+            .ok_or(format!("The scheduled payment {id} isn't due."))
+    }
+
+    /// Function that saves the settings of the open account and its API key (an empty key uses the global one).
+    pub fn save_account_settings(
+        &mut self,
+        account_settings: AccountSettings,
+        key: &str,
+    ) -> Result<(), String> {
+        if self.nano_account.is_empty() {
+            return Err("There's no open account.".to_string());
+        }
+        let errors = account_settings.validate();
+        if !errors.is_empty() {
+            return Err(errors.join(" "));
+        }
+
+        let mut settings = self.settings.clone();
+        match account_settings == AccountSettings::default() {
+            true => settings.accounts.remove(&self.nano_account),
+            false => settings
+                .accounts
+                .insert(self.nano_account.clone(), account_settings),
+        };
+        let previous = std::mem::replace(&mut self.settings, settings);
+        if let Err(e) = self.save_settings() {
+            self.settings = previous;
+            return Err(e);
+        }
+
+        let secret = account_key_secret(&self.nano_account);
+        match key.is_empty() {
+            true => self.secrets.delete(&secret)?,
+            false => self.secrets.set(&secret, key)?,
+        }
+        self.account_key = key.to_string();
+        Ok(())
+    }
+}
+
+/// Function that returns the name of the secret with the API key of an account.
+fn account_key_secret(account: &str) -> String {
+    format!("{API_KEY_SECRET}:{account}")
+}
+
+/// Function that gets or creates the config file directory according to the operating system.
+pub fn get_config_directory() -> Result<PathBuf, Box<dyn Error>> {
+    let app = "Aokiji";
+
+    let config_dir = match config_dir() {
+        Some(config_dir) => config_dir.join(app),
+        _ => current_dir()?.join(app),
+    };
+
+    if !config_dir.exists() {
+        create_dir_all(&config_dir)?;
+    }
+
+    Ok(config_dir)
+}
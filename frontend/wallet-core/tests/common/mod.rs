@@ -0,0 +1,440 @@
+//! This file contains the harness shared by the integration tests: an in-process mock of the Nano RPC, the wallets
+//! of the simulated participants and the sessions they run together.
+// each test file uses only part of the harness
+#![allow(dead_code)]
+
+use axum::{extract::State, routing::post, Json, Router};
+use frost_sig::FrostState;
+use routes::{account_to_public_key, public_key_to_account, StateBlock};
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+use wallet_core::{Proposal, Role, Session, Wallet};
+
+/// Constant value for the account the mock node funds other accounts from.
+pub const GENESIS: &str = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+/// Constant value for the only representative online in the mock node.
+pub const REPRESENTATIVE: &str =
+    "nano_3b54jp7j8ewcdmwpg1fjrr9ke11tgcwdjsd1dif44arruk65bfy3qtrycnmh";
+
+/// Struct that represents a block stored by the mock node.
+#[derive(Clone, Debug)]
+struct StoredBlock {
+    block: StateBlock,
+    subtype: String,
+    amount: u128,
+    height: usize,
+}
+
+/// Struct that represents the ledger kept by the mock node.
+#[derive(Default)]
+struct Ledger {
+    /// Hashes of the blocks of each account, oldest first.
+    chains: HashMap<String, Vec<String>>,
+
+    /// Every block known by the node, indexed by hash.
+    blocks: HashMap<String, StoredBlock>,
+
+    /// Blocks each account can receive: hash of the send and its amount.
+    receivable: HashMap<String, BTreeMap<String, (String, u128)>>,
+
+    /// Blocks accepted through `process`, in order.
+    published: Vec<StateBlock>,
+
+    /// Number of funding blocks created, used to give them unique hashes.
+    fundings: u64,
+}
+
+/// Struct that represents an in-process mock of the Nano RPC (it stops when dropped).
+/// It keeps a ledger, checks every processed block against it and the account's key, and answers the actions the
+/// wallet and frost-sig use: account_info, account_balance, account_history, block_info, process, work_generate,
+/// receivable and representatives_online.
+pub struct MockNode {
+    url: String,
+    ledger: Arc<Mutex<Ledger>>,
+    task: JoinHandle<()>,
+}
+
+impl MockNode {
+    /// Function that starts the mock node on a free localhost port.
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let ledger = Arc::new(Mutex::new(Ledger::default()));
+        let router = Router::new()
+            .route("/", post(handle))
+            .with_state(ledger.clone());
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Self { url, ledger, task }
+    }
+
+    /// Function that returns the RPC link of the mock node.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Function that makes `amount` raw receivable by an account, as if the genesis account sent it.
+    pub fn fund(&self, account: &str, amount: u128) -> String {
+        let mut ledger = self.ledger.lock().unwrap();
+        ledger.fundings += 1;
+        let hash = format!("{:064X}", ledger.fundings);
+
+        let block = StateBlock {
+            block_type: "state".to_string(),
+            account: GENESIS.to_string(),
+            previous: "0".repeat(64),
+            representative: REPRESENTATIVE.to_string(),
+            balance: 0,
+            link: hex::encode_upper(account_to_public_key(account).unwrap()),
+            signature: None,
+            work: None,
+        };
+        ledger.blocks.insert(
+            hash.clone(),
+            StoredBlock {
+                block,
+                subtype: "send".to_string(),
+                amount,
+                height: 1,
+            },
+        );
+        ledger
+            .receivable
+            .entry(account.to_string())
+            .or_default()
+            .insert(hash.clone(), (GENESIS.to_string(), amount));
+        hash
+    }
+
+    /// Function that returns the blocks the mock node accepted, in order.
+    pub fn published(&self) -> Vec<StateBlock> {
+        self.ledger.lock().unwrap().published.clone()
+    }
+
+    /// Function that returns the balance of an account in the ledger.
+    pub fn balance(&self, account: &str) -> u128 {
+        let ledger = self.ledger.lock().unwrap();
+        ledger
+            .frontier(account)
+            .map(|b| b.block.balance)
+            .unwrap_or(0)
+    }
+
+    /// Function that returns the amounts an account can receive.
+    pub fn receivable(&self, account: &str) -> Vec<u128> {
+        let ledger = self.ledger.lock().unwrap();
+        ledger
+            .receivable
+            .get(account)
+            .map(|blocks| blocks.values().map(|(_, amount)| *amount).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Ledger {
+    /// Function that returns the last block of an account (`None` if it wasn't opened).
+    fn frontier(&self, account: &str) -> Option<&StoredBlock> {
+        self.chains
+            .get(account)
+            .and_then(|chain| chain.last())
+            .and_then(|hash| self.blocks.get(hash))
+    }
+
+    /// Function that checks a block against the ledger and the account's key and appends it to its chain.
+    /// Publishing the same block again is accepted, since every participant's client may publish it.
+    fn process(&mut self, block: StateBlock) -> Result<String, String> {
+        let hash = block.hash_hex().map_err(|e| e.to_string())?;
+        if self.blocks.contains_key(&hash) {
+            return Ok(hash);
+        }
+
+        let public_key = account_to_public_key(&block.account).ok_or("Bad account")?;
+        block
+            .verify_signature(&public_key)
+            .map_err(|_| "Bad signature".to_string())?;
+
+        let (previous_balance, height) = match self.frontier(&block.account) {
+            Some(frontier) => {
+                if !frontier
+                    .block
+                    .hash_hex()
+                    .is_ok_and(|h| h.eq_ignore_ascii_case(&block.previous))
+                {
+                    return Err("Fork".to_string());
+                }
+                (frontier.block.balance, frontier.height)
+            }
+            None if block.previous.chars().all(|c| c == '0') => (0, 0),
+            None => return Err("Gap previous block".to_string()),
+        };
+
+        let (subtype, amount) = if block.balance < previous_balance {
+            let destination = hex::decode(&block.link)
+                .ok()
+                .and_then(|link| <[u8; 32]>::try_from(link).ok())
+                .map(|link| public_key_to_account(&link))
+                .ok_or("Bad link")?;
+            let amount = previous_balance - block.balance;
+            self.receivable
+                .entry(destination)
+                .or_default()
+                .insert(hash.clone(), (block.account.clone(), amount));
+            ("send", amount)
+        } else if block.balance > previous_balance {
+            let source = self
+                .receivable
+                .get_mut(&block.account)
+                .and_then(|blocks| blocks.remove(&block.link.to_uppercase()))
+                .ok_or("Unreceivable")?;
+            let amount = block.balance - previous_balance;
+            if source.1 != amount {
+                return Err("Balance mismatch".to_string());
+            }
+            (if height == 0 { "open" } else { "receive" }, amount)
+        } else {
+            ("change", 0)
+        };
+
+        self.chains
+            .entry(block.account.clone())
+            .or_default()
+            .push(hash.clone());
+        self.blocks.insert(
+            hash.clone(),
+            StoredBlock {
+                block: block.clone(),
+                subtype: subtype.to_string(),
+                amount,
+                height: height + 1,
+            },
+        );
+        self.published.push(block);
+        Ok(hash)
+    }
+}
+
+/// Function that answers each request sent to the mock node.
+async fn handle(
+    State(ledger): State<Arc<Mutex<Ledger>>>,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let mut ledger = ledger.lock().unwrap();
+    let account = request["account"].as_str().unwrap_or_default().to_string();
+
+    let response = match request["action"].as_str().unwrap_or_default() {
+        "account_info" => match ledger.frontier(&account) {
+            Some(frontier) => {
+                let chain = &ledger.chains[&account];
+                json!({
+                    "frontier": chain.last(),
+                    "open_block": chain.first(),
+                    "representative_block": chain.last(),
+                    "representative": frontier.block.representative,
+                    "balance": frontier.block.balance.to_string(),
+                    "block_count": chain.len().to_string(),
+                    "confirmation_height": chain.len().to_string(),
+                })
+            }
+            None => json!({ "error": "Account not found" }),
+        },
+        "account_balance" => {
+            let balance = ledger
+                .frontier(&account)
+                .map(|b| b.block.balance)
+                .unwrap_or(0);
+            let receivable: u128 = ledger
+                .receivable
+                .get(&account)
+                .map(|blocks| blocks.values().map(|(_, amount)| amount).sum())
+                .unwrap_or(0);
+            json!({
+                "balance": balance.to_string(),
+                "receivable": receivable.to_string(),
+                "balance_nano": routes::raw_to_nano(balance),
+                "receivable_nano": routes::raw_to_nano(receivable),
+            })
+        }
+        "account_history" => {
+            let history: Vec<Value> = ledger
+                .chains
+                .get(&account)
+                .map(|chain| {
+                    chain
+                        .iter()
+                        .rev()
+                        .map(|hash| {
+                            let stored = &ledger.blocks[hash];
+                            json!({
+                                "type": stored.subtype,
+                                "account": stored.block.account,
+                                "amount": stored.amount.to_string(),
+                                "hash": hash,
+                                "height": stored.height.to_string(),
+                                "confirmed": "true",
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            json!({ "account": account, "history": history })
+        }
+        "block_info" => {
+            let hash = request["hash"].as_str().unwrap_or_default().to_uppercase();
+            match ledger.blocks.get(&hash) {
+                Some(stored) => json!({
+                    "block_account": stored.block.account,
+                    "amount": stored.amount.to_string(),
+                    "balance": stored.block.balance.to_string(),
+                    "height": stored.height.to_string(),
+                    "confirmed": "true",
+                    "contents": stored.block,
+                    "subtype": stored.subtype,
+                }),
+                None => json!({ "error": "Block not found" }),
+            }
+        }
+        "process" => match StateBlock::from_json(&request["block"]) {
+            Ok(block) => match ledger.process(block) {
+                Ok(hash) => json!({ "hash": hash }),
+                Err(e) => json!({ "error": e }),
+            },
+            Err(_) => json!({ "error": "Block is invalid" }),
+        },
+        // the harness doesn't check work, so any value is enough
+        "work_generate" => json!({
+            "work": "0000000000000000",
+            "difficulty": request["difficulty"].as_str().unwrap_or("fffffff800000000"),
+            "hash": request["hash"],
+        }),
+        "receivable" => {
+            let blocks: serde_json::Map<String, Value> = ledger
+                .receivable
+                .get(&account)
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .map(|(hash, (source, amount))| {
+                            (
+                                hash.clone(),
+                                json!({ "amount": amount.to_string(), "source": source }),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            match blocks.is_empty() {
+                true => json!({ "blocks": "" }),
+                false => json!({ "blocks": blocks }),
+            }
+        }
+        "representatives_online" => json!({
+            "representatives": { REPRESENTATIVE: { "weight": "1000000000000000000000000000000000000" } }
+        }),
+        action => json!({ "error": format!("Unknown action {action}") }),
+    };
+
+    Json(response)
+}
+
+/// Function that creates an empty directory for the files of a test wallet.
+pub fn wallet_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("wallet-core-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Function that creates the wallet of each simulated participant, all pointing to the mock node.
+/// The work is asked to the node so the tests don't spend time generating it.
+pub fn participants(node: &MockNode, count: usize) -> Vec<Wallet> {
+    (0..count)
+        .map(|i| {
+            let mut wallet = Wallet::from_directory(wallet_directory(&format!("participant-{i}")));
+            wallet.config_file.url = node.url().to_string();
+            wallet.config_file.key = String::new();
+            wallet.settings.work.source = routes::WorkSourceKind::Remote;
+            wallet
+        })
+        .collect()
+}
+
+/// Function that runs a key generation session with every participant (the first one coordinates).
+pub async fn create_account(wallets: Vec<Wallet>, threshold: u32) -> Vec<Wallet> {
+    let count = wallets.len() as u32;
+    let handles: Vec<_> = wallets
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut wallet)| {
+            let path = wallet
+                .audit_directory
+                .with_file_name("account.json")
+                .to_string_lossy()
+                .to_string();
+            tokio::spawn(async move {
+                let (role, group) = match i {
+                    0 => (Role::Coordinator, FrostState::new(count, threshold)),
+                    _ => (
+                        Role::Participant("localhost".to_string()),
+                        FrostState::new(0, 0),
+                    ),
+                };
+                let (session, _) = Session::new();
+                session
+                    .create_account(&mut wallet, &role, group, &path)
+                    .await
+                    .map(|_| wallet)
+            })
+        })
+        .collect();
+
+    let mut wallets = Vec::new();
+    for handle in handles {
+        wallets.push(handle.await.unwrap().unwrap());
+    }
+    wallets
+}
+
+/// Function that signs a proposal with every participant given (the first one coordinates).
+pub async fn sign(wallets: Vec<Wallet>, proposal: Proposal) -> (Vec<Wallet>, Vec<String>) {
+    let handles: Vec<_> = wallets
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut wallet)| {
+            let proposal = proposal.clone();
+            tokio::spawn(async move {
+                let role = match i {
+                    0 => Role::Coordinator,
+                    _ => Role::Participant("localhost".to_string()),
+                };
+                let (session, _) = Session::new();
+                let hash = session.sign(&mut wallet, &role, &proposal).await;
+                (wallet, hash)
+            })
+        })
+        .collect();
+
+    let mut wallets = Vec::new();
+    let mut hashes = Vec::new();
+    for handle in handles {
+        let (wallet, hash) = handle.await.unwrap();
+        wallets.push(wallet);
+        hashes.push(hash.unwrap());
+    }
+    (wallets, hashes)
+}
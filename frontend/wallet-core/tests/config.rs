@@ -0,0 +1,152 @@
+//! Tests for the configuration files and the secret store.
+
+mod common;
+
+use common::wallet_directory;
+use std::{fs, path::PathBuf};
+use wallet_core::{
+    backup_path, AccountSettings, SecretStore, Wallet, CONFIG_VERSION, PORT, SECRETS_FILE,
+    SECRETS_KEY_FILE, SECRET_STORE_VARIABLE,
+};
+
+#[test]
+fn settings_that_cant_be_read_are_reported_and_kept() {
+    let directory = wallet_directory("broken-settings");
+    let settings = directory.join("settings.json");
+    fs::write(&settings, "{ \"websocket_url\": ").unwrap();
+
+    let wallet = Wallet::from_directory(directory.clone());
+    assert_eq!(wallet.config_errors.len(), 1);
+    assert!(wallet.config_errors[0].contains("settings.json"));
+    assert_eq!(
+        fs::read_to_string(&settings).unwrap(),
+        "{ \"websocket_url\": "
+    );
+}
+
+#[test]
+fn settings_without_a_version_are_upgraded_with_a_backup() {
+    let directory = wallet_directory("legacy-settings");
+    let settings = directory.join("settings.json");
+    let legacy = r#"{ "websocket_url": "wss://node.example" }"#;
+    fs::write(&settings, legacy).unwrap();
+
+    let wallet = Wallet::from_directory(directory.clone());
+    assert!(wallet.config_errors.is_empty());
+    assert_eq!(wallet.settings.version, CONFIG_VERSION);
+    assert_eq!(wallet.settings.websocket_url, "wss://node.example");
+
+    let upgraded: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&settings).unwrap()).unwrap();
+    assert_eq!(upgraded["version"], CONFIG_VERSION);
+    assert_eq!(fs::read_to_string(backup_path(&settings)).unwrap(), legacy);
+}
+
+#[test]
+fn newer_and_invalid_settings_are_not_written() {
+    let directory = wallet_directory("newer-settings");
+    let settings = directory.join("settings.json");
+    fs::write(&settings, r#"{ "version": 99 }"#).unwrap();
+
+    let wallet = Wallet::from_directory(directory.clone());
+    assert_eq!(wallet.config_errors.len(), 1);
+    assert!(wallet.save_settings().is_err());
+    assert_eq!(
+        fs::read_to_string(&settings).unwrap(),
+        r#"{ "version": 99 }"#
+    );
+
+    let mut wallet = Wallet::from_directory(wallet_directory("invalid-settings"));
+    wallet.settings.websocket_url = "node.example".to_string();
+    wallet.settings.daemon.url = "http://127.0.0.1:6706".to_string();
+    assert_eq!(wallet.validate().len(), 2);
+    assert!(wallet.save_settings().is_err());
+    assert!(!PathBuf::from(&wallet.settings_path).exists());
+}
+
+#[test]
+fn account_settings_replace_the_global_ones() {
+    // the test must not write to the secret store of the computer running it
+    std::env::set_var(SECRET_STORE_VARIABLE, "file");
+    let directory = wallet_directory("account-settings");
+    let account = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+    let mut wallet = Wallet::from_directory(directory.clone());
+    wallet.settings.broadcast_nodes = vec!["https://backup.example".to_string()];
+    wallet.nano_account = account.to_string();
+    assert_eq!(wallet.node().url, wallet.config_file.url);
+    assert_eq!(wallet.port(), PORT);
+
+    let overrides = AccountSettings {
+        url: Some("https://team.example".to_string()),
+        currency: Some("usd".to_string()),
+        port: Some(7000),
+        ..AccountSettings::default()
+    };
+    wallet
+        .save_account_settings(overrides.clone(), "team-key")
+        .unwrap();
+    assert_eq!(wallet.node().url, "https://team.example");
+    assert_eq!(wallet.node().key, "team-key");
+    assert_eq!(wallet.broadcast_nodes(), vec!["https://backup.example"]);
+    assert_eq!(wallet.price_settings().currency, "usd");
+    assert_eq!(wallet.port(), 7000);
+
+    let mut reloaded = Wallet::from_directory(directory.clone());
+    reloaded.nano_account = account.to_string();
+    assert_eq!(reloaded.account_settings(), overrides);
+    assert_eq!(
+        reloaded.secrets.get(&format!("api_key:{account}")),
+        Ok(Some("team-key".to_string()))
+    );
+
+    let invalid = AccountSettings {
+        port: Some(0),
+        ..AccountSettings::default()
+    };
+    assert!(reloaded.save_account_settings(invalid, "").is_err());
+    reloaded
+        .save_account_settings(AccountSettings::default(), "")
+        .unwrap();
+    assert!(reloaded.settings.accounts.is_empty());
+    assert_eq!(
+        reloaded.secrets.get(&format!("api_key:{account}")),
+        Ok(None)
+    );
+}
+
+#[test]
+fn encrypted_file_keeps_the_secrets_private() {
+    let directory = wallet_directory("secrets");
+    let store = SecretStore::encrypted_file(directory.clone());
+
+    assert_eq!(store.get("api_key"), Ok(None));
+    store.set("api_key", "node-api-key").unwrap();
+    assert_eq!(store.get("api_key"), Ok(Some("node-api-key".to_string())));
+
+    let contents = fs::read_to_string(directory.join(SECRETS_FILE)).unwrap();
+    assert!(!contents.contains("node-api-key"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(directory.join(SECRETS_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // a secret can't be moved to another name
+    let moved = contents.replace("\"api_key\"", "\"other\"");
+    fs::write(directory.join(SECRETS_FILE), moved).unwrap();
+    assert!(store.get("other").is_err());
+
+    store.delete("other").unwrap();
+    assert_eq!(store.get("other"), Ok(None));
+
+    // without its key the file can't be read, and reading it doesn't create another key
+    store.set("api_key", "node-api-key").unwrap();
+    fs::remove_file(directory.join(SECRETS_KEY_FILE)).unwrap();
+    assert!(store.get("api_key").unwrap_err().contains(SECRETS_KEY_FILE));
+    assert!(!directory.join(SECRETS_KEY_FILE).exists());
+}
@@ -0,0 +1,127 @@
+//! Tests for the daemon's API and its client.
+
+mod common;
+
+use common::{create_account, participants, sign, MockNode, REPRESENTATIVE};
+use routes::{nano_to_raw, raw_to_nano, RAW_PER_NANO};
+use std::time::Duration;
+use wallet_core::{Daemon, DaemonClient, Proposal, Role, SessionInfo, SessionStatus};
+
+const TOKEN: &str = "4c0b1f1b8f7e4d0a9e2f3a6b5c4d3e2f";
+const ACCOUNT: &str = "nano_3b54jp7j8ewcdmwpg1fjrr9ke11tgcwdjsd1dif44arruk65bfy3qtrycnmh";
+
+#[tokio::test]
+async fn daemon_refuses_requests_without_the_token() {
+    let daemon = Daemon::start(0, TOKEN, Vec::new()).await.unwrap();
+
+    let client = DaemonClient::new(daemon.url(), "wrong");
+    assert_eq!(
+        client.balance(ACCOUNT).await,
+        Err("Invalid token.".to_string())
+    );
+
+    let client = DaemonClient::new(daemon.url(), "");
+    assert_eq!(
+        client.session_status(1).await,
+        Err("Invalid token.".to_string())
+    );
+}
+
+#[tokio::test]
+async fn daemon_only_answers_about_its_accounts_and_sessions() {
+    let daemon = Daemon::start(0, TOKEN, Vec::new()).await.unwrap();
+    let client = DaemonClient::new(daemon.url(), TOKEN);
+
+    assert_eq!(
+        client.history(ACCOUNT, 10).await,
+        Err("The account isn't open in the daemon.".to_string())
+    );
+    assert_eq!(
+        client.propose_send(ACCOUNT, ACCOUNT, "1").await,
+        Err("The account isn't open in the daemon.".to_string())
+    );
+    assert_eq!(
+        client.session_status(1).await,
+        Err("The session doesn't exist.".to_string())
+    );
+    assert_eq!(
+        client
+            .sign(ACCOUNT, &Role::Coordinator, &Proposal::change(ACCOUNT))
+            .await,
+        Err("The daemon only coordinates sends.".to_string())
+    );
+}
+
+/// Function that asks the daemon about a session until it finishes.
+async fn wait_for_session(client: &DaemonClient, session_id: u64) -> SessionInfo {
+    for _ in 0..600 {
+        let info = client.session_status(session_id).await.unwrap();
+        if info.status.is_finished() {
+            return info;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The session {session_id} didn't finish.");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn daemons_read_the_account_and_sign_a_send_together() {
+    let node = MockNode::start().await;
+    let wallets = create_account(participants(&node, 3), 2).await;
+    let account = wallets[0].nano_account.clone();
+
+    // the account is opened before the daemons keep it, and a second payment is left receivable
+    node.fund(&account, 5 * RAW_PER_NANO);
+    let (mut wallets, open) =
+        sign(wallets.into_iter().take(2).collect(), Proposal::receive()).await;
+    node.fund(&account, 2 * RAW_PER_NANO);
+
+    // each participant keeps the account open in its own daemon
+    let peer_daemon = Daemon::start(0, TOKEN, vec![wallets.pop().unwrap()])
+        .await
+        .unwrap();
+    let daemon = Daemon::start(0, TOKEN, wallets).await.unwrap();
+    let client = DaemonClient::new(daemon.url(), TOKEN);
+    let peer = DaemonClient::new(peer_daemon.url(), TOKEN);
+
+    let balance = client.balance(&account).await.unwrap();
+    assert_eq!(balance.account, account);
+    assert_eq!(balance.balance, raw_to_nano(5 * RAW_PER_NANO));
+    assert_eq!(balance.receivable, raw_to_nano(2 * RAW_PER_NANO));
+    let history = peer.history(&account, 10).await.unwrap();
+    assert_eq!(history.history.len(), 1);
+    assert_eq!(history.history[0].hash, open[0]);
+    assert_eq!(history.history[0].amount, raw_to_nano(5 * RAW_PER_NANO));
+
+    // the daemon coordinates the send and the peer joins it with the same proposal
+    let proposed = client
+        .propose_send(&account, REPRESENTATIVE, "1.5")
+        .await
+        .unwrap();
+    assert_eq!(proposed.status, SessionStatus::Preparing);
+    let joined = peer
+        .join_session(&account, "localhost", &proposed.proposal)
+        .await
+        .unwrap();
+
+    let coordinated = wait_for_session(&client, proposed.session_id).await;
+    let signed = wait_for_session(&peer, joined.session_id).await;
+    for info in [&coordinated, &signed] {
+        assert_eq!(info.status, SessionStatus::Finished, "{info:?}");
+    }
+    assert!(coordinated.hash.is_some());
+    assert_eq!(coordinated.hash, signed.hash);
+
+    let sent = nano_to_raw("1.5").unwrap();
+    assert_eq!(node.balance(&account), 5 * RAW_PER_NANO - sent);
+    assert_eq!(node.receivable(REPRESENTATIVE), vec![sent]);
+    assert_eq!(
+        client.history(&account, 10).await.unwrap().history[0].hash,
+        coordinated.hash.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn daemon_needs_a_token() {
+    assert!(Daemon::start(0, "", Vec::new()).await.is_err());
+}
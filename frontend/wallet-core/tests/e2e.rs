@@ -0,0 +1,87 @@
+//! End-to-end tests that run the group's sessions against the mock node, through the same code paths as the
+//! desktop application: key generation with every participant, then open, receive and send blocks.
+
+mod common;
+
+use common::{create_account, participants, sign, MockNode, REPRESENTATIVE};
+use routes::{
+    account_to_public_key, get_receivable, nano_to_raw, process_block, AccountState, StateBlock,
+    RAW_PER_NANO,
+};
+use wallet_core::{Proposal, Wallet};
+
+/// Constant value for the number of simulated participants.
+const PARTICIPANTS: usize = 3;
+
+/// Constant value for the number of participants needed to sign.
+const THRESHOLD: u32 = 2;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn group_creates_an_account_and_signs_open_receive_and_send() {
+    let node = MockNode::start().await;
+
+    // every participant ends the key generation with the same account
+    let mut wallets = create_account(participants(&node, PARTICIPANTS), THRESHOLD).await;
+    let account = wallets[0].nano_account.clone();
+    assert!(wallets.iter().all(|wallet| wallet.nano_account == account));
+    assert!(wallets
+        .iter()
+        .all(|wallet| wallet.frost_state.threshold == THRESHOLD));
+    let signers: Vec<Wallet> = wallets.drain(..THRESHOLD as usize).collect();
+
+    node.fund(&account, 5 * RAW_PER_NANO);
+    node.fund(&account, 2 * RAW_PER_NANO);
+
+    // the first receive opens the account, the second one receives the rest
+    let (signers, open) = sign(signers, Proposal::receive()).await;
+    let (signers, receive) = sign(signers, Proposal::receive()).await;
+    assert_eq!(node.balance(&account), 7 * RAW_PER_NANO);
+
+    let (_, send) = sign(signers, Proposal::send(REPRESENTATIVE, "1.5")).await;
+    let sent = nano_to_raw("1.5").unwrap();
+    assert_eq!(node.balance(&account), 7 * RAW_PER_NANO - sent);
+    assert_eq!(node.receivable(REPRESENTATIVE), vec![sent]);
+
+    // every participant agreed on each block, and every published block is signed by the aggregated key
+    for hashes in [&open, &receive, &send] {
+        assert!(hashes.iter().all(|hash| *hash == hashes[0]));
+    }
+    let published = node.published();
+    let hashes: Vec<String> = published
+        .iter()
+        .map(|block| block.hash_hex().unwrap())
+        .collect();
+    assert_eq!(
+        hashes,
+        vec![open[0].clone(), receive[0].clone(), send[0].clone()]
+    );
+
+    let public_key = account_to_public_key(&account).unwrap();
+    for block in &published {
+        block.verify_signature(&public_key).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn mock_node_only_accepts_blocks_signed_by_the_account() {
+    let node = MockNode::start().await;
+    let hash = node.fund(REPRESENTATIVE, RAW_PER_NANO);
+
+    let receivable = get_receivable(node.url(), "", REPRESENTATIVE, 10)
+        .await
+        .unwrap()
+        .sorted();
+    assert_eq!(receivable.len(), 1);
+    assert_eq!(receivable[0].0, hash);
+
+    let state = AccountState {
+        account: REPRESENTATIVE.to_string(),
+        frontier: None,
+        balance: 0,
+        representative: REPRESENTATIVE.to_string(),
+    };
+    let block = StateBlock::receive(&state, &hash, RAW_PER_NANO).unwrap();
+    assert!(process_block(node.url(), "", "open", &block).await.is_err());
+    assert!(node.published().is_empty());
+    assert_eq!(node.receivable(REPRESENTATIVE), vec![RAW_PER_NANO]);
+}
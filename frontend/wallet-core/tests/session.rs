@@ -0,0 +1,244 @@
+//! Tests for the wallet and its sessions that don't need the group or a node.
+
+mod common;
+
+use common::wallet_directory;
+use routes::{AccountState, Cadence, Network, NetworkSettings, PayoutBatch, ScheduledPayment, DAY};
+use std::{fs, path::PathBuf};
+use wallet_core::{
+    desktop_entry, single_instance, start_session_relay, uri_argument, ChainKind, ChainProposal,
+    Instance, Proposal, ReceivableSource, Role, Session, SessionStatus, TransactionKind, Wallet,
+    CHAIN_MISMATCH_ERROR,
+};
+
+#[test]
+fn open_account_reports_missing_and_invalid_files() {
+    let directory = wallet_directory("open");
+    let mut wallet = Wallet::from_directory(directory.clone());
+
+    let missing = directory.join("missing.json");
+    assert_eq!(
+        wallet.open_account(missing.to_str().unwrap()),
+        Err("Couldn't open the file.".to_string())
+    );
+
+    let invalid = directory.join("invalid.json");
+    fs::write(&invalid, "{}").unwrap();
+    assert_eq!(
+        wallet.open_account(invalid.to_str().unwrap()),
+        Err("File has invalid format.".to_string())
+    );
+    assert!(wallet.account_path.is_empty());
+}
+
+#[test]
+fn proposals_are_built_from_the_transaction_names() {
+    assert_eq!(
+        TransactionKind::from_name("send"),
+        Some(TransactionKind::Send)
+    );
+    assert_eq!(TransactionKind::from_name("RECEIVE ALL"), None);
+
+    let proposal = Proposal::send("nano_1receiver", "1.5");
+    assert_eq!(proposal.kind, TransactionKind::Send);
+    assert_eq!(proposal.amount, "1.5");
+    assert_eq!(Proposal::change("nano_1rep").representative, "nano_1rep");
+
+    assert_eq!(Role::Coordinator.coordinator(), None);
+    assert_eq!(
+        Role::Participant("10.0.0.2".to_string()).coordinator(),
+        Some("10.0.0.2")
+    );
+}
+
+#[test]
+fn payment_requests_are_paid_on_the_network_of_the_wallet() {
+    let account = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+    let uri = format!("nano:{account}?amount=1500000000000000000000000000001&label=Rent");
+    let mut wallet = Wallet::from_directory(wallet_directory("payment"));
+
+    let request = wallet.payment_request(&uri).unwrap();
+    assert_eq!(request.account, account);
+    assert_eq!(
+        request.amount_in_nano(),
+        Some("1.500000000000000000000000000001".to_string())
+    );
+    assert_eq!(request.description(), Some("Rent".to_string()));
+
+    wallet.settings.network = NetworkSettings {
+        network: Network::Dev,
+        prefix: "test".to_string(),
+    };
+    assert!(wallet.payment_request(&uri).is_err());
+
+    let arguments = [
+        "aokiji".to_string(),
+        "--flag".to_string(),
+        uri.to_uppercase(),
+    ];
+    assert_eq!(uri_argument(arguments), Some(uri.to_uppercase()));
+    assert_eq!(uri_argument(["aokiji".to_string()]), None);
+    assert!(desktop_entry(std::path::Path::new("/opt/Aokiji.AppImage"))
+        .contains("Exec=\"/opt/Aokiji.AppImage\" %u\nIcon=aokiji"));
+}
+
+#[test]
+fn scheduled_payments_belong_to_the_open_account() {
+    let account = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+    let receiver = "nano_1111111111111111111111111111111111111111111111111111hifc8npp";
+    let directory = wallet_directory("schedule");
+    let mut wallet = Wallet::from_directory(directory.clone());
+    let start = routes::current_time() - DAY;
+
+    let payment = ScheduledPayment::new(account, receiver, "1", "Rent", Cadence::Monthly, start);
+    assert!(wallet.schedule_payment(payment.clone()).is_err());
+
+    wallet.nano_account = account.to_string();
+    let test_receiver = ScheduledPayment {
+        receivers_account: receiver.replacen("nano_", "test_", 1),
+        ..payment.clone()
+    };
+    assert!(wallet.schedule_payment(test_receiver).is_err());
+    wallet.schedule_payment(payment.clone()).unwrap();
+    assert_eq!(wallet.due_payments(), vec![(payment.clone(), start)]);
+    assert_eq!(wallet.due_payment(&payment.id), Ok((payment, start)));
+    assert!(wallet.due_payment("unknown").is_err());
+
+    // another account of the same wallet has its own schedule
+    let mut other = Wallet::from_directory(directory);
+    other.nano_account = receiver.to_string();
+    assert!(other.due_payments().is_empty());
+}
+
+#[tokio::test]
+async fn session_reports_failure_when_the_node_is_unreachable() {
+    let mut wallet = Wallet::from_directory(wallet_directory("unreachable"));
+    wallet.config_file.url = "http://127.0.0.1:1".to_string();
+
+    let (session, mut status) = Session::new();
+    let result = session
+        .sign(&mut wallet, &Role::Coordinator, &Proposal::receive())
+        .await;
+    assert!(result.is_err());
+    drop(session);
+
+    let mut statuses = Vec::new();
+    while let Some(status) = status.recv().await {
+        statuses.push(status);
+    }
+    assert_eq!(statuses.first(), Some(&SessionStatus::Preparing));
+    assert!(matches!(statuses.last(), Some(SessionStatus::Failed(_))));
+}
+
+#[tokio::test]
+async fn each_session_has_its_own_relay_file() {
+    let wallet = Wallet::from_directory(wallet_directory("relay"));
+    let first = start_session_relay(&wallet).await.unwrap();
+    let second = start_session_relay(&wallet).await.unwrap();
+
+    // frost-sig reads the link of its own relay, never the one of another session
+    assert_ne!(first.config_file_path, second.config_file_path);
+    let config = fs::read_to_string(&first.config_file_path).unwrap();
+    assert!(config.contains(first.relay.url()));
+    assert!(!config.contains(second.relay.url()));
+
+    let path = first.config_file_path.clone();
+    drop(first);
+    assert!(!PathBuf::from(path).exists());
+    assert!(PathBuf::from(&second.config_file_path).exists());
+}
+
+#[test]
+fn chain_is_only_signed_if_every_participant_builds_it() {
+    let account = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+    let state = AccountState {
+        account: account.to_string(),
+        frontier: None,
+        balance: 0,
+        representative: String::new(),
+    };
+    let sources = ["AB", "CD"]
+        .iter()
+        .map(|hash| ReceivableSource {
+            hash: hash.repeat(32),
+            amount: "1000".to_string(),
+        })
+        .collect();
+    let (proposal, blocks) = ChainProposal::new(
+        &state,
+        ChainKind::Receive {
+            sources,
+            representative: account.to_string(),
+        },
+    )
+    .unwrap();
+
+    // the first block opens the account and every block builds on the one before it
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].block.subtype(&blocks[0].previous_state), "open");
+    assert_eq!(
+        blocks[1].block.subtype(&blocks[1].previous_state),
+        "receive"
+    );
+    assert_eq!(blocks[1].block.previous, blocks[0].hash);
+    assert_eq!(blocks[1].block.balance, 2000);
+    assert_eq!(proposal.verify(&state).unwrap(), blocks);
+
+    // a participant whose node knows another frontier, or a tampered list of hashes, refuses the chain
+    let opened = AccountState {
+        frontier: Some("EF".repeat(32)),
+        representative: account.to_string(),
+        ..state.clone()
+    };
+    assert_eq!(
+        proposal.verify(&opened).unwrap_err(),
+        CHAIN_MISMATCH_ERROR.to_string()
+    );
+    let mut tampered = proposal.clone();
+    tampered.hashes.reverse();
+    assert_eq!(
+        tampered.verify(&state).unwrap_err(),
+        CHAIN_MISMATCH_ERROR.to_string()
+    );
+
+    // the payouts of a batch are sent from the balance left by the block before
+    let opened = AccountState {
+        balance: 3000,
+        ..opened
+    };
+    let batch = PayoutBatch::parse(&format!(
+        "{account},0.000000000000000000000000001\n{account},0.000000000000000000000000002"
+    ))
+    .unwrap();
+    let (proposal, blocks) = ChainProposal::new(
+        &opened,
+        ChainKind::Send {
+            payouts: batch.payouts,
+        },
+    )
+    .unwrap();
+    let balances: Vec<_> = blocks.iter().map(|block| block.block.balance).collect();
+    assert_eq!(balances, [2000, 0]);
+    assert_eq!(blocks[0].block.subtype(&blocks[0].previous_state), "send");
+    assert_eq!(proposal.verify(&opened).unwrap(), blocks);
+}
+
+#[tokio::test]
+async fn links_are_forwarded_to_the_running_instance() {
+    let directory = wallet_directory("instance");
+    let uri = "nano:nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+
+    let Ok(Instance::Primary(mut links)) = single_instance(&directory, None) else {
+        panic!("the first instance must keep running");
+    };
+    assert!(matches!(
+        single_instance(&directory, Some(uri)),
+        Ok(Instance::Forwarded)
+    ));
+    assert!(matches!(
+        single_instance(&directory, None),
+        Ok(Instance::Forwarded)
+    ));
+    assert_eq!(links.recv().await, Some(uri.to_string()));
+    assert_eq!(links.recv().await, Some(String::new()));
+}